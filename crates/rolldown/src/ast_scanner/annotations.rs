@@ -0,0 +1,63 @@
+use oxc::{
+  ast::{CommentKind, Trivias},
+  span::Span,
+};
+use rustc_hash::FxHashSet;
+
+/// Positions of the code annotated with `/*#__PURE__*/` or `/*#__NO_SIDE_EFFECTS__*/`. `@` can be used
+/// instead of `#`.
+#[derive(Debug, Default)]
+pub struct Annotations {
+  pure: FxHashSet<u32>,
+  no_side_effects: FxHashSet<u32>,
+}
+
+impl Annotations {
+  pub fn new(source: &str, trivias: &Trivias) -> Self {
+    let mut annotations = Self::default();
+    for (kind, span) in trivias.comments() {
+      let text = span.source_text(source).trim();
+      let annotated = match text.strip_prefix(|c| c == '#' || c == '@') {
+        Some("__PURE__") => &mut annotations.pure,
+        Some("__NO_SIDE_EFFECTS__") => &mut annotations.no_side_effects,
+        _ => continue,
+      };
+      annotated.insert(next_token_start(source, trivias, comment_end(kind, span)));
+    }
+    annotations
+  }
+
+  /// Whether the call or `new` expression starting at `span` is annotated with `/*#__PURE__*/`.
+  pub fn is_pure(&self, span: Span) -> bool {
+    self.pure.contains(&span.start)
+  }
+
+  /// Whether the declaration starting at `span` is annotated with `/*#__NO_SIDE_EFFECTS__*/`.
+  pub fn is_no_side_effects(&self, span: Span) -> bool {
+    self.no_side_effects.contains(&span.start)
+  }
+}
+
+/// The spans of comments don't include `//`, `/*` and `*/`.
+fn comment_end(kind: CommentKind, span: Span) -> u32 {
+  match kind {
+    CommentKind::SingleLine => span.end,
+    CommentKind::MultiLine => span.end + 2,
+  }
+}
+
+/// Skips the whitespace and comments starting at `pos`.
+fn next_token_start(source: &str, trivias: &Trivias, mut pos: u32) -> u32 {
+  loop {
+    let rest = &source[pos as usize..];
+    let trimmed = rest.trim_start();
+    pos += u32::try_from(rest.len() - trimmed.len()).unwrap();
+    if !trimmed.starts_with("//") && !trimmed.starts_with("/*") {
+      return pos;
+    }
+    let Some((start, comment)) = trivias.comments_range(pos + 2..=pos + 2).next() else {
+      return pos;
+    };
+    pos = comment_end(comment.kind, Span::new(*start, comment.end));
+  }
+}
@@ -0,0 +1,113 @@
+//! Static assignments of commonjs exports, like `exports.foo = value`. If a module doesn't use `exports` and
+//! `module` otherwise, importers only depend on the assignments of the exports they read, so the other ones
+//! can be dropped.
+
+use oxc::{
+  ast::ast::{
+    Argument, AssignmentTarget, Expression, MemberExpression, SimpleAssignmentTarget, Statement,
+  },
+  syntax::operator::AssignmentOperator,
+};
+use rolldown_rstr::{Rstr, ToRstr};
+
+use super::AstScanner;
+
+impl<'ast> AstScanner<'ast> {
+  /// Whether `expr` is `exports` or `module.exports`.
+  fn is_commonjs_exports_object(&self, expr: &Expression<'ast>) -> bool {
+    match expr {
+      Expression::Identifier(ident) => {
+        ident.name == "exports" && self.is_unresolved_reference(ident)
+      }
+      Expression::MemberExpression(member_expr) => match &**member_expr {
+        MemberExpression::StaticMemberExpression(member_expr) => {
+          member_expr.property.name == "exports"
+            && matches!(
+              &member_expr.object,
+              Expression::Identifier(ident)
+                if ident.name == "module" && self.is_unresolved_reference(ident)
+            )
+        }
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  /// `exports.foo = value` or `module.exports.foo = value`. Returns the name of the export and the value.
+  fn commonjs_export_assignment<'a>(
+    &self,
+    expr: &'a Expression<'ast>,
+  ) -> Option<(Rstr, &'a Expression<'ast>)> {
+    let Expression::AssignmentExpression(assign_expr) = expr else {
+      return None;
+    };
+    if assign_expr.operator != AssignmentOperator::Assign {
+      return None;
+    }
+    let AssignmentTarget::SimpleAssignmentTarget(SimpleAssignmentTarget::MemberAssignmentTarget(
+      member_expr,
+    )) = &assign_expr.left
+    else {
+      return None;
+    };
+    let MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+      return None;
+    };
+    self
+      .is_commonjs_exports_object(&member_expr.object)
+      .then(|| (member_expr.property.name.to_rstr(), &assign_expr.right))
+  }
+
+  /// `Object.defineProperty(exports, 'foo', descriptor)`. Returns the name of the export and the descriptor.
+  fn commonjs_export_definition<'a>(
+    &self,
+    expr: &'a Expression<'ast>,
+  ) -> Option<(Rstr, &'a Expression<'ast>)> {
+    let Expression::CallExpression(call_expr) = expr else {
+      return None;
+    };
+    let Expression::MemberExpression(callee) = &call_expr.callee else {
+      return None;
+    };
+    let MemberExpression::StaticMemberExpression(callee) = &**callee else {
+      return None;
+    };
+    let Expression::Identifier(object) = &callee.object else {
+      return None;
+    };
+    if object.name != "Object"
+      || callee.property.name != "defineProperty"
+      || !self.is_unresolved_reference(object)
+    {
+      return None;
+    }
+    let [Argument::Expression(target), Argument::Expression(Expression::StringLiteral(name)), Argument::Expression(descriptor)] =
+      call_expr.arguments.as_slice()
+    else {
+      return None;
+    };
+    self.is_commonjs_exports_object(target).then(|| (name.value.to_rstr(), descriptor))
+  }
+
+  /// The names of the exports assigned by `stmt` and the assigned value. Chained assignments like
+  /// `exports.foo = exports.bar = void 0`, which TypeScript emits, assign all of their names.
+  pub(super) fn commonjs_exports_of_stmt<'a>(
+    &self,
+    stmt: &'a Statement<'ast>,
+  ) -> Option<(Vec<Rstr>, &'a Expression<'ast>)> {
+    let Statement::ExpressionStatement(expr_stmt) = stmt else {
+      return None;
+    };
+    if let Some((name, descriptor)) = self.commonjs_export_definition(&expr_stmt.expression) {
+      return Some((vec![name], descriptor));
+    }
+    let (name, mut value) = self.commonjs_export_assignment(&expr_stmt.expression)?;
+    let mut names = vec![name];
+    while let Some((name, next_value)) = self.commonjs_export_assignment(value) {
+      names.push(name);
+      value = next_value;
+    }
+    Some((names, value))
+  }
+}
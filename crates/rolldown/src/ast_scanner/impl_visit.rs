@@ -1,25 +1,169 @@
 use std::sync::Arc;
 
 use oxc::{
-  ast::{ast::IdentifierReference, visit::walk::walk_statement, Visit},
+  ast::{
+    ast::{
+      BindingPatternKind, Declaration, ExportDefaultDeclarationKind, Expression,
+      IdentifierReference, MemberExpression, ModuleDeclaration, Program, Statement,
+      UnaryExpression,
+    },
+    visit::walk::{walk_expression, walk_statement, walk_unary_expression},
+    Visit,
+  },
   codegen::{self, Codegen, CodegenOptions, Gen},
+  span::GetSpan,
+  syntax::operator::UnaryOperator,
 };
-use rolldown_common::ImportKind;
+use rolldown_common::{ImportKind, NamespaceMemberRef, Specifier};
 use rolldown_error::BuildError;
+use rolldown_oxc_utils::BindingIdentifierExt;
+use rolldown_rstr::ToRstr;
 
 use super::{side_effect_detector::SideEffectDetector, AstScanner};
 
 impl<'ast> AstScanner<'ast> {
   fn visit_top_level_stmt(&mut self, stmt: &oxc::ast::ast::Statement<'ast>) {
-    self.current_stmt_info.side_effect =
-      SideEffectDetector::new(self.scope).detect_side_effect_of_stmt(stmt);
-    self.visit_statement(stmt);
+    let commonjs_exports = self.commonjs_exports_of_stmt(stmt);
+    let mut detector =
+      SideEffectDetector::new(self.scope, &self.annotations, self.unknown_global_side_effects);
+    let side_effect = match &commonjs_exports {
+      // Assigning an export isn't observable without reading it, except for `__esModule`, which is read by
+      // `__toESM` of every importer
+      Some((names, value)) => {
+        names.iter().any(|name| name.as_str() == "__esModule")
+          || detector.detect_side_effect_of_expr(value)
+      }
+      None => detector.detect_side_effect_of_stmt(stmt),
+    };
+    let no_side_effects_functions = &self.symbol_table.no_side_effects_functions;
+    let side_effect_calls = detector
+      .calls
+      .into_iter()
+      .filter(|symbol_id| !no_side_effects_functions.contains(symbol_id))
+      .map(|symbol_id| (self.idx, symbol_id).into())
+      .collect::<Vec<_>>();
+    self.current_stmt_info.side_effect = side_effect || !side_effect_calls.is_empty();
+    if !side_effect {
+      // Calls of imported functions are resolved after imports are bound
+      self.current_stmt_info.side_effect_calls = side_effect_calls;
+    }
+    if let Some((names, value)) = commonjs_exports {
+      let stmt_info_id = self.result.stmt_infos.next_idx();
+      for name in names {
+        self.result.commonjs_exports.entry(name).or_default().push(stmt_info_id);
+      }
+      self.visit_expression(value);
+    } else {
+      self.visit_statement(stmt);
+    }
+  }
+
+  /// Functions annotated with `@__NO_SIDE_EFFECTS__` need to be known before scanning, since statements
+  /// may call functions declared after them.
+  fn collect_no_side_effects_functions(&mut self, program: &Program<'ast>) {
+    for stmt in &program.body {
+      let is_annotated = self.annotations.is_no_side_effects(stmt.span());
+      match stmt {
+        Statement::Declaration(decl) => self.collect_no_side_effects_decl(decl, is_annotated),
+        Statement::ModuleDeclaration(module_decl) => match &**module_decl {
+          ModuleDeclaration::ExportNamedDeclaration(named_decl) => {
+            if let Some(decl) = &named_decl.declaration {
+              self.collect_no_side_effects_decl(decl, is_annotated);
+            }
+          }
+          ModuleDeclaration::ExportDefaultDeclaration(default_decl) => {
+            let symbol_id = match &default_decl.declaration {
+              ExportDefaultDeclarationKind::FunctionDeclaration(func)
+                if is_annotated || self.annotations.is_no_side_effects(func.span) =>
+              {
+                func.id.as_ref().map(BindingIdentifierExt::expect_symbol_id)
+              }
+              ExportDefaultDeclarationKind::Expression(
+                expr @ (Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_)),
+              ) if is_annotated || self.annotations.is_no_side_effects(expr.span()) => None,
+              _ => continue,
+            };
+            let symbol_id =
+              symbol_id.unwrap_or_else(|| self.result.default_export_ref.unwrap().symbol);
+            self.symbol_table.no_side_effects_functions.insert(symbol_id);
+          }
+          _ => {}
+        },
+        _ => {}
+      }
+    }
+  }
+
+  fn collect_no_side_effects_decl(&mut self, decl: &Declaration<'ast>, is_annotated: bool) {
+    match decl {
+      Declaration::FunctionDeclaration(func)
+        if is_annotated || self.annotations.is_no_side_effects(func.span) =>
+      {
+        if let Some(id) = &func.id {
+          self.symbol_table.no_side_effects_functions.insert(id.expect_symbol_id());
+        }
+      }
+      // `const foo = () => {}`
+      Declaration::VariableDeclaration(var_decl) => {
+        let is_annotated = is_annotated || self.annotations.is_no_side_effects(var_decl.span);
+        for declarator in &var_decl.declarations {
+          if let (
+            BindingPatternKind::BindingIdentifier(id),
+            Some(
+              init @ (Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_)),
+            ),
+          ) = (&declarator.id.kind, &declarator.init)
+          {
+            if is_annotated || self.annotations.is_no_side_effects(init.span()) {
+              self.symbol_table.no_side_effects_functions.insert(id.expect_symbol_id());
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  /// Records `ns.foo` of `import * as ns from './foo'` as a reference to the export `foo`, which is resolved
+  /// at link time, instead of a reference to the whole namespace object. Returns `false` if `expr` isn't
+  /// such a member access.
+  fn try_add_referenced_namespace_member(&mut self, expr: &Expression<'ast>) -> bool {
+    let Expression::MemberExpression(member_expr) = expr else {
+      return false;
+    };
+    let MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+      return false;
+    };
+    let Expression::Identifier(ident) = &member_expr.object else {
+      return false;
+    };
+    if member_expr.optional {
+      return false;
+    }
+    let Some(symbol_id) = self.resolve_symbol_from_reference(ident) else {
+      return false;
+    };
+    let is_star_import = self
+      .result
+      .named_imports
+      .get(&symbol_id)
+      .is_some_and(|import| matches!(import.imported, Specifier::Star));
+    if !is_star_import {
+      return false;
+    }
+    self.current_stmt_info.referenced_namespace_members.push(NamespaceMemberRef {
+      namespace_ref: (self.idx, symbol_id).into(),
+      property_name: member_expr.property.name.to_rstr(),
+      span: member_expr.span,
+    });
+    true
   }
 }
 
 impl<'ast> Visit<'ast> for AstScanner<'ast> {
   #[tracing::instrument(skip_all)]
   fn visit_program(&mut self, program: &oxc::ast::ast::Program<'ast>) {
+    self.collect_no_side_effects_functions(program);
     for (idx, stmt) in program.body.iter().enumerate() {
       self.current_stmt_info.stmt_idx = Some(idx);
       if cfg!(debug_assertions) {
@@ -72,6 +216,24 @@ impl<'ast> Visit<'ast> for AstScanner<'ast> {
     }
   }
 
+  fn visit_expression(&mut self, expr: &Expression<'ast>) {
+    // Assignment targets, like `ns.foo = 1`, aren't expressions and still reference the namespace object
+    if !self.try_add_referenced_namespace_member(expr) {
+      walk_expression(self, expr);
+    }
+  }
+
+  fn visit_unary_expression(&mut self, expr: &UnaryExpression<'ast>) {
+    // `delete ns.foo` can't be rewritten to `delete foo`
+    if expr.operator == UnaryOperator::Delete {
+      if let Expression::MemberExpression(member_expr) = &expr.argument {
+        self.visit_member_expression(member_expr);
+        return;
+      }
+    }
+    walk_unary_expression(self, expr);
+  }
+
   fn visit_statement(&mut self, stmt: &oxc::ast::ast::Statement<'ast>) {
     if let oxc::ast::ast::Statement::ModuleDeclaration(decl) = stmt {
       self.scan_module_decl(decl.0);
@@ -0,0 +1,228 @@
+//! Globals of the JavaScript language that can be read without side effects. Reading other globals may throw
+//! a `ReferenceError` if they don't exist, and reading other properties may call getters.
+
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashSet;
+
+const TYPED_ARRAYS: &[&str] = &[
+  "Int8Array",
+  "Uint8Array",
+  "Uint8ClampedArray",
+  "Int16Array",
+  "Uint16Array",
+  "Int32Array",
+  "Uint32Array",
+  "Float32Array",
+  "Float64Array",
+  "BigInt64Array",
+  "BigUint64Array",
+];
+
+const ERRORS: &[&str] = &[
+  "Error",
+  "AggregateError",
+  "EvalError",
+  "RangeError",
+  "ReferenceError",
+  "SyntaxError",
+  "TypeError",
+  "URIError",
+];
+
+const GLOBALS: &[&str] = &[
+  "undefined",
+  "NaN",
+  "Infinity",
+  "globalThis",
+  "Object",
+  "Function",
+  "Array",
+  "Number",
+  "Boolean",
+  "String",
+  "Symbol",
+  "BigInt",
+  "Date",
+  "RegExp",
+  "Promise",
+  "Proxy",
+  "Map",
+  "Set",
+  "WeakMap",
+  "WeakSet",
+  "ArrayBuffer",
+  "DataView",
+  "Math",
+  "JSON",
+  "Reflect",
+  "Intl",
+  "isFinite",
+  "isNaN",
+  "parseFloat",
+  "parseInt",
+  "decodeURI",
+  "decodeURIComponent",
+  "encodeURI",
+  "encodeURIComponent",
+  "escape",
+  "unescape",
+];
+
+/// The properties of global objects that can be read without side effects, keyed by the path of the object.
+const MEMBERS: &[(&str, &[&str])] = &[
+  (
+    "Object",
+    &[
+      "assign",
+      "create",
+      "defineProperties",
+      "defineProperty",
+      "entries",
+      "freeze",
+      "fromEntries",
+      "getOwnPropertyDescriptor",
+      "getOwnPropertyDescriptors",
+      "getOwnPropertyNames",
+      "getOwnPropertySymbols",
+      "getPrototypeOf",
+      "groupBy",
+      "hasOwn",
+      "is",
+      "isExtensible",
+      "isFrozen",
+      "isSealed",
+      "keys",
+      "preventExtensions",
+      "prototype",
+      "seal",
+      "setPrototypeOf",
+      "values",
+    ],
+  ),
+  (
+    "Object.prototype",
+    &[
+      "constructor",
+      "hasOwnProperty",
+      "isPrototypeOf",
+      "propertyIsEnumerable",
+      "toLocaleString",
+      "toString",
+      "valueOf",
+    ],
+  ),
+  ("Array", &["from", "isArray", "of", "prototype"]),
+  ("Function", &["prototype"]),
+  (
+    "Math",
+    &[
+      "E", "LN10", "LN2", "LOG10E", "LOG2E", "PI", "SQRT1_2", "SQRT2", "abs", "acos", "acosh",
+      "asin", "asinh", "atan", "atan2", "atanh", "cbrt", "ceil", "clz32", "cos", "cosh", "exp",
+      "expm1", "floor", "fround", "hypot", "imul", "log", "log10", "log1p", "log2", "max", "min",
+      "pow", "random", "round", "sign", "sin", "sinh", "sqrt", "tan", "tanh", "trunc",
+    ],
+  ),
+  ("JSON", &["parse", "stringify"]),
+  (
+    "Reflect",
+    &[
+      "apply",
+      "construct",
+      "defineProperty",
+      "deleteProperty",
+      "get",
+      "getOwnPropertyDescriptor",
+      "getPrototypeOf",
+      "has",
+      "isExtensible",
+      "ownKeys",
+      "preventExtensions",
+      "set",
+      "setPrototypeOf",
+    ],
+  ),
+  (
+    "Symbol",
+    &[
+      "asyncIterator",
+      "for",
+      "hasInstance",
+      "isConcatSpreadable",
+      "iterator",
+      "keyFor",
+      "match",
+      "matchAll",
+      "replace",
+      "search",
+      "species",
+      "split",
+      "toPrimitive",
+      "toStringTag",
+      "unscopables",
+    ],
+  ),
+  (
+    "Number",
+    &[
+      "EPSILON",
+      "MAX_SAFE_INTEGER",
+      "MAX_VALUE",
+      "MIN_SAFE_INTEGER",
+      "MIN_VALUE",
+      "NEGATIVE_INFINITY",
+      "NaN",
+      "POSITIVE_INFINITY",
+      "isFinite",
+      "isInteger",
+      "isNaN",
+      "isSafeInteger",
+      "parseFloat",
+      "parseInt",
+    ],
+  ),
+  ("String", &["fromCharCode", "fromCodePoint", "raw"]),
+  ("Promise", &["all", "allSettled", "any", "race", "reject", "resolve"]),
+  ("Date", &["UTC", "now", "parse"]),
+  ("ArrayBuffer", &["isView"]),
+];
+
+/// Constructors that have no side effects when they're called with `new` and without arguments.
+const PURE_CONSTRUCTORS: &[&str] = &[
+  "Object",
+  "Array",
+  "Number",
+  "Boolean",
+  "String",
+  "Date",
+  "RegExp",
+  "Map",
+  "Set",
+  "WeakMap",
+  "WeakSet",
+  "ArrayBuffer",
+];
+
+/// The dotted paths of all the known globals, like `Math` and `Math.PI`.
+static KNOWN_GLOBALS: Lazy<FxHashSet<String>> = Lazy::new(|| {
+  let mut known_globals = FxHashSet::default();
+  known_globals
+    .extend(GLOBALS.iter().chain(TYPED_ARRAYS).chain(ERRORS).map(|name| (*name).to_string()));
+  known_globals.extend(TYPED_ARRAYS.iter().map(|name| format!("{name}.BYTES_PER_ELEMENT")));
+  for (object, properties) in MEMBERS {
+    known_globals.extend(properties.iter().map(|property| format!("{object}.{property}")));
+  }
+  known_globals
+});
+
+/// Whether reading the global `a.b.c`, given as `["a", "b", "c"]`, has no side effects.
+pub fn is_known_global(path: &[&str]) -> bool {
+  KNOWN_GLOBALS.contains(&path.join("."))
+}
+
+/// Whether `new name()` has no side effects.
+pub fn is_pure_constructor(name: &str) -> bool {
+  PURE_CONSTRUCTORS.contains(&name)
+    || TYPED_ARRAYS.contains(&name)
+    // `new AggregateError(errors)` iterates `errors`, which throws if it isn't iterable
+    || (ERRORS.contains(&name) && name != "AggregateError")
+}
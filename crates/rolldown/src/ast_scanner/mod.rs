@@ -1,4 +1,7 @@
+pub mod annotations;
+pub mod commonjs_exports;
 pub mod impl_visit;
+pub mod known_globals;
 pub mod side_effect_detector;
 
 use index_vec::IndexVec;
@@ -8,15 +11,15 @@ use oxc::{
       ExportAllDeclaration, ExportDefaultDeclaration, ExportNamedDeclaration, IdentifierReference,
       ImportDeclaration, ModuleDeclaration, Program,
     },
-    Visit,
+    Trivias, Visit,
   },
   semantic::SymbolId,
   span::{Atom, Span},
 };
 use rolldown_common::{
   representative_name, AstScope, ExportsKind, FilePath, ImportKind, ImportRecordId, LocalExport,
-  ModuleType, NamedImport, NormalModuleId, RawImportRecord, Specifier, StmtInfo, StmtInfos,
-  SymbolRef,
+  ModuleType, NamedImport, NormalModuleId, RawImportRecord, Specifier, StmtInfo, StmtInfoId,
+  StmtInfos, SymbolRef,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::{BindingIdentifierExt, BindingPatternExt};
@@ -24,6 +27,7 @@ use rolldown_rstr::{Rstr, ToRstr};
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
+use self::annotations::Annotations;
 use super::types::ast_symbols::AstSymbols;
 
 #[derive(Debug, Default)]
@@ -37,9 +41,13 @@ pub struct ScanResult {
   pub default_export_ref: Option<SymbolRef>,
   pub imports: FxHashMap<Span, ImportRecordId>,
   pub exports_kind: ExportsKind,
+  /// The statements assigning each commonjs export, like `exports.foo = value`. Only collected if the module
+  /// doesn't use `exports` and `module` otherwise.
+  pub commonjs_exports: FxHashMap<Rstr, Vec<StmtInfoId>>,
   pub warnings: Vec<BuildError>,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct AstScanner<'a> {
   idx: NormalModuleId,
   source: &'a Arc<str>,
@@ -54,6 +62,8 @@ pub struct AstScanner<'a> {
   pub namespace_ref: SymbolRef,
   used_exports_ref: bool,
   used_module_ref: bool,
+  annotations: Annotations,
+  unknown_global_side_effects: bool,
 }
 
 impl<'ast> AstScanner<'ast> {
@@ -93,18 +103,30 @@ impl<'ast> AstScanner<'ast> {
       namespace_ref,
       used_exports_ref: false,
       used_module_ref: false,
+      annotations: Annotations::default(),
+      unknown_global_side_effects: true,
       source,
       file_path,
     }
   }
 
-  pub fn scan(mut self, program: &Program<'ast>) -> ScanResult {
+  /// Whether reading a global that isn't a known global of the language is a side effect. Defaults to `true`.
+  pub fn with_unknown_global_side_effects(mut self, unknown_global_side_effects: bool) -> Self {
+    self.unknown_global_side_effects = unknown_global_side_effects;
+    self
+  }
+
+  pub fn scan(mut self, program: &Program<'ast>, trivias: &Trivias) -> ScanResult {
+    self.annotations = Annotations::new(self.source, trivias);
     self.visit_program(program);
     let mut exports_kind = ExportsKind::None;
 
     if self.esm_export_keyword.is_some() {
       exports_kind = ExportsKind::Esm;
-    } else if self.used_exports_ref || self.used_module_ref {
+    } else if self.used_exports_ref
+      || self.used_module_ref
+      || !self.result.commonjs_exports.is_empty()
+    {
       exports_kind = ExportsKind::CommonJs;
     } else {
       // TODO(hyf0): Should add warnings if the module type doesn't satisfy the exports kind.
@@ -123,6 +145,13 @@ impl<'ast> AstScanner<'ast> {
       }
     }
 
+    if exports_kind != ExportsKind::CommonJs || self.used_exports_ref || self.used_module_ref {
+      // The exports object might be read anywhere, so every assignment is kept
+      for stmt_info_id in self.result.commonjs_exports.drain().flat_map(|(_, ids)| ids) {
+        self.result.stmt_infos[stmt_info_id].side_effect = true;
+      }
+    }
+
     self.result.exports_kind = exports_kind;
     self.result
   }
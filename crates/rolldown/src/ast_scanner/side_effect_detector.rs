@@ -1,44 +1,45 @@
-use once_cell::sync::Lazy;
-use oxc::ast::ast::{IdentifierReference, MemberExpression};
+use oxc::{ast::ast::IdentifierReference, semantic::SymbolId, syntax::operator::BinaryOperator};
 use rolldown_common::AstScope;
-use rustc_hash::FxHashSet;
-
-// Probably we should generate this using macros.
-static SIDE_EFFECT_FREE_MEMBER_EXPR_2: Lazy<FxHashSet<(&'static str, &'static str)>> =
-  Lazy::new(|| {
-    [
-      ("Object", "create"),
-      ("Object", "defineProperty"),
-      ("Object", "getOwnPropertyDescriptor"),
-      ("Object", "getPrototypeOf"),
-      ("Object", "getOwnPropertyNames"),
-    ]
-    .into_iter()
-    .collect()
-  });
-
-static SIDE_EFFECT_FREE_MEMBER_EXPR_3: Lazy<FxHashSet<(&'static str, &'static str, &'static str)>> =
-  Lazy::new(|| {
-    [("Object", "prototype", "hasOwnProperty"), ("Object", "prototype", "constructor")]
-      .into_iter()
-      .collect()
-  });
+
+use super::{
+  annotations::Annotations,
+  known_globals::{is_known_global, is_pure_constructor},
+};
+use crate::utils::{
+  define::member_chain,
+  static_value::{get_static_boolean, is_short_circuited, StaticPrimitive},
+};
 
 /// Detect if a statement "may" have side effect.
 pub struct SideEffectDetector<'a> {
   pub scope: &'a AstScope,
+  annotations: &'a Annotations,
+  /// Calls of top level functions, which have no side effects if the function is annotated with
+  /// `@__NO_SIDE_EFFECTS__`. They are not considered as side effects by the detector.
+  pub calls: Vec<SymbolId>,
+  /// Whether reading a global that isn't a known global of the language is a side effect.
+  unknown_global_side_effects: bool,
 }
 
 impl<'a> SideEffectDetector<'a> {
-  pub fn new(scope: &'a AstScope) -> Self {
-    Self { scope }
+  pub fn new(
+    scope: &'a AstScope,
+    annotations: &'a Annotations,
+    unknown_global_side_effects: bool,
+  ) -> Self {
+    Self { scope, annotations, calls: vec![], unknown_global_side_effects }
   }
 
   fn is_unresolved_reference(&self, ident_ref: &IdentifierReference) -> bool {
     self.scope.is_unresolved(ident_ref.reference_id.get().unwrap())
   }
 
-  fn detect_side_effect_of_class(&self, cls: &oxc::ast::ast::Class) -> bool {
+  fn top_level_symbol_for(&self, ident_ref: &IdentifierReference) -> Option<SymbolId> {
+    let symbol_id = self.scope.symbol_id_for(ident_ref.reference_id.get().unwrap())?;
+    (self.scope.get_root_binding(&ident_ref.name) == Some(symbol_id)).then_some(symbol_id)
+  }
+
+  fn detect_side_effect_of_class(&mut self, cls: &oxc::ast::ast::Class) -> bool {
     use oxc::ast::ast::ClassElement;
     cls.body.body.iter().any(|elm| match elm {
       ClassElement::StaticBlock(static_block) => {
@@ -65,39 +66,14 @@ impl<'a> SideEffectDetector<'a> {
     })
   }
 
-  fn detect_side_effect_of_member_expr(expr: &oxc::ast::ast::MemberExpression) -> bool {
-    // MemberExpression is considered having side effect by default, unless it's some builtin global variables.
-    let MemberExpression::StaticMemberExpression(member_expr) = expr else {
-      return true;
-    };
-    let prop_name = &member_expr.property.name;
-    match &member_expr.object {
-      oxc::ast::ast::Expression::Identifier(ident) => {
-        let object_name = &ident.name;
-        // Check if `object_name.prop_name` is pure
-        !SIDE_EFFECT_FREE_MEMBER_EXPR_2.contains(&(object_name.as_str(), prop_name.as_str()))
-      }
-      oxc::ast::ast::Expression::MemberExpression(mem_expr) => {
-        let MemberExpression::StaticMemberExpression(mem_expr) = &**mem_expr else {
-          return true;
-        };
-        let mid_prop = &mem_expr.property.name;
-        let oxc::ast::ast::Expression::Identifier(obj_ident) = &mem_expr.object else {
-          return true;
-        };
-        let object_name = &obj_ident.name;
-        // Check if `object_name.mid_prop.prop_name` is pure
-        !SIDE_EFFECT_FREE_MEMBER_EXPR_3.contains(&(
-          object_name.as_str(),
-          mid_prop.as_str(),
-          prop_name.as_str(),
-        ))
-      }
-      _ => true,
-    }
+  /// Reading `a.b.c` or `a` is a side effect, unless `a` is a global and `a.b.c` is a known global.
+  fn is_known_global_read(&self, expr: &oxc::ast::ast::Expression) -> bool {
+    member_chain(expr).is_some_and(|(path, root)| {
+      root.is_some_and(|ident| self.is_unresolved_reference(ident)) && is_known_global(&path)
+    })
   }
 
-  fn detect_side_effect_of_expr(&self, expr: &oxc::ast::ast::Expression) -> bool {
+  pub fn detect_side_effect_of_expr(&mut self, expr: &oxc::ast::ast::Expression) -> bool {
     use oxc::ast::ast::Expression;
     match expr {
       Expression::BooleanLiteral(_)
@@ -134,16 +110,22 @@ impl<'a> SideEffectDetector<'a> {
       Expression::UnaryExpression(unary_expr) => {
         self.detect_side_effect_of_expr(&unary_expr.argument)
       }
-      Expression::MemberExpression(mem_expr) => Self::detect_side_effect_of_member_expr(mem_expr),
+      // Property access is considered as having side effects, since it may call a getter
+      Expression::MemberExpression(_) => !self.is_known_global_read(expr),
       Expression::ClassExpression(cls) => self.detect_side_effect_of_class(cls),
       // Accessing global variables considered as side effect.
-      Expression::Identifier(ident) => self.is_unresolved_reference(ident),
+      Expression::Identifier(ident) => {
+        self.is_unresolved_reference(ident)
+          && self.unknown_global_side_effects
+          && !self.is_known_global_read(expr)
+      }
       Expression::TemplateLiteral(literal) => {
         literal.expressions.iter().any(|expr| self.detect_side_effect_of_expr(expr))
       }
       Expression::LogicalExpression(logic_expr) => {
         self.detect_side_effect_of_expr(&logic_expr.left)
-          || self.detect_side_effect_of_expr(&logic_expr.right)
+          || (!is_short_circuited(&logic_expr.left, logic_expr.operator)
+            && self.detect_side_effect_of_expr(&logic_expr.right))
       }
       Expression::ParenthesizedExpression(paren_expr) => {
         self.detect_side_effect_of_expr(&paren_expr.expression)
@@ -153,9 +135,29 @@ impl<'a> SideEffectDetector<'a> {
       }
       Expression::ConditionalExpression(cond_expr) => {
         self.detect_side_effect_of_expr(&cond_expr.test)
-          || self.detect_side_effect_of_expr(&cond_expr.consequent)
-          || self.detect_side_effect_of_expr(&cond_expr.alternate)
+          || match get_static_boolean(&cond_expr.test) {
+            Some(true) => self.detect_side_effect_of_expr(&cond_expr.consequent),
+            Some(false) => self.detect_side_effect_of_expr(&cond_expr.alternate),
+            None => {
+              self.detect_side_effect_of_expr(&cond_expr.consequent)
+                || self.detect_side_effect_of_expr(&cond_expr.alternate)
+            }
+          }
       }
+      Expression::BinaryExpression(binary_expr) => {
+        // Other operators may convert objects to primitives, which calls user code
+        let only_compares_identity = matches!(
+          binary_expr.operator,
+          BinaryOperator::StrictEquality | BinaryOperator::StrictInequality
+        ) || (StaticPrimitive::from_expression(&binary_expr.left)
+          .is_some()
+          && StaticPrimitive::from_expression(&binary_expr.right).is_some());
+        !only_compares_identity
+          || self.detect_side_effect_of_expr(&binary_expr.left)
+          || self.detect_side_effect_of_expr(&binary_expr.right)
+      }
+      Expression::CallExpression(call_expr) => self.detect_side_effect_of_call(call_expr),
+      Expression::NewExpression(new_expr) => self.detect_side_effect_of_new(new_expr),
       Expression::TSAsExpression(_)
       | Expression::TSSatisfiesExpression(_)
       | Expression::TSTypeAssertion(_)
@@ -168,11 +170,8 @@ impl<'a> SideEffectDetector<'a> {
       | Expression::ArrayExpression(_)
       | Expression::AssignmentExpression(_)
       | Expression::AwaitExpression(_)
-      | Expression::BinaryExpression(_)
-      | Expression::CallExpression(_)
       | Expression::ChainExpression(_)
       | Expression::ImportExpression(_)
-      | Expression::NewExpression(_)
       | Expression::TaggedTemplateExpression(_)
       | Expression::ThisExpression(_)
       | Expression::UpdateExpression(_)
@@ -183,7 +182,51 @@ impl<'a> SideEffectDetector<'a> {
     }
   }
 
-  fn detect_side_effect_of_decl(&self, decl: &oxc::ast::ast::Declaration) -> bool {
+  /// Calls of top-level functions are recorded in `calls`, since they are side effect free if the function
+  /// turns out to be annotated with `/*#__NO_SIDE_EFFECTS__*/`.
+  fn detect_side_effect_of_call(&mut self, call_expr: &oxc::ast::ast::CallExpression) -> bool {
+    if self.detect_side_effect_of_arguments(&call_expr.arguments) {
+      return true;
+    }
+    if self.annotations.is_pure(call_expr.span) {
+      return false;
+    }
+    match &call_expr.callee {
+      oxc::ast::ast::Expression::Identifier(ident) => match self.top_level_symbol_for(ident) {
+        Some(symbol_id) => {
+          self.calls.push(symbol_id);
+          false
+        }
+        None => true,
+      },
+      _ => true,
+    }
+  }
+
+  fn detect_side_effect_of_new(&mut self, new_expr: &oxc::ast::ast::NewExpression) -> bool {
+    if self.annotations.is_pure(new_expr.span) {
+      return self.detect_side_effect_of_arguments(&new_expr.arguments);
+    }
+    match &new_expr.callee {
+      // Like `new Map()`
+      oxc::ast::ast::Expression::Identifier(ident) => {
+        !(new_expr.arguments.is_empty()
+          && self.is_unresolved_reference(ident)
+          && is_pure_constructor(&ident.name))
+      }
+      _ => true,
+    }
+  }
+
+  fn detect_side_effect_of_arguments(&mut self, args: &[oxc::ast::ast::Argument]) -> bool {
+    args.iter().any(|arg| match arg {
+      oxc::ast::ast::Argument::Expression(expr) => self.detect_side_effect_of_expr(expr),
+      // Spreading may call an iterator
+      oxc::ast::ast::Argument::SpreadElement(_) => true,
+    })
+  }
+
+  fn detect_side_effect_of_decl(&mut self, decl: &oxc::ast::ast::Declaration) -> bool {
     use oxc::ast::ast::Declaration;
     match decl {
       Declaration::VariableDeclaration(var_decl) => var_decl
@@ -201,7 +244,7 @@ impl<'a> SideEffectDetector<'a> {
     }
   }
 
-  pub fn detect_side_effect_of_stmt(&self, stmt: &oxc::ast::ast::Statement) -> bool {
+  pub fn detect_side_effect_of_stmt(&mut self, stmt: &oxc::ast::ast::Statement) -> bool {
     use oxc::ast::ast::Statement;
     match stmt {
       Statement::Declaration(decl) => self.detect_side_effect_of_decl(decl),
@@ -250,9 +293,16 @@ impl<'a> SideEffectDetector<'a> {
           || self.detect_side_effect_of_stmt(&while_stmt.body)
       }
       Statement::IfStatement(if_stmt) => {
+        let test = get_static_boolean(&if_stmt.test);
+        // Branches that never run, like `if (process.env.NODE_ENV !== 'production')` in production builds,
+        // don't matter.
         self.detect_side_effect_of_expr(&if_stmt.test)
-          || self.detect_side_effect_of_stmt(&if_stmt.consequent)
-          || if_stmt.alternate.as_ref().map_or(false, |stmt| self.detect_side_effect_of_stmt(stmt))
+          || (test != Some(false) && self.detect_side_effect_of_stmt(&if_stmt.consequent))
+          || (test != Some(true)
+            && if_stmt
+              .alternate
+              .as_ref()
+              .map_or(false, |stmt| self.detect_side_effect_of_stmt(stmt)))
       }
       Statement::ReturnStatement(ret_stmt) => {
         ret_stmt.argument.as_ref().map_or(false, |expr| self.detect_side_effect_of_expr(expr))
@@ -291,7 +341,7 @@ impl<'a> SideEffectDetector<'a> {
     }
   }
 
-  fn detect_side_effect_of_block(&self, block: &oxc::ast::ast::BlockStatement) -> bool {
+  fn detect_side_effect_of_block(&mut self, block: &oxc::ast::ast::BlockStatement) -> bool {
     block.body.iter().any(|stmt| self.detect_side_effect_of_stmt(stmt))
   }
 }
@@ -302,9 +352,13 @@ mod test {
   use rolldown_common::AstScope;
   use rolldown_oxc_utils::OxcCompiler;
 
-  use crate::ast_scanner::side_effect_detector::SideEffectDetector;
+  use crate::ast_scanner::{annotations::Annotations, side_effect_detector::SideEffectDetector};
 
   fn get_statements_side_effect(code: &str) -> bool {
+    get_statements_side_effect_with(code, true)
+  }
+
+  fn get_statements_side_effect_with(code: &str, unknown_global_side_effects: bool) -> bool {
     let source_type = SourceType::default()
       .with_always_strict(true)
       .with_module(true)
@@ -322,11 +376,14 @@ mod test {
       )
     };
 
-    let has_side_effect = program
-      .program()
-      .body
-      .iter()
-      .any(|stmt| SideEffectDetector::new(&ast_scope).detect_side_effect_of_stmt(stmt));
+    let annotations = Annotations::new(program.source(), program.trivias());
+
+    let has_side_effect = program.program().body.iter().any(|stmt| {
+      let mut detector =
+        SideEffectDetector::new(&ast_scope, &annotations, unknown_global_side_effects);
+      // Calls of top level functions are side effects, unless they're annotated with `@__NO_SIDE_EFFECTS__`
+      detector.detect_side_effect_of_stmt(stmt) || !detector.calls.is_empty()
+    });
 
     has_side_effect
   }
@@ -455,6 +512,87 @@ mod test {
     assert!(get_statements_side_effect("if (true) { bar; }"));
   }
 
+  #[test]
+  fn test_static_if_statement() {
+    assert!(!get_statements_side_effect("if (false) { bar; }"));
+    assert!(!get_statements_side_effect("if ('production' !== 'production') { bar; }"));
+    assert!(!get_statements_side_effect("if (!true) { bar; } else { const a = 1; }"));
+    assert!(!get_statements_side_effect("if ('development' === 'production' && bar) { bar; }"));
+    assert!(!get_statements_side_effect("false ? bar : true"));
+    // the branch that runs may have side effect
+    assert!(get_statements_side_effect("if ('production' === 'production') { bar; }"));
+    assert!(get_statements_side_effect("if (false) { } else { bar; }"));
+    assert!(get_statements_side_effect("if (1 == '1') { bar; }"));
+  }
+
+  #[test]
+  fn test_binary_expression() {
+    assert!(!get_statements_side_effect("1 + 2"));
+    assert!(!get_statements_side_effect("const a = {}; a === 1"));
+    // converting objects to primitives may have side effect
+    assert!(get_statements_side_effect("const a = {}; a + 1"));
+    assert!(get_statements_side_effect("const a = {}; a == 1"));
+    // accessing global variable may have side effect
+    assert!(get_statements_side_effect("bar === 1"));
+  }
+
+  #[test]
+  fn test_pure_annotation() {
+    assert!(!get_statements_side_effect("/*#__PURE__*/ foo()"));
+    assert!(!get_statements_side_effect("/*@__PURE__*/ foo.bar()"));
+    assert!(!get_statements_side_effect("const a = /* #__PURE__ */ new Foo(1, 'a')"));
+    assert!(!get_statements_side_effect("// #__PURE__\nfoo()"));
+    assert!(!get_statements_side_effect("/*#__PURE__*/ /* other comment */ foo()"));
+    // arguments are still evaluated
+    assert!(get_statements_side_effect("/*#__PURE__*/ foo(bar)"));
+    assert!(get_statements_side_effect("/*#__PURE__*/ foo(...[])"));
+    // not annotated
+    assert!(get_statements_side_effect("foo()"));
+    assert!(get_statements_side_effect("new Foo()"));
+    assert!(get_statements_side_effect("/* __PURE__ */ foo()"));
+    assert!(get_statements_side_effect("function foo() {} foo()"));
+    assert!(get_statements_side_effect("foo(/*#__PURE__*/ bar())"));
+  }
+
+  #[test]
+  fn test_known_globals() {
+    assert!(!get_statements_side_effect("Math"));
+    assert!(!get_statements_side_effect("Math.PI"));
+    assert!(!get_statements_side_effect("const isArray = Array.isArray"));
+    assert!(!get_statements_side_effect("Symbol.iterator"));
+    assert!(!get_statements_side_effect("Object.prototype.hasOwnProperty"));
+    assert!(!get_statements_side_effect("Uint8Array.BYTES_PER_ELEMENT"));
+    assert!(!get_statements_side_effect("const a = new Map()"));
+    assert!(!get_statements_side_effect("new WeakSet(), new Float64Array(), new TypeError()"));
+    // unknown members and calls may have side effect
+    assert!(get_statements_side_effect("Math.foo"));
+    assert!(get_statements_side_effect("Math.abs(1)"));
+    assert!(get_statements_side_effect("JSON.parse.foo"));
+    assert!(get_statements_side_effect("Math?.PI"));
+    // constructors with arguments may throw or iterate
+    assert!(get_statements_side_effect("new Map(entries)"));
+    assert!(get_statements_side_effect("new Set([])"));
+    assert!(get_statements_side_effect("new Promise()"));
+    // `new AggregateError()` throws, since it iterates its first argument
+    assert!(!get_statements_side_effect("AggregateError"));
+    assert!(get_statements_side_effect("new AggregateError()"));
+    // globals missing from some supported environments aren't known
+    assert!(get_statements_side_effect("WeakRef"));
+    assert!(get_statements_side_effect("Atomics"));
+    // shadowed globals aren't known
+    assert!(get_statements_side_effect("const Math = {}; Math.PI"));
+    assert!(get_statements_side_effect("function Map() { bar() }; new Map()"));
+  }
+
+  #[test]
+  fn test_unknown_global_side_effects() {
+    assert!(!get_statements_side_effect_with("foo", false));
+    assert!(!get_statements_side_effect_with("const a = foo ?? bar", false));
+    // property access and calls still may have side effect
+    assert!(get_statements_side_effect_with("foo.bar", false));
+    assert!(get_statements_side_effect_with("foo()", false));
+  }
+
   #[test]
   fn test_empty_statement() {
     assert!(!get_statements_side_effect(";"));
@@ -139,7 +139,7 @@ impl Bundler {
 
     let build_info = scan_ret?;
 
-    let link_stage = LinkStage::new(build_info, &self.input_options);
+    let link_stage = LinkStage::new(build_info, &self.input_options, &self.output_options);
     Ok(link_stage.link())
   }
 
@@ -1,13 +1,45 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cmp::Reverse};
 
+use index_vec::IndexVec;
+use rolldown_common::{ChunkId, SymbolRef};
 use rolldown_rstr::ToRstr;
+use rustc_hash::FxHashMap;
 
 use super::Chunk;
-use crate::{stages::link_stage::LinkStageOutput, utils::renamer::Renamer};
+use crate::{
+  options::normalized_output_options::NormalizedOutputOptions, stages::link_stage::LinkStageOutput,
+  utils::renamer::Renamer, OutputFormat,
+};
 
 impl Chunk {
-  pub fn de_conflict(&mut self, graph: &LinkStageOutput) {
-    let mut renamer = Renamer::new(&graph.symbols, graph.module_table.normal_modules.len());
+  /// `chunk_representative_names` are the names the parameters of the AMD factory that receive the chunks are
+  /// based on.
+  pub fn de_conflict(
+    &mut self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    chunk_representative_names: &IndexVec<ChunkId, String>,
+  ) {
+    let mut renamer = Renamer::new(
+      &graph.symbols,
+      graph.module_table.normal_modules.len(),
+      output_options.minify.identifiers,
+    );
+
+    if matches!(output_options.format, OutputFormat::Iife | OutputFormat::Umd) {
+      // `exports` is the parameter of the IIFE/UMD wrapper that collects exports of the chunk
+      renamer.reserve(Cow::Owned("exports".into()));
+    }
+    if matches!(output_options.format, OutputFormat::System) {
+      // `exports` and `module` are the parameters of the `System.register` callback
+      renamer.reserve(Cow::Owned("exports".into()));
+      renamer.reserve(Cow::Owned("module".into()));
+    }
+    if matches!(output_options.format, OutputFormat::Amd) {
+      // `require` and `exports` are the dependencies that AMD loaders provide to the factory
+      renamer.reserve(Cow::Owned("require".into()));
+      renamer.reserve(Cow::Owned("exports".into()));
+    }
 
     self
       .modules
@@ -20,31 +52,79 @@ impl Chunk {
         renamer.reserve(Cow::Owned(name.to_rstr()));
       });
 
-    self.imports_from_other_chunks.iter().flat_map(|(_, items)| items.iter()).for_each(|item| {
-      renamer.add_top_level_symbol(item.import_ref);
-    });
-
-    self
-      .modules
+    let mut top_level_symbols = self
+      .imports_from_other_chunks
       .iter()
-      .copied()
-      // Starts with entry module
-      .rev()
-      .map(|id| &graph.module_table.normal_modules[id])
-      .for_each(|module| {
-        module
-          .stmt_infos
+      .flat_map(|(_, items)| items.iter().map(|item| item.import_ref))
+      .chain(
+        self
+          .modules
           .iter()
-          .filter(|stmt_info| stmt_info.is_included)
-          .flat_map(|stmt_info| stmt_info.declared_symbols.iter().copied())
-          .for_each(|symbol_ref| {
-            renamer.add_top_level_symbol(symbol_ref);
-          });
+          .copied()
+          // Starts with entry module
+          .rev()
+          .map(|id| &graph.module_table.normal_modules[id])
+          .flat_map(|module| {
+            module
+              .stmt_infos
+              .iter()
+              .filter(|stmt_info| stmt_info.is_included)
+              .flat_map(|stmt_info| stmt_info.declared_symbols.iter().copied())
+          }),
+      )
+      .collect::<Vec<_>>();
+
+    if output_options.minify.identifiers {
+      // The most referenced symbols get the shortest names
+      let reference_counts = self.count_top_level_references(graph);
+      top_level_symbols.sort_by_key(|symbol_ref| {
+        Reverse(reference_counts.get(&graph.symbols.par_canonical_ref_for(*symbol_ref)))
       });
+    }
+
+    top_level_symbols.into_iter().for_each(|symbol_ref| {
+      renamer.add_top_level_symbol(symbol_ref);
+    });
+
+    if matches!(output_options.format, OutputFormat::Amd) {
+      // Named after the top-level symbols, so that they keep their names, and before the non-top-level
+      // symbols, so that these don't shadow the parameters.
+      let mut importee_ids = self
+        .imports_from_other_chunks
+        .iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(importee_id, _)| *importee_id)
+        .collect::<Vec<_>>();
+      importee_ids.sort_unstable();
+      self.import_param_names = importee_ids
+        .into_iter()
+        .map(|importee_id| {
+          (importee_id, renamer.create_top_level_name(&chunk_representative_names[importee_id]))
+        })
+        .collect();
+    }
 
     // rename non-top-level names
     renamer.rename_non_top_level_symbol(&self.modules, &graph.module_table.normal_modules);
 
     self.canonical_names = renamer.into_canonical_names();
   }
+
+  /// How often each top-level symbol is declared or referenced by the included statements of the chunk, keyed
+  /// by the canonical symbol.
+  fn count_top_level_references(&self, graph: &LinkStageOutput) -> FxHashMap<SymbolRef, usize> {
+    let mut reference_counts = FxHashMap::default();
+    self
+      .modules
+      .iter()
+      .flat_map(|id| graph.module_table.normal_modules[*id].stmt_infos.iter())
+      .filter(|stmt_info| stmt_info.is_included)
+      .flat_map(|stmt_info| {
+        stmt_info.declared_symbols.iter().chain(stmt_info.referenced_symbols.iter())
+      })
+      .for_each(|symbol_ref| {
+        *reference_counts.entry(graph.symbols.par_canonical_ref_for(*symbol_ref)).or_default() += 1;
+      });
+    reference_counts
+  }
 }
@@ -3,12 +3,14 @@ mod de_conflict;
 pub mod render_chunk;
 mod render_chunk_exports;
 mod render_chunk_imports;
+mod render_chunk_wrapper;
 
 use index_vec::IndexVec;
 use rolldown_common::ChunkId;
 
 pub type ChunksVec = IndexVec<ChunkId, Chunk>;
 
+use oxc::codegen::CodegenReturn;
 use rolldown_common::{
   ChunkKind, ExternalModuleId, NamedImport, NormalModuleId, RenderedChunk, RenderedModule,
   Specifier, SymbolRef,
@@ -16,17 +18,17 @@ use rolldown_common::{
 use rolldown_error::BuildError;
 use rolldown_rstr::Rstr;
 use rolldown_sourcemap::{
-  collapse_sourcemaps, ConcatSource, RawSource, SourceMap, SourceMapSource,
+  collapse_sourcemaps, ConcatSource, RawSource, Source, SourceMap, SourceMapSource,
 };
 use rolldown_utils::BitSet;
 use rustc_hash::FxHashMap;
 
 use crate::options::normalized_input_options::NormalizedInputOptions;
 use crate::options::normalized_output_options::NormalizedOutputOptions;
-use crate::utils::render_normal_module::render_normal_module;
+use crate::utils::render_normal_module::{render_hoisted_declarations, render_normal_module};
 use crate::{
   error::BatchedResult,
-  FileNameTemplate,
+  FileNamesOutputOption, OutputFormat,
   {
     chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput,
     types::module_render_context::ModuleRenderContext,
@@ -51,6 +53,9 @@ pub struct Chunk {
   pub imports_from_external_modules: FxHashMap<ExternalModuleId, Vec<NamedImport>>,
   // meaningless if the chunk is an entrypoint
   pub exports_to_other_chunks: FxHashMap<SymbolRef, Rstr>,
+  /// The parameters of the AMD factory that receive the chunks this chunk imports bindings from. Only used
+  /// for `OutputFormat::Amd`, where the imported bindings are read from these parameters at each use.
+  pub import_param_names: FxHashMap<ChunkId, Rstr>,
 }
 
 pub struct ChunkRenderReturn {
@@ -69,17 +74,54 @@ impl Chunk {
     Self { modules, name, bits, kind, ..Self::default() }
   }
 
-  pub fn file_name_template<'a>(
-    &mut self,
+  /// Returns the name of the option the template comes from, for error messages, and the option. With
+  /// `preserve_modules`, every chunk stands for an input module and uses `entry_file_names`.
+  pub fn file_names_option<'a>(
+    &self,
     output_options: &'a NormalizedOutputOptions,
-  ) -> &'a FileNameTemplate {
-    if matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined) {
-      &output_options.entry_file_names
+  ) -> (&'static str, &'a FileNamesOutputOption) {
+    if output_options.preserve_modules
+      || matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined)
+    {
+      ("output.entryFileNames", &output_options.entry_file_names)
     } else {
-      &output_options.chunk_file_names
+      ("output.chunkFileNames", &output_options.chunk_file_names)
     }
   }
 
+  fn expect_file_name(&self) -> &str {
+    self.file_name.as_ref().expect("At this point, file name should already be generated")
+  }
+
+  /// The specifier used to import `importee` from this chunk. File names may contain directories, so the
+  /// specifier is relative to the directory of this chunk.
+  pub fn import_path_for(&self, importee: &Chunk) -> String {
+    let importer_dir = self.expect_file_name().split('/').collect::<Vec<_>>();
+    let importer_dir = &importer_dir[..importer_dir.len() - 1];
+    let importee_path = importee.expect_file_name().split('/').collect::<Vec<_>>();
+    let common_len = importer_dir
+      .iter()
+      .zip(&importee_path[..importee_path.len() - 1])
+      .take_while(|(a, b)| a == b)
+      .count();
+    let up = importer_dir.len() - common_len;
+    let prefix = if up == 0 { "./".to_string() } else { "../".repeat(up) };
+    format!("{prefix}{}", importee_path[common_len..].join("/"))
+  }
+
+  /// The module id of this chunk in `OutputFormat::Amd`. AMD loaders treat ids ending with `.js` as plain
+  /// URLs, so the extension is omitted.
+  pub fn amd_id(&self) -> &str {
+    let file_name = self.expect_file_name();
+    file_name.strip_suffix(".js").unwrap_or(file_name)
+  }
+
+  /// Same as `Chunk::import_path_for`, but without the `.js` extension as described in `Chunk::amd_id`.
+  pub fn amd_import_path_for(&self, importee: &Chunk) -> String {
+    let path = self.import_path_for(importee);
+    path.strip_suffix(".js").map_or(path.clone(), ToString::to_string)
+  }
+
   #[allow(clippy::unnecessary_wraps, clippy::cast_possible_truncation)]
   pub async fn render(
     &self,
@@ -88,68 +130,35 @@ impl Chunk {
     chunk_graph: &ChunkGraph,
     output_options: &NormalizedOutputOptions,
   ) -> BatchedResult<ChunkRenderReturn> {
-    use rayon::prelude::*;
-    let mut rendered_modules = FxHashMap::default();
     let mut concat_source = ConcatSource::default();
+    let is_system = matches!(output_options.format, OutputFormat::System);
 
-    concat_source
-      .add_source(Box::new(RawSource::new(self.render_imports_for_esm(graph, chunk_graph))));
+    let mut system_execute_head = None;
+    let wrapper_outro = if is_system {
+      let (intro, execute_head, outro) = self.render_system_wrapper(graph, chunk_graph);
+      concat_source.add_source(Box::new(RawSource::new(intro)));
+      system_execute_head = Some(execute_head);
+      Some(outro)
+    } else if let Some((intro, outro)) = self.render_wrapper(graph, chunk_graph, output_options) {
+      concat_source.add_source(Box::new(RawSource::new(intro)));
+      Some(outro)
+    } else {
+      concat_source
+        .add_source(Box::new(RawSource::new(self.render_imports_for_esm(graph, chunk_graph))));
+      None
+    };
+
+    let (hoisted_sources, module_sources, rendered_modules) =
+      self.render_modules(input_options, graph, chunk_graph, output_options)?;
+
+    // The declarations hoisted for `OutputFormat::System` are placed in the `System.register` callback, before
+    // `execute`
+    hoisted_sources.into_iter().for_each(|source| concat_source.add_source(source));
+    if let Some(execute_head) = system_execute_head {
+      concat_source.add_source(Box::new(RawSource::new(execute_head)));
+    }
+    module_sources.into_iter().for_each(|source| concat_source.add_source(source));
 
-    self
-      .modules
-      .par_iter()
-      .copied()
-      .map(|id| &graph.module_table.normal_modules[id])
-      .filter_map(|m| {
-        let rendered_output = render_normal_module(
-          &ModuleRenderContext {
-            canonical_names: &self.canonical_names,
-            graph,
-            chunk_graph,
-            input_options,
-          },
-          &graph.ast_table[m.id],
-          m.resource_id
-                .expect_file()
-                .relative_path(&input_options.cwd)
-                .to_string_lossy().as_ref(),
-           !output_options.sourcemap.is_hidden()
-        );
-        Some((
-          m.resource_id.expect_file().to_string(),
-          &m.pretty_path,
-          RenderedModule { code: None },
-          rendered_output.as_ref().map(|v| v.source_text.to_string()),
-          if output_options.sourcemap.is_hidden() {
-            None
-          } else {
-            let mut sourcemap_chain = m.sourcemap_chain.iter().collect::<Vec<_>>();
-            if let Some(Some(sourcemap)) = rendered_output.as_ref().map(|x| x.source_map.as_ref()) {
-              sourcemap_chain.push(sourcemap);
-            }
-            Some(collapse_sourcemaps(sourcemap_chain))
-          },
-        ))
-      })
-      .collect::<Vec<_>>()
-      .into_iter()
-      .try_for_each(
-        |(module_path, module_pretty_path, rendered_module, rendered_content, map)| -> Result<(), BuildError> {
-          if let Some(rendered_content) = rendered_content {
-            concat_source.add_source(Box::new(RawSource::new(format!("// {module_pretty_path}"))));
-            if let Some(map) = match map {
-              None => None,
-              Some(v) => v?,
-            } {
-              concat_source.add_source(Box::new(SourceMapSource::new(rendered_content, map)));
-            } else {
-              concat_source.add_source(Box::new(RawSource::new(rendered_content)));
-            }
-          }
-          rendered_modules.insert(module_path, rendered_module);
-          Ok(())
-        },
-      )?;
     let rendered_chunk = self.get_rendered_chunk_info(graph, output_options, rendered_modules);
 
     // TODO avoid rendered_chunk clone
@@ -158,10 +167,14 @@ impl Chunk {
       concat_source.add_prepend_source(Box::new(RawSource::new(banner_txt)));
     }
 
-    if let Some(exports) = self.render_exports(graph, output_options) {
+    if let Some(exports) = self.render_exports(graph, chunk_graph, output_options) {
       concat_source.add_source(Box::new(RawSource::new(exports)));
     }
 
+    if let Some(outro) = wrapper_outro {
+      concat_source.add_source(Box::new(RawSource::new(outro)));
+    }
+
     // add footer
     if let Some(footer_txt) = output_options.footer.call(rendered_chunk.clone()).await? {
       concat_source.add_source(Box::new(RawSource::new(footer_txt)));
@@ -171,4 +184,97 @@ impl Chunk {
 
     Ok(ChunkRenderReturn { code: content, map, rendered_chunk })
   }
+
+  /// Render the modules of the chunk. Returns the sources of the declarations hoisted for
+  /// `OutputFormat::System`, the sources of the modules and the `RenderedModule`s.
+  #[allow(clippy::type_complexity)]
+  fn render_modules(
+    &self,
+    input_options: &NormalizedInputOptions,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &NormalizedOutputOptions,
+  ) -> Result<
+    (Vec<Box<dyn Source + Send>>, Vec<Box<dyn Source + Send>>, FxHashMap<String, RenderedModule>),
+    BuildError,
+  > {
+    use rayon::prelude::*;
+    let rendered_module_parts = self
+      .modules
+      .par_iter()
+      .copied()
+      .map(|id| &graph.module_table.normal_modules[id])
+      .map(|m| {
+        let ctx = ModuleRenderContext {
+          canonical_names: &self.canonical_names,
+          graph,
+          chunk_graph,
+          input_options,
+        };
+        let source_name = m.resource_id.expect_file().relative_path(&input_options.cwd);
+        let source_name = source_name.to_string_lossy();
+        let enable_sourcemap = output_options.sourcemap.is_some();
+        let hoisted_output = render_hoisted_declarations(
+          &ctx,
+          &graph.ast_table[m.id],
+          source_name.as_ref(),
+          enable_sourcemap,
+          output_options.minify.whitespace,
+        );
+        let rendered_output = render_normal_module(
+          &ctx,
+          &graph.ast_table[m.id],
+          source_name.as_ref(),
+          enable_sourcemap,
+          output_options.minify.whitespace,
+        );
+        let into_part = |output: Option<CodegenReturn>| {
+          output.map(|output| {
+            let map = if enable_sourcemap {
+              let mut sourcemap_chain = m.sourcemap_chain.iter().collect::<Vec<_>>();
+              if let Some(sourcemap) = output.source_map.as_ref() {
+                sourcemap_chain.push(sourcemap);
+              }
+              Some(collapse_sourcemaps(sourcemap_chain))
+            } else {
+              None
+            };
+            (output.source_text, map)
+          })
+        };
+        (
+          m.resource_id.expect_file().to_string(),
+          &m.pretty_path,
+          RenderedModule { code: None },
+          into_part(hoisted_output),
+          into_part(rendered_output),
+        )
+      })
+      .collect::<Vec<_>>();
+
+    let mut rendered_modules = FxHashMap::default();
+    let mut hoisted_sources = vec![];
+    let mut module_sources = vec![];
+    rendered_module_parts.into_iter().try_for_each(
+      |(module_path, module_pretty_path, rendered_module, hoisted_part, rendered_part)| -> Result<(), BuildError> {
+        for (part, sources) in [(hoisted_part, &mut hoisted_sources), (rendered_part, &mut module_sources)] {
+          let Some((rendered_content, map)) = part else {
+            continue;
+          };
+          if !output_options.minify.whitespace {
+            sources.push(Box::new(RawSource::new(format!("// {module_pretty_path}"))) as Box<dyn Source + Send>);
+          }
+          if let Some(map) = map.transpose()?.flatten() {
+            sources.push(Box::new(SourceMapSource::new(rendered_content, map)));
+          } else {
+            sources.push(Box::new(RawSource::new(rendered_content)));
+          }
+        }
+        rendered_modules.insert(module_path, rendered_module);
+        Ok(())
+      },
+    )?;
+
+    Ok((hoisted_sources, module_sources, rendered_modules))
+  }
 }
@@ -1,8 +1,12 @@
 use rolldown_common::{ChunkKind, SymbolRef, WrapKind};
 use rolldown_rstr::Rstr;
+use rustc_hash::FxHashMap;
 
 use crate::{
-  options::normalized_output_options::NormalizedOutputOptions, stages::link_stage::LinkStageOutput,
+  chunk_graph::ChunkGraph,
+  options::normalized_output_options::NormalizedOutputOptions,
+  stages::link_stage::LinkStageOutput,
+  utils::ecma_script::{property_access_str, property_key_str, string_literal_str},
   OutputFormat,
 };
 
@@ -12,31 +16,42 @@ impl Chunk {
   pub fn render_exports(
     &self,
     graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
     output_options: &NormalizedOutputOptions,
   ) -> Option<String> {
     if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind {
       let linking_info = &graph.metas[*entry_module_id];
       if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
-        match output_options.format {
-          OutputFormat::Esm => {
-            let wrap_ref_name =
-              &self.canonical_names.get(&linking_info.wrapper_ref.unwrap()).unwrap_or_else(|| {
-                panic!(
-                  "Cannot find canonical name for wrap ref {:?} of {:?}",
-                  linking_info.wrapper_ref.unwrap(),
-                  graph.module_table.normal_modules[*entry_module_id].resource_id
-                )
-              });
-            return Some(format!("export default {wrap_ref_name}();\n"));
-          }
-          OutputFormat::Cjs => {
-            unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
-          }
+        if matches!(output_options.format, OutputFormat::Cjs) {
+          unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
         }
+        let wrap_ref_name =
+          &self.canonical_names.get(&linking_info.wrapper_ref.unwrap()).unwrap_or_else(|| {
+            panic!(
+              "Cannot find canonical name for wrap ref {:?} of {:?}",
+              linking_info.wrapper_ref.unwrap(),
+              graph.module_table.normal_modules[*entry_module_id].resource_id
+            )
+          });
+        return Some(match output_options.format {
+          OutputFormat::Esm => format!("export default {wrap_ref_name}();\n"),
+          OutputFormat::Iife | OutputFormat::Umd | OutputFormat::Amd => {
+            format!("return {wrap_ref_name}();\n")
+          }
+          OutputFormat::System => format!("exports(\"default\", {wrap_ref_name}());\n"),
+          OutputFormat::Cjs => unreachable!(),
+        });
       }
     }
 
-    let export_items = self.get_export_items(graph);
+    let mut export_items = self.get_export_items(graph);
+    if matches!(output_options.format, OutputFormat::System) {
+      // Other exports are reported by the hoisted declarations and the setters, see `Chunk::render_system_wrapper`
+      export_items.retain(|(_, export_ref)| {
+        let canonical_ref = graph.symbols.par_canonical_ref_for(*export_ref);
+        graph.symbols.get(canonical_ref).namespace_alias.is_some()
+      });
+    }
 
     if export_items.is_empty() {
       return None;
@@ -49,21 +64,101 @@ impl Chunk {
         let symbol = graph.symbols.get(canonical_ref);
         let canonical_name = &self.canonical_names[&canonical_ref];
         if let Some(ns_alias) = &symbol.namespace_alias {
-          let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
+          let ns_expr = self.import_access_str(graph, chunk_graph, ns_alias.namespace_ref);
           let property_name = &ns_alias.property_name;
-          s.push_str(&format!("var {canonical_name} = {canonical_ns_name}.{property_name};\n"));
+          s.push_str(&format!("var {canonical_name} = {ns_expr}.{property_name};\n"));
+        } else if let Some((param_name, export_alias)) =
+          self.cross_chunk_import_access(&graph.symbols, chunk_graph, canonical_ref)
+        {
+          // Re-exported imports from other chunks are live bindings, like the imports themselves
+          return format!(
+            "Object.defineProperty(exports, {}, {{ enumerable: true, get: function() {{ return {}; }} }});",
+            string_literal_str(&exported_name),
+            property_access_str(param_name, export_alias)
+          );
         }
-        if canonical_name == &exported_name {
-          format!("{canonical_name}")
-        } else {
-          format!("{canonical_name} as {exported_name}")
+        match output_options.format {
+          OutputFormat::Iife | OutputFormat::Umd | OutputFormat::Amd => {
+            format!("{} = {canonical_name};", property_access_str("exports", &exported_name))
+          }
+          OutputFormat::System => format!("{}: {canonical_name}", property_key_str(&exported_name)),
+          _ => {
+            if canonical_name == &exported_name {
+              format!("{canonical_name}")
+            } else {
+              format!("{canonical_name} as {exported_name}")
+            }
+          }
         }
       })
       .collect::<Vec<_>>();
-    s.push_str(&format!("export {{ {} }};", rendered_items.join(", "),));
+    match output_options.format {
+      OutputFormat::Iife => {
+        s.push_str(&rendered_items.join("\n"));
+        s.push_str("\nreturn exports;");
+      }
+      // The `exports` object is provided by the caller of the UMD/AMD factory, so there is nothing to return.
+      OutputFormat::Umd | OutputFormat::Amd => {
+        s.push_str(&rendered_items.join("\n"));
+      }
+      OutputFormat::System => {
+        s.push_str(&format!("exports({{ {} }});", rendered_items.join(", ")));
+      }
+      _ => {
+        s.push_str(&format!("export {{ {} }};", rendered_items.join(", "),));
+      }
+    }
     Some(s)
   }
 
+  /// `foo`, or `chunk.foo` if `canonical_ref` is imported from another chunk. See
+  /// `Chunk::cross_chunk_import_access`.
+  fn import_access_str(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    canonical_ref: SymbolRef,
+  ) -> String {
+    match self.cross_chunk_import_access(&graph.symbols, chunk_graph, canonical_ref) {
+      Some((param_name, export_alias)) => property_access_str(param_name, export_alias),
+      None => self.canonical_names[&canonical_ref].to_string(),
+    }
+  }
+
+  /// The names under which each symbol is exported from the chunk, keyed by the canonical symbol.
+  ///
+  /// Symbols accessed through a namespace object and the `module.exports` of a wrapped CommonJS entry
+  /// aren't included, since they aren't bound to a variable of the chunk.
+  pub fn get_exported_names_by_symbol(
+    &self,
+    graph: &LinkStageOutput,
+  ) -> FxHashMap<SymbolRef, Vec<Rstr>> {
+    let mut exported_names: FxHashMap<SymbolRef, Vec<Rstr>> = FxHashMap::default();
+    if let ChunkKind::EntryPoint { module, .. } = &self.kind {
+      if matches!(graph.metas[*module].wrap_kind, WrapKind::Cjs) {
+        return exported_names;
+      }
+    }
+    self.get_export_items(graph).into_iter().for_each(|(exported_name, export_ref)| {
+      let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+      if graph.symbols.get(canonical_ref).namespace_alias.is_none() {
+        exported_names.entry(canonical_ref).or_default().push(exported_name);
+      }
+    });
+    exported_names
+  }
+
+  /// Same as `Chunk::get_exported_names_by_symbol`, but keyed by the canonical names of the symbols.
+  pub fn get_exported_names_by_local(&self, graph: &LinkStageOutput) -> FxHashMap<Rstr, Vec<Rstr>> {
+    self
+      .get_exported_names_by_symbol(graph)
+      .into_iter()
+      .map(|(canonical_ref, exported_names)| {
+        (self.canonical_names[&canonical_ref].clone(), exported_names)
+      })
+      .collect()
+  }
+
   fn get_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
     match self.kind {
       ChunkKind::EntryPoint { module, .. } => {
@@ -96,7 +191,11 @@ impl Chunk {
       let linking_info = &graph.metas[*entry_module_id];
       if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
         match output_options.format {
-          OutputFormat::Esm => {
+          OutputFormat::Esm
+          | OutputFormat::Iife
+          | OutputFormat::Umd
+          | OutputFormat::System
+          | OutputFormat::Amd => {
             return vec!["default".to_string()];
           }
           OutputFormat::Cjs => {
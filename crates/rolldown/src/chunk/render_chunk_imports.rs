@@ -1,6 +1,15 @@
-use rolldown_common::Specifier;
+use std::{borrow::Cow, path::Path};
 
-use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput};
+use rolldown_common::{representative_name, ChunkId, ExternalModuleId, Specifier, SymbolRef};
+use rolldown_rstr::Rstr;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+  chunk_graph::ChunkGraph,
+  stages::link_stage::LinkStageOutput,
+  types::symbols::Symbols,
+  utils::ecma_script::{generate_unique_name, property_access_str},
+};
 
 use super::Chunk;
 
@@ -57,9 +66,11 @@ impl Chunk {
       }
     });
 
-    // render imports from other chunks
-
-    self.imports_from_other_chunks.iter().for_each(|(exporter_id, items)| {
+    // render imports from other chunks. Sort them by chunk id to get a stable order that follows the
+    // execution order of the chunks where it can.
+    let mut imports_from_other_chunks = self.imports_from_other_chunks.iter().collect::<Vec<_>>();
+    imports_from_other_chunks.sort_unstable_by_key(|(chunk_id, _)| **chunk_id);
+    imports_from_other_chunks.into_iter().for_each(|(exporter_id, items)| {
       let importee_chunk = &chunk_graph.chunks[*exporter_id];
       let mut import_items = items
         .iter()
@@ -76,17 +87,281 @@ impl Chunk {
           }
         })
         .collect::<Vec<_>>();
-      let file_name = importee_chunk
-        .file_name
-        .as_ref()
-        .expect("At this point, file name should already be generated");
+      let import_path = self.import_path_for(importee_chunk);
       if import_items.is_empty() {
-        s.push_str(&format!("import \"./{file_name}\";\n"));
+        s.push_str(&format!("import \"{import_path}\";\n"));
       } else {
         import_items.sort();
-        s.push_str(&format!("import {{ {} }} from \"./{file_name}\";\n", import_items.join(", ")));
+        s.push_str(&format!("import {{ {} }} from \"{import_path}\";\n", import_items.join(", ")));
       }
     });
     s
   }
 }
+
+/// An external module that is passed into the function wrapper of a chunk, such as the IIFE
+/// `(function(foo) { ... })(Foo)`.
+#[derive(Debug)]
+pub struct ExternalImportParam {
+  pub importee_id: ExternalModuleId,
+  /// `None` if the external module is only imported for its side effects.
+  pub param_name: Option<String>,
+}
+
+impl Chunk {
+  /// Render imports from external modules for output formats that wrap the chunk in a function.
+  ///
+  /// Each external module that provides bindings becomes a parameter of the function. The returned string
+  /// contains declarations binding the imported names to properties of these parameters, like
+  /// `var foo = ext.foo;`.
+  pub fn render_imports_for_function_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    used_names: &mut FxHashSet<String>,
+  ) -> (Vec<ExternalImportParam>, String) {
+    let mut s = String::new();
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+
+    let params = imports_from_external_modules
+      .into_iter()
+      .map(|(importee_id, named_imports)| {
+        if named_imports.is_empty() {
+          return ExternalImportParam { importee_id: *importee_id, param_name: None };
+        }
+        let importee = &graph.module_table.external_modules[*importee_id];
+        // Reuse the local binding of `import * as ns from '...'` as the parameter if there is one.
+        let param_name = named_imports.iter().find(|item| item.imported.is_star()).map_or_else(
+          || generate_unique_name(&representative_name(&importee.name), used_names),
+          |item| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(item.imported_as);
+            self.canonical_names[&canonical_ref].to_string()
+          },
+        );
+
+        let mut bindings = named_imports
+          .iter()
+          .filter_map(|item| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(item.imported_as);
+            let alias = &self.canonical_names[&canonical_ref];
+            match &item.imported {
+              Specifier::Star => None,
+              Specifier::Literal(imported) if imported.as_str() == "default" => Some(format!(
+                "var {alias} = {param_name} && {param_name}.__esModule ? {param_name}.default : {param_name};\n"
+              )),
+              Specifier::Literal(imported) => {
+                Some(format!("var {alias} = {};\n", property_access_str(&param_name, imported)))
+              }
+            }
+          })
+          .collect::<Vec<_>>();
+        bindings.sort();
+        bindings.dedup();
+        bindings.iter().for_each(|binding| s.push_str(binding));
+
+        ExternalImportParam { importee_id: *importee_id, param_name: Some(param_name) }
+      })
+      .collect::<Vec<_>>();
+
+    (params, s)
+  }
+}
+
+/// A chunk that is passed into the function wrapper of a chunk, such as the AMD
+/// `define(["./foo.js"], function(foo) { ... })`.
+#[derive(Debug)]
+pub struct ChunkImportParam {
+  pub importee_id: ChunkId,
+  /// `None` if the chunk is only imported for its side effects.
+  pub param_name: Option<String>,
+}
+
+impl Chunk {
+  /// The name of the chunk as an identifier, based on its file name.
+  pub fn representative_name(&self) -> String {
+    let file_stem = Path::new(self.expect_file_name())
+      .file_stem()
+      .map_or(Cow::Borrowed(""), |stem| stem.to_string_lossy());
+    representative_name(&file_stem).into_owned()
+  }
+
+  /// If `canonical_ref` is imported from another chunk, returns the parameter of the AMD factory that
+  /// receives the chunk and the name under which the chunk exports the symbol. See
+  /// `Chunk::import_param_names`.
+  pub fn cross_chunk_import_access<'a>(
+    &'a self,
+    symbols: &Symbols,
+    chunk_graph: &'a ChunkGraph,
+    canonical_ref: SymbolRef,
+  ) -> Option<(&'a Rstr, &'a Rstr)> {
+    if self.import_param_names.is_empty() {
+      return None;
+    }
+    let importee_id = symbols.get(canonical_ref).chunk_id?;
+    let param_name = self.import_param_names.get(&importee_id)?;
+    let export_alias =
+      chunk_graph.chunks[importee_id].exports_to_other_chunks.get(&canonical_ref)?;
+    Some((param_name, export_alias))
+  }
+
+  /// Render imports from other chunks for output formats that wrap the chunk in a function.
+  ///
+  /// Each chunk that provides bindings becomes a parameter of the function, see `Chunk::import_param_names`.
+  /// The finalizer reads imported bindings from these parameters at each use, like `chunk.foo`, so they are
+  /// live. Only the symbols generated by the bundler, which are referenced by their names in the finalized
+  /// modules, are bound to local variables, like `var __toESM = chunk.__toESM;`. These are assigned once,
+  /// when the importee chunk is evaluated.
+  pub fn render_chunk_imports_for_function_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    used_names: &mut FxHashSet<String>,
+  ) -> (Vec<ChunkImportParam>, String) {
+    let mut s = String::new();
+    used_names.extend(self.import_param_names.values().map(ToString::to_string));
+    let mut imports_from_other_chunks = self.imports_from_other_chunks.iter().collect::<Vec<_>>();
+    imports_from_other_chunks.sort_unstable_by_key(|(chunk_id, _)| **chunk_id);
+
+    let params = imports_from_other_chunks
+      .into_iter()
+      .map(|(importee_id, items)| {
+        let Some(param_name) = self.import_param_names.get(importee_id) else {
+          return ChunkImportParam { importee_id: *importee_id, param_name: None };
+        };
+
+        let mut bindings = items
+          .iter()
+          .filter_map(|item| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(item.import_ref);
+            is_referenced_by_name(graph, canonical_ref).then_some((item, canonical_ref))
+          })
+          .map(|(item, canonical_ref)| {
+            let local_binding = &self.canonical_names[&canonical_ref];
+            let Specifier::Literal(export_alias) = item.export_alias.as_ref().unwrap() else {
+              panic!("should not be star import from other chunks")
+            };
+            format!("var {local_binding} = {};\n", property_access_str(param_name, export_alias))
+          })
+          .collect::<Vec<_>>();
+        bindings.sort();
+        bindings.iter().for_each(|binding| s.push_str(binding));
+
+        ChunkImportParam { importee_id: *importee_id, param_name: Some(param_name.to_string()) }
+      })
+      .collect::<Vec<_>>();
+
+    (params, s)
+  }
+}
+
+/// Whether the finalized modules refer to `canonical_ref` by its name instead of reading it from the
+/// parameter of the AMD factory. These are the symbols that the bundler generates references to: runtime
+/// helpers, the namespace objects and the wrappers of modules.
+fn is_referenced_by_name(graph: &LinkStageOutput, canonical_ref: SymbolRef) -> bool {
+  canonical_ref.owner == graph.runtime.id()
+    || graph.module_table.normal_modules[canonical_ref.owner].namespace_symbol == canonical_ref
+    || graph.metas[canonical_ref.owner].wrapper_ref == Some(canonical_ref)
+}
+
+/// A dependency in `System.register([...dependencies], ...)`, along with the body of the setter that is
+/// called with the namespace of the dependency.
+#[derive(Debug)]
+pub struct SystemDependency {
+  pub specifier: String,
+  pub setter_body: String,
+}
+
+impl Chunk {
+  /// Render imports for `OutputFormat::System`.
+  ///
+  /// Imported bindings are declared in the scope of the `System.register` callback and assigned in the
+  /// setters, like `foo = module.foo;`. Imported bindings that are re-exported by the chunk are passed to
+  /// `exports(...)` as well, so importers of the chunk see their updates.
+  ///
+  /// Returns the dependencies and the names that need to be declared.
+  pub fn render_imports_for_system(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    exported_names: &FxHashMap<SymbolRef, Vec<Rstr>>,
+  ) -> (Vec<SystemDependency>, Vec<String>) {
+    let mut declared_names = vec![];
+    let mut render_setter_body = |assignments: Vec<(SymbolRef, String)>| {
+      let mut lines = assignments
+        .into_iter()
+        .flat_map(|(canonical_ref, assignment)| {
+          let local = &self.canonical_names[&canonical_ref];
+          declared_names.push(local.to_string());
+          std::iter::once(format!("{local} = {assignment};\n")).chain(
+            exported_names
+              .get(&canonical_ref)
+              .into_iter()
+              .flatten()
+              .map(move |exported_name| format!("exports(\"{exported_name}\", {local});\n")),
+          )
+        })
+        .collect::<Vec<_>>();
+      // Several imports may be bound to the same local
+      let mut seen = FxHashSet::default();
+      lines.retain(|line| seen.insert(line.clone()));
+      lines.concat()
+    };
+
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    let mut dependencies = imports_from_external_modules
+      .into_iter()
+      .map(|(importee_id, named_imports)| {
+        let importee = &graph.module_table.external_modules[*importee_id];
+        let mut assignments = named_imports
+          .iter()
+          .map(|item| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(item.imported_as);
+            let assignment = match &item.imported {
+              Specifier::Star => "module".to_string(),
+              Specifier::Literal(imported) => property_access_str("module", imported),
+            };
+            (canonical_ref, assignment)
+          })
+          .collect::<Vec<_>>();
+        assignments
+          .sort_by_cached_key(|(canonical_ref, _)| self.canonical_names[canonical_ref].as_str());
+        SystemDependency {
+          specifier: importee.name.to_string(),
+          setter_body: render_setter_body(assignments),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let mut imports_from_other_chunks = self.imports_from_other_chunks.iter().collect::<Vec<_>>();
+    imports_from_other_chunks.sort_unstable_by_key(|(chunk_id, _)| **chunk_id);
+    imports_from_other_chunks.into_iter().for_each(|(exporter_id, items)| {
+      let importee_chunk = &chunk_graph.chunks[*exporter_id];
+      let mut assignments = items
+        .iter()
+        .map(|item| {
+          let canonical_ref = graph.symbols.par_canonical_ref_for(item.import_ref);
+          let Specifier::Literal(export_alias) = item.export_alias.as_ref().unwrap() else {
+            panic!("should not be star import from other chunks")
+          };
+          (canonical_ref, property_access_str("module", export_alias))
+        })
+        .collect::<Vec<_>>();
+      assignments
+        .sort_by_cached_key(|(canonical_ref, _)| self.canonical_names[canonical_ref].as_str());
+      dependencies.push(SystemDependency {
+        specifier: self.import_path_for(importee_chunk),
+        setter_body: render_setter_body(assignments),
+      });
+    });
+
+    declared_names.sort();
+    declared_names.dedup();
+    (dependencies, declared_names)
+  }
+}
@@ -0,0 +1,394 @@
+use oxc::ast::ast;
+use rolldown_common::{representative_name, ChunkKind, ImportKind, WrapKind};
+use rolldown_rstr::Rstr;
+use rustc_hash::FxHashSet;
+
+use crate::{
+  chunk_graph::ChunkGraph,
+  options::normalized_output_options::NormalizedOutputOptions,
+  stages::link_stage::LinkStageOutput,
+  utils::ecma_script::{property_access_str, property_key_str, string_literal_str},
+  OutputFormat,
+};
+
+use super::Chunk;
+
+/// The global variable holding the external module in environments without a module loader.
+fn global_name_of(module_id: &str, output_options: &NormalizedOutputOptions) -> String {
+  // Guess the global name from the module id if the user didn't provide one.
+  output_options
+    .globals
+    .get(module_id)
+    .cloned()
+    .unwrap_or_else(|| representative_name(module_id).into_owned())
+}
+
+impl Chunk {
+  /// Render the head and the tail of the function wrapper of the chunk. Returns `None` if the output
+  /// format doesn't wrap chunks, or wraps them in more parts, like `Chunk::render_system_wrapper`.
+  pub fn render_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &NormalizedOutputOptions,
+  ) -> Option<(String, String)> {
+    match output_options.format {
+      OutputFormat::Iife => Some(self.render_iife_wrapper(graph, output_options)),
+      OutputFormat::Umd => Some(self.render_umd_wrapper(graph, output_options)),
+      OutputFormat::Amd => Some(self.render_amd_wrapper(graph, chunk_graph, output_options)),
+      OutputFormat::Esm | OutputFormat::Cjs | OutputFormat::System => None,
+    }
+  }
+
+  /// Names that are already taken in the top-level scope of the chunk. Identifiers introduced by the
+  /// wrapper must not conflict with them.
+  fn collect_used_names(&self, graph: &LinkStageOutput) -> FxHashSet<String> {
+    self
+      .canonical_names
+      .values()
+      .map(ToString::to_string)
+      .chain(self.modules.iter().flat_map(|id| {
+        graph.module_table.normal_modules[*id]
+          .scope
+          .root_unresolved_references()
+          .keys()
+          .map(ToString::to_string)
+      }))
+      .collect()
+  }
+
+  /// Whether the exports of the chunk are assigned to an `exports` object passed into the wrapper.
+  fn needs_exports_param(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+  ) -> bool {
+    let is_wrapped_cjs_entry = matches!(
+      &self.kind,
+      ChunkKind::EntryPoint { module, .. } if matches!(graph.metas[*module].wrap_kind, WrapKind::Cjs)
+    );
+    !is_wrapped_cjs_entry && !self.get_export_names(graph, output_options).is_empty()
+  }
+
+  /// Render the head and the tail of
+  ///
+  /// ```js
+  /// var name = (function(exports, foo) {
+  ///   "use strict";
+  ///   ...
+  ///   return exports;
+  /// })({}, Foo);
+  /// ```
+  pub fn render_iife_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+  ) -> (String, String) {
+    let mut used_names = self.collect_used_names(graph);
+    let (params, bindings) = self.render_imports_for_function_wrapper(graph, &mut used_names);
+    let needs_exports_param = self.needs_exports_param(graph, output_options);
+
+    let (mut param_names, mut args): (Vec<String>, Vec<String>) = params
+      .into_iter()
+      .filter_map(|param| {
+        let param_name = param.param_name?;
+        let importee = &graph.module_table.external_modules[param.importee_id];
+        Some((param_name, global_name_of(&importee.name, output_options)))
+      })
+      .unzip();
+    if needs_exports_param {
+      param_names.insert(0, "exports".to_string());
+      args.insert(0, "{}".to_string());
+    }
+
+    let has_exports = !self.get_export_names(graph, output_options).is_empty();
+    let declaration = match &output_options.name {
+      Some(name) if has_exports => format!("var {name} = "),
+      _ => String::new(),
+    };
+
+    let mut intro =
+      format!("{declaration}(function({}) {{\n\"use strict\";\n", param_names.join(", "));
+    intro.push_str(&bindings);
+    let outro = format!("}})({});", args.join(", "));
+    (intro, outro)
+  }
+
+  /// Render the head and the tail of
+  ///
+  /// ```js
+  /// (function(global, factory) {
+  ///   typeof exports === "object" && typeof module !== "undefined" ? factory(exports, require("foo")) :
+  ///   typeof define === "function" && define.amd ? define(["exports", "foo"], factory) :
+  ///   (global = typeof globalThis !== "undefined" ? globalThis : global || self, factory(global.name = {}, global.Foo));
+  /// })(this, function(exports, foo) {
+  ///   "use strict";
+  ///   ...
+  /// });
+  /// ```
+  pub fn render_umd_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+  ) -> (String, String) {
+    let mut used_names = self.collect_used_names(graph);
+    let (params, bindings) = self.render_imports_for_function_wrapper(graph, &mut used_names);
+    let needs_exports_param = self.needs_exports_param(graph, output_options);
+    // A wrapped CommonJS entry returns its `module.exports` from the factory.
+    let returns_value =
+      !needs_exports_param && !self.get_export_names(graph, output_options).is_empty();
+
+    // Side-effect-only externals are loaded after the ones bound to a parameter, so that they don't
+    // shift the positions of the arguments.
+    let (bound, side_effect_only): (Vec<_>, Vec<_>) =
+      params.into_iter().partition(|param| param.param_name.is_some());
+    let module_ids = bound
+      .iter()
+      .chain(side_effect_only.iter())
+      .map(|param| graph.module_table.external_modules[param.importee_id].name.as_str())
+      .collect::<Vec<_>>();
+
+    let mut param_names =
+      bound.iter().filter_map(|param| param.param_name.clone()).collect::<Vec<_>>();
+    let mut cjs_args = module_ids
+      .iter()
+      .map(|id| format!("require({})", string_literal_str(id)))
+      .collect::<Vec<_>>();
+    let mut amd_deps = module_ids.iter().map(|id| string_literal_str(id)).collect::<Vec<_>>();
+    let mut global_args = bound
+      .iter()
+      .map(|param| {
+        let importee = &graph.module_table.external_modules[param.importee_id];
+        property_access_str("global", &global_name_of(&importee.name, output_options))
+      })
+      .collect::<Vec<_>>();
+
+    if needs_exports_param {
+      param_names.insert(0, "exports".to_string());
+      cjs_args.insert(0, "exports".to_string());
+      amd_deps.insert(0, "\"exports\"".to_string());
+      global_args.insert(
+        0,
+        match &output_options.name {
+          Some(name) => format!("{} = {{}}", property_access_str("global", name)),
+          None => "{}".to_string(),
+        },
+      );
+    }
+
+    let cjs_factory_call = format!("factory({})", cjs_args.join(", "));
+    let cjs_factory_call =
+      if returns_value { format!("module.exports = {cjs_factory_call}") } else { cjs_factory_call };
+    let amd_id = output_options
+      .amd
+      .id
+      .as_ref()
+      .map(|id| format!("{}, ", string_literal_str(id)))
+      .unwrap_or_default();
+    let define = output_options.amd.define.as_deref().unwrap_or("define");
+    let global_factory_call = format!("factory({})", global_args.join(", "));
+    let global_factory_call = match &output_options.name {
+      Some(name) if returns_value => {
+        format!("{} = {global_factory_call}", property_access_str("global", name))
+      }
+      _ => global_factory_call,
+    };
+
+    let mut intro = String::new();
+    intro.push_str("(function(global, factory) {\n");
+    intro.push_str(&format!(
+      "  typeof exports === \"object\" && typeof module !== \"undefined\" ? {cjs_factory_call} :\n"
+    ));
+    intro.push_str(&format!(
+      "  typeof {define} === \"function\" && {define}.amd ? {define}({amd_id}[{}], factory) :\n",
+      amd_deps.join(", ")
+    ));
+    intro.push_str(&format!(
+      "  (global = typeof globalThis !== \"undefined\" ? globalThis : global || self, {global_factory_call});\n"
+    ));
+    intro
+      .push_str(&format!("}})(this, function({}) {{\n\"use strict\";\n", param_names.join(", ")));
+    intro.push_str(&bindings);
+    let outro = "});".to_string();
+    (intro, outro)
+  }
+
+  /// Render the parts of
+  ///
+  /// ```js
+  /// System.register(["./foo.js"], function(exports, module) {
+  ///   "use strict";
+  ///   var foo;
+  ///   ... // hoisted declarations
+  ///   exports({ bar: bar });
+  ///   return {
+  ///     setters: [function(module) {
+  ///       foo = module.foo;
+  ///     }],
+  ///     execute: function() {
+  ///       ...
+  ///     }
+  ///   };
+  /// });
+  /// ```
+  ///
+  /// The top-level declarations of the modules are hoisted into the callback by the finalizer. The
+  /// exported functions among them are exported before `execute`, so they are available to the importers
+  /// even if the chunks import each other.
+  pub fn render_system_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+  ) -> (String, String, String) {
+    let exported_names = self.get_exported_names_by_symbol(graph);
+    let (dependencies, declared_names) =
+      self.render_imports_for_system(graph, chunk_graph, &exported_names);
+
+    let specifiers =
+      dependencies.iter().map(|dep| string_literal_str(&dep.specifier)).collect::<Vec<_>>();
+    let setters = dependencies
+      .iter()
+      .map(|dep| {
+        if dep.setter_body.is_empty() {
+          "function() {}".to_string()
+        } else {
+          format!("function(module) {{\n{}}}", dep.setter_body)
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let mut intro = format!(
+      "System.register([{}], function(exports, module) {{\n\"use strict\";",
+      specifiers.join(", ")
+    );
+    if !declared_names.is_empty() {
+      intro.push_str(&format!("\nvar {};", declared_names.join(", ")));
+    }
+
+    let mut execute_head = String::new();
+    let function_exports = self.render_hoisted_function_exports(graph);
+    if !function_exports.is_empty() {
+      execute_head.push_str(&format!("exports({{ {} }});\n", function_exports.join(", ")));
+    }
+    execute_head.push_str("return {\n");
+    execute_head.push_str(&format!("setters: [{}],\n", setters.join(", ")));
+    execute_head.push_str("execute: function() {\n");
+    let outro = "}\n};\n});".to_string();
+    (intro, execute_head, outro)
+  }
+
+  /// Render `name: local` for the exported functions that the finalizer hoisted out of `execute`.
+  fn render_hoisted_function_exports(&self, graph: &LinkStageOutput) -> Vec<String> {
+    let exported_names = self.get_exported_names_by_local(graph);
+    self
+      .modules
+      .iter()
+      .flat_map(|id| {
+        graph.ast_table[*id].hoisted_program().into_iter().flat_map(|program| {
+          program.body.iter().filter_map(|stmt| match stmt {
+            ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(func)) => {
+              func.id.as_ref().map(|id| Rstr::new(&id.name))
+            }
+            _ => None,
+          })
+        })
+      })
+      .flat_map(|local| {
+        exported_names
+          .get(&local)
+          .into_iter()
+          .flatten()
+          .map(move |exported_name| format!("{}: {local}", property_key_str(exported_name)))
+      })
+      .collect()
+  }
+
+  /// Render the head and the tail of
+  ///
+  /// ```js
+  /// define(["require", "exports", "foo", "./bar"], function(require, exports, foo, bar) {
+  ///   "use strict";
+  ///   ...
+  /// });
+  /// ```
+  pub fn render_amd_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &NormalizedOutputOptions,
+  ) -> (String, String) {
+    let mut used_names = self.collect_used_names(graph);
+    let (chunk_params, chunk_bindings) =
+      self.render_chunk_imports_for_function_wrapper(graph, &mut used_names);
+    let (external_params, external_bindings) =
+      self.render_imports_for_function_wrapper(graph, &mut used_names);
+
+    let mut deps = vec![];
+    let mut param_names = vec![];
+    // `require` is needed to load dynamically imported modules relative to the current module.
+    if self.has_dynamic_imports(graph, output_options) {
+      deps.push("\"require\"".to_string());
+      param_names.push("require".to_string());
+    }
+    if self.needs_exports_param(graph, output_options) {
+      deps.push("\"exports\"".to_string());
+      param_names.push("exports".to_string());
+    }
+    // Side-effect-only dependencies are listed last, so that they don't shift the positions of the parameters.
+    let mut side_effect_only_deps = vec![];
+    external_params.into_iter().for_each(|param| {
+      let dep = string_literal_str(&graph.module_table.external_modules[param.importee_id].name);
+      match param.param_name {
+        Some(param_name) => {
+          deps.push(dep);
+          param_names.push(param_name);
+        }
+        None => side_effect_only_deps.push(dep),
+      }
+    });
+    chunk_params.into_iter().for_each(|param| {
+      let dep =
+        string_literal_str(&self.amd_import_path_for(&chunk_graph.chunks[param.importee_id]));
+      match param.param_name {
+        Some(param_name) => {
+          deps.push(dep);
+          param_names.push(param_name);
+        }
+        None => side_effect_only_deps.push(dep),
+      }
+    });
+    deps.extend(side_effect_only_deps);
+
+    let amd_id = match (&output_options.amd.id, output_options.amd.auto_id) {
+      (Some(id), _) => format!("{}, ", string_literal_str(id)),
+      (None, Some(true)) => format!("{}, ", string_literal_str(self.amd_id())),
+      _ => String::new(),
+    };
+    let define = output_options.amd.define.as_deref().unwrap_or("define");
+
+    let mut intro = format!(
+      "{define}({amd_id}[{}], function({}) {{\n\"use strict\";\n",
+      deps.join(", "),
+      param_names.join(", ")
+    );
+    intro.push_str(&external_bindings);
+    intro.push_str(&chunk_bindings);
+    let outro = "});".to_string();
+    (intro, outro)
+  }
+
+  /// Whether any module of the chunk keeps an `import()` expression. Inlined dynamic imports of bundled
+  /// modules don't count.
+  fn has_dynamic_imports(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+  ) -> bool {
+    self.modules.iter().any(|id| {
+      graph.module_table.normal_modules[*id].import_records.iter().any(|rec| {
+        matches!(rec.kind, ImportKind::DynamicImport)
+          && (!output_options.inline_dynamic_imports || rec.resolved_module.as_external().is_some())
+      })
+    })
+  }
+}
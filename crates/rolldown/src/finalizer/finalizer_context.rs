@@ -5,6 +5,7 @@ use rustc_hash::FxHashMap;
 
 use crate::{
   chunk_graph::ChunkGraph,
+  options::normalized_output_options::NormalizedOutputOptions,
   runtime::RuntimeModuleBrief,
   types::{
     linking_metadata::{LinkingMetadata, LinkingMetadataVec},
@@ -23,4 +24,9 @@ pub struct FinalizerContext<'me> {
   pub canonical_names: &'me FxHashMap<SymbolRef, Rstr>,
   pub runtime: &'me RuntimeModuleBrief,
   pub chunk_graph: &'me ChunkGraph,
+  pub output_options: &'me NormalizedOutputOptions,
+  /// Exported names of the symbols in the chunk, keyed by the canonical name of the symbol. Only collected
+  /// for `OutputFormat::System` and `OutputFormat::Amd`, where assignments to exported symbols must be
+  /// reported via `exports(name, value)` or `exports.name = value`.
+  pub live_binding_exports: &'me FxHashMap<Rstr, Vec<Rstr>>,
 }
@@ -1,7 +1,6 @@
 // cSpell:disable
 
 use oxc::{
-  allocator,
   ast::{
     ast::{self, SimpleAssignmentTarget},
     visit::walk_mut::walk_expression_mut,
@@ -10,9 +9,10 @@ use oxc::{
   span::{Span, SPAN},
 };
 use rolldown_common::{ExportsKind, ModuleId, SymbolRef, WrapKind};
-use rolldown_oxc_utils::{Dummy, ExpressionExt, IntoIn, StatementExt, TakeIn};
+use rolldown_oxc_utils::{ExpressionExt, IntoIn, StatementExt, TakeIn};
 
 use super::Finalizer;
+use crate::OutputFormat;
 
 impl<'ast, 'me: 'ast> Finalizer<'me, 'ast> {
   fn visit_top_level_statement_mut(&mut self, stmt: &mut ast::Statement<'ast>) {
@@ -209,56 +209,11 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
           let esm_ref_name = self.canonical_name_for_runtime("__esmMin");
           let old_body = program.body.take_in(self.alloc);
 
-          let mut fn_stmts = allocator::Vec::new_in(self.alloc);
-          let mut hoisted_names = vec![];
-          let mut stmts_inside_closure = allocator::Vec::new_in(self.alloc);
-
           // Hoist all top-level "var" and "function" declarations out of the closure
-          old_body.into_iter().for_each(|mut stmt| match &mut stmt {
-            ast::Statement::Declaration(decl) => match decl {
-              ast::Declaration::VariableDeclaration(_) | ast::Declaration::ClassDeclaration(_) => {
-                if let Some(converted) = self.convert_decl_to_assignment(decl, &mut hoisted_names) {
-                  stmts_inside_closure.push(converted);
-                }
-              }
-              ast::Declaration::FunctionDeclaration(_) => {
-                fn_stmts.push(stmt);
-              }
-              ast::Declaration::UsingDeclaration(_) => unimplemented!(),
-              _ => {}
-            },
-            ast::Statement::ModuleDeclaration(_) => unreachable!(
-              "At this point, all module declarations should have been removed or transformed"
-            ),
-            _ => {
-              stmts_inside_closure.push(stmt);
-            }
-          });
+          let (fn_stmts, var_decl_stmt, stmts_inside_closure) =
+            self.hoist_top_level_declarations(old_body);
           program.body.extend(fn_stmts);
-          if !hoisted_names.is_empty() {
-            let mut declarators = allocator::Vec::new_in(self.alloc);
-            declarators.reserve_exact(hoisted_names.len());
-            hoisted_names.into_iter().for_each(|var_name| {
-              declarators.push(ast::VariableDeclarator {
-                id: ast::BindingPattern {
-                  kind: ast::BindingPatternKind::BindingIdentifier(
-                    self.snippet.id(&var_name, SPAN).into_in(self.alloc),
-                  ),
-                  ..Dummy::dummy(self.alloc)
-                },
-                kind: ast::VariableDeclarationKind::Var,
-                ..Dummy::dummy(self.alloc)
-              });
-            });
-            program.body.push(ast::Statement::Declaration(ast::Declaration::VariableDeclaration(
-              ast::VariableDeclaration {
-                declarations: declarators,
-                kind: ast::VariableDeclarationKind::Var,
-                ..Dummy::dummy(self.alloc)
-              }
-              .into_in(self.alloc),
-            )));
-          }
+          program.body.extend(var_decl_stmt);
           program.body.push(self.snippet.esm_wrapper_stmt(
             wrap_ref_name,
             esm_ref_name,
@@ -268,6 +223,16 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
         WrapKind::None => {}
       }
     }
+
+    if matches!(self.ctx.output_options.format, OutputFormat::System) {
+      // Top-level declarations are hoisted out of `execute` into the `System.register` callback, so exported
+      // functions are available to the importers before `execute` is called, even in circular imports.
+      let old_body = program.body.take_in(self.alloc);
+      let (fn_stmts, var_decl_stmt, rest_stmts) = self.hoist_top_level_declarations(old_body);
+      program.body.extend(var_decl_stmt);
+      program.body.extend(fn_stmts);
+      program.body.extend(rest_stmts);
+    }
   }
 
   fn visit_binding_identifier(&mut self, ident: &mut ast::BindingIdentifier<'ast>) {
@@ -299,6 +264,7 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
 
   fn visit_call_expression(&mut self, expr: &mut ast::CallExpression<'ast>) {
     self.try_rewrite_identifier_reference_expr(&mut expr.callee, true);
+    self.try_rewrite_namespace_member_expr(&mut expr.callee, true);
 
     // visit children
     for arg in expr.arguments.iter_mut() {
@@ -312,6 +278,8 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
 
   #[allow(clippy::collapsible_else_if)]
   fn visit_expression(&mut self, expr: &mut ast::Expression<'ast>) {
+    let updated_exported_symbol = self.exported_symbol_updated_by(expr);
+
     if let Some(call_expr) = expr.as_call_expression() {
       // Rewrite `require(...)` to `require_xxx(...)` or `(init_xxx(), __toCommonJS(xxx_exports))`
       if let ast::Expression::Identifier(callee) = &call_expr.callee {
@@ -338,9 +306,16 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
     }
 
     self.try_rewrite_identifier_reference_expr(expr, false);
+    self.try_rewrite_namespace_member_expr(expr, false);
 
     // visit children
     walk_expression_mut(self, expr);
+
+    if let Some((local, exported_names)) = updated_exported_symbol {
+      self.report_live_binding_update(expr, local, exported_names);
+    }
+    self.try_rewrite_inlined_dynamic_import(expr);
+    self.rewrite_import_expression_for_format(expr);
   }
 
   fn visit_object_property(&mut self, prop: &mut ast::ObjectProperty<'ast>) {
@@ -420,6 +395,8 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
   fn visit_import_expression(&mut self, expr: &mut ast::ImportExpression<'ast>) {
     // Make sure the import expression is in correct form. If it's not, we should leave it as it is.
     match &mut expr.source {
+      // Inlined dynamic imports are rewritten as a whole in `try_rewrite_inlined_dynamic_import`
+      ast::Expression::StringLiteral(_) if self.ctx.output_options.inline_dynamic_imports => {}
       ast::Expression::StringLiteral(str) if expr.arguments.len() == 0 => {
         let rec_id = self.ctx.module.imports[&expr.span];
         let rec = &self.ctx.module.import_records[rec_id];
@@ -429,7 +406,14 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
             let chunk_id = self.ctx.chunk_graph.module_to_chunk[importee_id]
               .expect("Normal module should belong to a chunk");
             let chunk = &self.ctx.chunk_graph.chunks[chunk_id];
-            str.value = self.snippet.atom(&format!("./{}", chunk.file_name.as_ref().unwrap()));
+            let importer_chunk = &self.ctx.chunk_graph.chunks
+              [self.ctx.chunk_graph.module_to_chunk[self.ctx.id].expect("Should be in a chunk")];
+            let specifier = if matches!(self.ctx.output_options.format, OutputFormat::Amd) {
+              importer_chunk.amd_import_path_for(chunk)
+            } else {
+              importer_chunk.import_path_for(chunk)
+            };
+            str.value = self.snippet.atom(&specifier);
           }
           ModuleId::External(_) => {
             // external module doesn't belong to any chunk, just keep this as it is
@@ -1,5 +1,5 @@
 use oxc::{
-  allocator::Allocator,
+  allocator::{self, Allocator},
   ast::ast::{self, IdentifierReference, Statement},
   span::{Atom, SPAN},
 };
@@ -10,6 +10,7 @@ mod finalizer_context;
 mod impl_visit_mut_for_finalizer;
 pub use finalizer_context::FinalizerContext;
 use rolldown_rstr::Rstr;
+mod output_format;
 mod rename;
 
 pub struct Finalizer<'me, 'ast> {
@@ -41,6 +42,46 @@ where
     self.canonical_name_for(symbol)
   }
 
+  /// Rewrite `import('./foo')` of an inlined module to something like
+  /// - `Promise.resolve().then(() => foo_exports)`
+  /// - `Promise.resolve().then(() => (init_foo(), foo_exports))` for modules wrapped as ESM
+  /// - `Promise.resolve().then(() => __toESM(require_foo()))` for modules wrapped as CommonJS
+  fn try_rewrite_inlined_dynamic_import(&self, expr: &mut ast::Expression<'ast>) {
+    if !self.ctx.output_options.inline_dynamic_imports {
+      return;
+    }
+    let ast::Expression::ImportExpression(import_expr) = expr else {
+      return;
+    };
+    let Some(rec_id) = self.ctx.module.imports.get(&import_expr.span) else {
+      return;
+    };
+    let ModuleId::Normal(importee_id) = self.ctx.module.import_records[*rec_id].resolved_module
+    else {
+      return;
+    };
+    let importee = &self.ctx.modules[importee_id];
+    let importee_linking_info = &self.ctx.linking_infos[importee_id];
+    let value = match importee_linking_info.wrap_kind {
+      WrapKind::None => {
+        self.snippet.id_ref_expr(self.canonical_name_for(importee.namespace_symbol), SPAN)
+      }
+      WrapKind::Cjs => self.snippet.call_expr_with_arg_expr_expr(
+        self.canonical_name_for_runtime("__toESM"),
+        self
+          .snippet
+          .call_expr_expr(self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap())),
+      ),
+      WrapKind::Esm => self.snippet.seq2_in_paren_expr(
+        self
+          .snippet
+          .call_expr_expr(self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap())),
+        self.snippet.id_ref_expr(self.canonical_name_for(importee.namespace_symbol), SPAN),
+      ),
+    };
+    *expr = self.snippet.promise_resolve_then_expr(value);
+  }
+
   fn should_remove_import_export_stmt(
     &self,
     stmt: &mut Statement<'ast>,
@@ -84,18 +125,119 @@ where
     let symbol = self.ctx.symbols.get(canonical_ref);
 
     if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr =
-        self.snippet.literal_prop_access_member_expr_expr(canonical_ns_name, prop_name);
-
-      access_expr
+      ast::Expression::MemberExpression(
+        self
+          .namespace_alias_member_expr(ns_alias.namespace_ref, &ns_alias.property_name)
+          .into_in(self.alloc),
+      )
     } else {
-      let canonical_name = self.canonical_name_for(canonical_ref);
-      self.snippet.id_ref_expr(canonical_name, SPAN)
+      self
+        .cross_chunk_import_expr(canonical_ref)
+        .unwrap_or_else(|| self.snippet.id_ref_expr(self.canonical_name_for(canonical_ref), SPAN))
+    }
+  }
+
+  /// For `OutputFormat::Amd`, imports from other chunks are read from the parameter of the factory that
+  /// receives the importee chunk at each use, like `chunk.foo`, so they are live bindings. Returns `None` if
+  /// `canonical_ref` isn't imported from another chunk.
+  fn cross_chunk_import_expr(&self, canonical_ref: SymbolRef) -> Option<ast::Expression<'ast>> {
+    let chunk_id = self.ctx.chunk_graph.module_to_chunk[self.ctx.id]?;
+    let (param_name, export_alias) = self.ctx.chunk_graph.chunks[chunk_id]
+      .cross_chunk_import_access(self.ctx.symbols, self.ctx.chunk_graph, canonical_ref)?;
+    Some(self.snippet.literal_prop_access_member_expr_expr(param_name, export_alias))
+  }
+
+  /// `ns.prop` for a symbol with a `NamespaceAlias`, where `ns` might be imported from another chunk.
+  fn namespace_alias_member_expr(
+    &self,
+    namespace_ref: SymbolRef,
+    prop_name: &str,
+  ) -> ast::MemberExpression<'ast> {
+    let ns_expr = self
+      .cross_chunk_import_expr(namespace_ref)
+      .unwrap_or_else(|| self.snippet.id_ref_expr(self.canonical_name_for(namespace_ref), SPAN));
+    self.snippet.literal_prop_access_member_expr_with_object(ns_expr, prop_name)
+  }
+
+  /// The exported names of the top-level binding `name` of the chunk, if its value needs to be reported to
+  /// the importers. See `FinalizerContext::live_binding_exports`.
+  fn live_binding_exported_names(&self, name: &str) -> Option<&'me [Rstr]> {
+    if self.ctx.live_binding_exports.is_empty() {
+      return None;
     }
+    self.ctx.live_binding_exports.get(&Rstr::new(name)).map(Vec::as_slice)
+  }
+
+  /// Hoist top-level declarations out of `stmts`. Returns
+  /// - the function declarations
+  /// - a `var` declaration of the names declared by the other declarations
+  /// - the remaining statements, where the other declarations are turned into assignments
+  fn hoist_top_level_declarations(
+    &self,
+    stmts: allocator::Vec<'ast, ast::Statement<'ast>>,
+  ) -> (
+    allocator::Vec<'ast, ast::Statement<'ast>>,
+    Option<ast::Statement<'ast>>,
+    allocator::Vec<'ast, ast::Statement<'ast>>,
+  ) {
+    let mut fn_stmts = allocator::Vec::new_in(self.alloc);
+    let mut hoisted_names = vec![];
+    let mut rest_stmts = allocator::Vec::new_in(self.alloc);
+
+    stmts.into_iter().for_each(|mut stmt| match &mut stmt {
+      ast::Statement::Declaration(decl) => match decl {
+        ast::Declaration::VariableDeclaration(_) | ast::Declaration::ClassDeclaration(_) => {
+          if let Some(converted) = self.convert_decl_to_assignment(decl, &mut hoisted_names) {
+            rest_stmts.push(converted);
+          }
+        }
+        ast::Declaration::FunctionDeclaration(_) => {
+          fn_stmts.push(stmt);
+        }
+        ast::Declaration::UsingDeclaration(_) => unimplemented!(),
+        _ => {}
+      },
+      ast::Statement::ModuleDeclaration(_) => {
+        unreachable!(
+          "At this point, all module declarations should have been removed or transformed"
+        )
+      }
+      _ => {
+        rest_stmts.push(stmt);
+      }
+    });
+
+    let var_decl_stmt = (!hoisted_names.is_empty()).then(|| {
+      let mut declarators = allocator::Vec::new_in(self.alloc);
+      declarators.reserve_exact(hoisted_names.len());
+      hoisted_names.into_iter().for_each(|var_name| {
+        declarators.push(ast::VariableDeclarator {
+          id: ast::BindingPattern {
+            kind: ast::BindingPatternKind::BindingIdentifier(
+              self.snippet.id(&var_name, SPAN).into_in(self.alloc),
+            ),
+            ..Dummy::dummy(self.alloc)
+          },
+          kind: ast::VariableDeclarationKind::Var,
+          ..Dummy::dummy(self.alloc)
+        });
+      });
+      ast::Statement::Declaration(ast::Declaration::VariableDeclaration(
+        ast::VariableDeclaration {
+          declarations: declarators,
+          kind: ast::VariableDeclarationKind::Var,
+          ..Dummy::dummy(self.alloc)
+        }
+        .into_in(self.alloc),
+      ))
+    });
+
+    (fn_stmts, var_decl_stmt, rest_stmts)
   }
 
+  /// Turn a declaration into assignments, like `var a = 1, b` => `a = 1`, and push the declared names into
+  /// `hoisted_names`. If the value of a declared name needs to be reported to the importers, the assignment
+  /// reports it, like `exports("a", a = 1)`.
   fn convert_decl_to_assignment(
     &self,
     decl: &mut ast::Declaration<'ast>,
@@ -105,20 +247,39 @@ where
       ast::Declaration::VariableDeclaration(var_decl) => {
         let mut seq_expr = ast::SequenceExpression::dummy(self.alloc);
         var_decl.declarations.iter_mut().for_each(|var_decl| {
-          var_decl.id.binding_identifiers().iter().for_each(|id| {
-            hoisted_names.push(id.name.clone());
-          });
+          let names =
+            var_decl.id.binding_identifiers().iter().map(|id| id.name.clone()).collect::<Vec<_>>();
+          hoisted_names.extend(names.iter().cloned());
           // Turn `var ... = ...` to `... = ...`
           if let Some(init_expr) = &mut var_decl.init {
+            let is_simple_id =
+              matches!(var_decl.id.kind, ast::BindingPatternKind::BindingIdentifier(_));
             let left = var_decl.id.take_in(self.alloc).into_assignment_target(self.alloc);
-            seq_expr.expressions.push(ast::Expression::AssignmentExpression(
+            let mut assignment = ast::Expression::AssignmentExpression(
               ast::AssignmentExpression {
                 left,
                 right: init_expr.take_in(self.alloc),
                 ..Dummy::dummy(self.alloc)
               }
               .into_in(self.alloc),
-            ));
+            );
+            if is_simple_id {
+              // `a = 1` => `exports("a", a = 1)`
+              if let Some(exported_names) = self.live_binding_exported_names(&names[0]) {
+                self.report_live_binding_update(&mut assignment, &names[0], exported_names);
+              }
+              seq_expr.expressions.push(assignment);
+            } else {
+              // `({ a } = foo)` => `({ a } = foo), exports("a", a)`
+              seq_expr.expressions.push(assignment);
+              names.iter().for_each(|name| {
+                if let Some(exported_names) = self.live_binding_exported_names(name) {
+                  let mut value = self.snippet.id_ref_expr(name, SPAN);
+                  self.report_live_binding_update(&mut value, name, exported_names);
+                  seq_expr.expressions.push(value);
+                }
+              });
+            }
           };
         });
         if seq_expr.expressions.is_empty() {
@@ -137,19 +298,20 @@ where
         let cls_name = cls_decl.id.take().expect("should have a name at this point").name;
         hoisted_names.push(cls_name.clone());
         // Turn `class xxx {}` to `xxx = class {}`
-        Some(ast::Statement::ExpressionStatement(
-          ast::ExpressionStatement {
-            expression: ast::Expression::AssignmentExpression(
-              ast::AssignmentExpression {
-                left: self.snippet.simple_id_assignment_target(&cls_name, cls_decl.span),
-                right: ast::Expression::ClassExpression(cls_decl.take_in(self.alloc)),
-                ..Dummy::dummy(self.alloc)
-              }
-              .into_in(self.alloc),
-            ),
+        let mut assignment = ast::Expression::AssignmentExpression(
+          ast::AssignmentExpression {
+            left: self.snippet.simple_id_assignment_target(&cls_name, cls_decl.span),
+            right: ast::Expression::ClassExpression(cls_decl.take_in(self.alloc)),
             ..Dummy::dummy(self.alloc)
           }
           .into_in(self.alloc),
+        );
+        if let Some(exported_names) = self.live_binding_exported_names(&cls_name) {
+          self.report_live_binding_update(&mut assignment, &cls_name, exported_names);
+        }
+        Some(ast::Statement::ExpressionStatement(
+          ast::ExpressionStatement { expression: assignment, ..Dummy::dummy(self.alloc) }
+            .into_in(self.alloc),
         ))
       }
       ast::Declaration::FunctionDeclaration(_) => {
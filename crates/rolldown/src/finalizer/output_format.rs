@@ -0,0 +1,116 @@
+use oxc::{
+  allocator,
+  ast::ast::{self, SimpleAssignmentTarget},
+  span::SPAN,
+  syntax::operator::{BinaryOperator, UpdateOperator},
+};
+use rolldown_oxc_utils::TakeIn;
+use rolldown_rstr::Rstr;
+
+use super::Finalizer;
+use crate::OutputFormat;
+
+impl<'me, 'ast> Finalizer<'me, 'ast>
+where
+  'me: 'ast,
+{
+  /// If `expr` assigns to or updates an exported symbol, return the canonical name of the symbol and its
+  /// exported names.
+  ///
+  /// This needs to be called before the identifiers of `expr` are rewritten, which drops their `ReferenceId`s.
+  pub fn exported_symbol_updated_by(
+    &self,
+    expr: &ast::Expression<'ast>,
+  ) -> Option<(&'me Rstr, &'me [Rstr])> {
+    if self.ctx.live_binding_exports.is_empty() {
+      return None;
+    }
+    let target = match expr {
+      ast::Expression::AssignmentExpression(assign_expr) => match &assign_expr.left {
+        ast::AssignmentTarget::SimpleAssignmentTarget(target) => target,
+        ast::AssignmentTarget::AssignmentTargetPattern(_) => return None,
+      },
+      ast::Expression::UpdateExpression(update_expr) => &update_expr.argument,
+      _ => return None,
+    };
+    let SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref) = target else {
+      return None;
+    };
+    let symbol_id = self.scope.symbol_id_for(id_ref.reference_id.get()?)?;
+    let canonical_ref = self.ctx.symbols.par_canonical_ref_for((self.ctx.id, symbol_id).into());
+    let local = self.canonical_name_for(canonical_ref);
+    let exported_names = self.ctx.live_binding_exports.get(local)?;
+    Some((local, exported_names.as_slice()))
+  }
+
+  /// Report the new value of an updated exported symbol to the importers, like
+  ///
+  /// - `foo = 1` => `exports("foo", foo = 1)`
+  /// - `++foo` => `exports("foo", ++foo)`
+  /// - `foo++` => `(exports("foo", foo + 1), foo++)`
+  ///
+  /// for `OutputFormat::System`, or `exports.foo = foo = 1` and so on for `OutputFormat::Amd`.
+  pub fn report_live_binding_update(
+    &self,
+    expr: &mut ast::Expression<'ast>,
+    local: &str,
+    exported_names: &[Rstr],
+  ) {
+    let wrap_with_exports_calls = |value: ast::Expression<'ast>| {
+      exported_names.iter().rev().fold(value, |value, exported_name| {
+        if matches!(self.ctx.output_options.format, OutputFormat::Amd) {
+          self.snippet.assign_to_literal_prop_expr("exports", exported_name, value)
+        } else {
+          self.snippet.call_expr_with_2arg_expr_expr(
+            "exports",
+            self.snippet.string_literal_expr(exported_name, SPAN),
+            value,
+          )
+        }
+      })
+    };
+    match expr {
+      ast::Expression::UpdateExpression(update_expr) if !update_expr.prefix => {
+        let operator = match update_expr.operator {
+          UpdateOperator::Increment => BinaryOperator::Addition,
+          UpdateOperator::Decrement => BinaryOperator::Subtraction,
+        };
+        let new_value = self.snippet.binary_expr(
+          self.snippet.id_ref_expr(local, SPAN),
+          operator,
+          self.snippet.number_expr(1.0),
+        );
+        *expr = self
+          .snippet
+          .seq2_in_paren_expr(wrap_with_exports_calls(new_value), expr.take_in(self.alloc));
+      }
+      _ => {
+        *expr = wrap_with_exports_calls(expr.take_in(self.alloc));
+      }
+    }
+  }
+
+  /// Rewrite `import("./foo.js")` for output formats that don't support dynamic imports natively
+  ///
+  /// - `OutputFormat::System`: `module.import("./foo.js")`
+  /// - `OutputFormat::Amd`: `new Promise((resolve) => require(["./foo"], resolve))`
+  pub fn rewrite_import_expression_for_format(&self, expr: &mut ast::Expression<'ast>) {
+    let ast::Expression::ImportExpression(import_expr) = expr else {
+      return;
+    };
+    match self.ctx.output_options.format {
+      OutputFormat::System => {
+        let mut arguments = allocator::Vec::new_in(self.alloc);
+        arguments.push(ast::Argument::Expression(import_expr.source.take_in(self.alloc)));
+        *expr = self.snippet.call_expr_with_callee_expr(
+          self.snippet.literal_prop_access_member_expr_expr("module", "import"),
+          arguments,
+        );
+      }
+      OutputFormat::Amd => {
+        *expr = self.snippet.require_in_promise_expr(import_expr.source.take_in(self.alloc));
+      }
+      OutputFormat::Esm | OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd => {}
+    }
+  }
+}
@@ -1,4 +1,7 @@
-use oxc::ast::ast::{self, IdentifierReference};
+use oxc::{
+  ast::ast::{self, IdentifierReference},
+  span::GetSpan,
+};
 use rolldown_common::SymbolRef;
 use rolldown_oxc_utils::{ExpressionExt, IntoIn};
 
@@ -27,12 +30,16 @@ where
     let canonical_ref = self.ctx.symbols.par_canonical_ref_for(symbol_ref);
     let symbol = self.ctx.symbols.get(canonical_ref);
 
-    if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr =
-        self.snippet.literal_prop_access_member_expr_expr(canonical_ns_name, prop_name);
-
+    let access_expr = if let Some(ns_alias) = &symbol.namespace_alias {
+      Some(ast::Expression::MemberExpression(
+        self
+          .namespace_alias_member_expr(ns_alias.namespace_ref, &ns_alias.property_name)
+          .into_in(self.alloc),
+      ))
+    } else {
+      self.cross_chunk_import_expr(canonical_ref)
+    };
+    if let Some(access_expr) = access_expr {
       return Some(if is_callee {
         // `foo()` might be transformed to `xxx.foo()`. To keep the semantic of callee's `this` binding,
         // we need to wrap the transformed callee. Make it like `(0, xxx.foo)()`.
@@ -71,9 +78,8 @@ where
     let symbol = self.ctx.symbols.get(canonical_ref);
 
     if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr = self.snippet.literal_prop_access_member_expr(canonical_ns_name, prop_name);
+      let access_expr =
+        self.namespace_alias_member_expr(ns_alias.namespace_ref, &ns_alias.property_name);
 
       return Some(ast::SimpleAssignmentTarget::MemberAssignmentTarget(
         access_expr.into_in(self.alloc),
@@ -105,6 +111,29 @@ where
     }
   }
 
+  /// Rewrites `ns.foo` to the export it reads, if it's resolved at link time.
+  pub fn try_rewrite_namespace_member_expr(
+    &mut self,
+    expr: &mut ast::Expression<'ast>,
+    is_callee: bool,
+  ) {
+    if !matches!(expr, ast::Expression::MemberExpression(_)) {
+      return;
+    }
+    let Some(symbol_ref) = self.ctx.linking_info.resolved_namespace_members.get(&expr.span())
+    else {
+      return;
+    };
+    let new_expr = self.generate_finalized_expr_for_symbol_ref(*symbol_ref);
+    *expr = if is_callee && matches!(new_expr, ast::Expression::MemberExpression(_)) {
+      // The export might be a property of another namespace, like `require_foo().foo`. Keep the callee's
+      // `this` binding unchanged, like for `foo()` in `generate_finalized_expr_for_reference`.
+      self.snippet.seq2_in_paren_expr(self.snippet.number_expr(0.0), new_expr)
+    } else {
+      new_expr
+    };
+  }
+
   pub fn rewrite_simple_assignment_target(
     &mut self,
     simple_target: &mut ast::SimpleAssignmentTarget<'ast>,
@@ -128,9 +157,8 @@ where
     let symbol = self.ctx.symbols.get(canonical_ref);
 
     if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr = self.snippet.literal_prop_access_member_expr(canonical_ns_name, prop_name);
+      let access_expr =
+        self.namespace_alias_member_expr(ns_alias.namespace_ref, &ns_alias.property_name);
       *simple_target =
         ast::SimpleAssignmentTarget::MemberAssignmentTarget(access_expr.into_in(self.alloc));
     } else {
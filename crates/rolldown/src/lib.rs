@@ -27,8 +27,15 @@ pub use crate::{
     file_name_template::FileNameTemplate,
     input_options::{resolve_options::ResolveOptions, External, InputOptions},
     output_options::{OutputFormat, OutputOptions, SourceMapType},
+    types::advanced_chunks_options::{AdvancedChunksOptions, ChunkGroupOptions},
+    types::amd_options::AmdOptions,
     types::input_item::InputItem,
-    types::output_option::AddonOutputOption,
+    types::manual_chunks_option::ManualChunksOption,
+    types::minify_options::MinifyOptions,
+    types::output_option::{AddonOutputOption, FileNamesOutputOption},
+    types::sourcemap_ignore_list::SourcemapIgnoreList,
+    types::sourcemap_path_transform::SourcemapPathTransform,
+    types::treeshake_options::{InnerTreeshakeOptions, ModuleSideEffects, TreeshakeOptions},
   },
   types::rolldown_output::RolldownOutput,
 };
@@ -22,6 +22,7 @@ use crate::runtime::RuntimeModuleBrief;
 use crate::types::module_table::{ExternalModuleVec, ModuleTable};
 use crate::types::resolved_request_info::ResolvedRequestInfo;
 use crate::types::symbols::Symbols;
+use crate::utils::define::Defines;
 
 use crate::error::{BatchedErrors, BatchedResult};
 use crate::SharedResolver;
@@ -73,6 +74,7 @@ impl ModuleLoader {
     plugin_driver: SharedPluginDriver,
     fs: OsFileSystem,
     resolver: SharedResolver,
+    defines: Defines,
   ) -> Self {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Msg>();
 
@@ -82,6 +84,7 @@ impl ModuleLoader {
       resolver,
       fs,
       plugin_driver,
+      defines,
     };
 
     Self {
@@ -119,6 +122,8 @@ impl ModuleLoader {
             id,
             module_path,
             info.module_type,
+            info.side_effects,
+            info.package_json_side_effects,
           );
           tokio::spawn(async move { task.run().await });
           id.into()
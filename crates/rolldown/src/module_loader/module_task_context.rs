@@ -1,7 +1,10 @@
 use rolldown_fs::OsFileSystem;
 use rolldown_plugin::SharedPluginDriver;
 
-use crate::{options::normalized_input_options::SharedNormalizedInputOptions, SharedResolver};
+use crate::{
+  options::normalized_input_options::SharedNormalizedInputOptions, utils::define::Defines,
+  SharedResolver,
+};
 
 use super::Msg;
 
@@ -12,6 +15,7 @@ pub struct ModuleTaskCommonData {
   pub resolver: SharedResolver,
   pub fs: OsFileSystem,
   pub plugin_driver: SharedPluginDriver,
+  pub defines: Defines,
 }
 
 impl ModuleTaskCommonData {
@@ -7,6 +7,7 @@ use rolldown_common::{
   AstScope, FilePath, ImportRecordId, ModuleType, NormalModuleId, RawImportRecord, ResolvedPath,
   ResourceId, SymbolRef,
 };
+use rolldown_error::BuildError;
 use rolldown_oxc_utils::{OxcCompiler, OxcProgram};
 use rolldown_plugin::{HookResolveIdExtraOptions, SharedPluginDriver};
 use sugar_path::AsPath;
@@ -21,7 +22,10 @@ use crate::{
     ast_symbols::AstSymbols, normal_module_builder::NormalModuleBuilder,
     resolved_request_info::ResolvedRequestInfo,
   },
-  utils::{load_source::load_source, resolve_id::resolve_id, transform_source::transform_source},
+  utils::{
+    dead_branch::fold_dead_branches, load_source::load_source, resolve_id::resolve_id,
+    transform_source::transform_source,
+  },
   SharedResolver,
 };
 pub struct NormalModuleTask<'task> {
@@ -29,6 +33,8 @@ pub struct NormalModuleTask<'task> {
   module_id: NormalModuleId,
   resolved_path: ResolvedPath,
   module_type: ModuleType,
+  side_effects: Option<bool>,
+  package_json_side_effects: Option<bool>,
   errors: BatchedErrors,
 }
 
@@ -38,8 +44,18 @@ impl<'task> NormalModuleTask<'task> {
     id: NormalModuleId,
     path: ResolvedPath,
     module_type: ModuleType,
+    side_effects: Option<bool>,
+    package_json_side_effects: Option<bool>,
   ) -> Self {
-    Self { ctx, module_id: id, resolved_path: path, module_type, errors: BatchedErrors::default() }
+    Self {
+      ctx,
+      module_id: id,
+      resolved_path: path,
+      module_type,
+      side_effects,
+      package_json_side_effects,
+      errors: BatchedErrors::default(),
+    }
   }
 
   pub async fn run(mut self) {
@@ -67,6 +83,7 @@ impl<'task> NormalModuleTask<'task> {
       &self.resolved_path,
       &self.ctx.fs,
       &mut sourcemap_chain,
+      &mut self.side_effects,
     )
     .await
     {
@@ -83,6 +100,7 @@ impl<'task> NormalModuleTask<'task> {
       &self.resolved_path,
       source,
       &mut sourcemap_chain,
+      &mut self.side_effects,
     )
     .await
     {
@@ -93,6 +111,14 @@ impl<'task> NormalModuleTask<'task> {
       }
     };
 
+    let side_effects = match self.determine_side_effects().await {
+      Ok(side_effects) => side_effects,
+      Err(err) => {
+        self.errors.push(err);
+        return Ok(());
+      }
+    };
+
     let (ast, scope, scan_result, ast_symbol, namespace_symbol) = self.scan(&source);
     tracing::trace!("scan {:?}", self.resolved_path);
 
@@ -107,6 +133,7 @@ impl<'task> NormalModuleTask<'task> {
       default_export_ref,
       imports,
       exports_kind,
+      commonjs_exports,
       repr_name,
       warnings: scan_warnings,
     } = scan_result;
@@ -125,10 +152,12 @@ impl<'task> NormalModuleTask<'task> {
       default_export_ref,
       scope: Some(scope),
       exports_kind: Some(exports_kind),
+      commonjs_exports: Some(commonjs_exports),
       namespace_symbol: Some(namespace_symbol),
       module_type: self.module_type,
       pretty_path: Some(self.resolved_path.prettify(&self.ctx.input_options.cwd)),
       sourcemap_chain,
+      side_effects,
       ..Default::default()
     };
 
@@ -149,6 +178,19 @@ impl<'task> NormalModuleTask<'task> {
     Ok(())
   }
 
+  /// The plugin hooks decide first, then the `module_side_effects` option, and then package.json.
+  async fn determine_side_effects(&self) -> Result<Option<bool>, BuildError> {
+    if self.side_effects.is_some() {
+      return Ok(self.side_effects);
+    }
+    let mut side_effects = None;
+    if let Some(module_side_effects) = &self.ctx.input_options.module_side_effects {
+      side_effects =
+        module_side_effects.call(&self.resolved_path.path, &self.ctx.input_options.cwd).await?;
+    }
+    Ok(side_effects.or(self.package_json_side_effects))
+  }
+
   fn scan(&self, source: &Arc<str>) -> (OxcProgram, AstScope, ScanResult, AstSymbols, SymbolRef) {
     fn determine_oxc_source_type(path: impl AsRef<Path>, ty: ModuleType) -> SourceType {
       // Determine oxc source type for parsing
@@ -175,6 +217,11 @@ impl<'task> NormalModuleTask<'task> {
     let source_type =
       determine_oxc_source_type(self.resolved_path.path.as_path(), self.module_type);
     let mut program = OxcCompiler::parse(Arc::clone(source), source_type);
+    if !self.ctx.defines.is_empty() {
+      // The semantic information below is built from the replaced program
+      self.ctx.defines.replace(&mut program, source_type);
+    }
+    fold_dead_branches(&mut program);
 
     let semantic = program.make_semantic(source_type);
     let (mut symbol_table, scope) = semantic.into_symbol_table_and_scope_tree();
@@ -194,10 +241,11 @@ impl<'task> NormalModuleTask<'task> {
       self.module_type,
       source,
       &file_path,
-    );
+    )
+    .with_unknown_global_side_effects(self.ctx.input_options.unknown_global_side_effects);
     let namespace_symbol = scanner.namespace_ref;
     program.hoist_import_export_from_stmts();
-    let scan_result = scanner.scan(program.program());
+    let scan_result = scanner.scan(program.program(), program.trivias());
 
     (program, ast_scope, scan_result, symbol_for_module, namespace_symbol)
   }
@@ -218,6 +266,8 @@ impl<'task> NormalModuleTask<'task> {
         path: specifier.to_string().into(),
         module_type: ModuleType::Unknown,
         is_external: true,
+        side_effects: None,
+        package_json_side_effects: None,
       });
     }
 
@@ -55,6 +55,7 @@ impl RuntimeNormalModuleTask {
       repr_name,
       import_records: _,
       exports_kind: _,
+      commonjs_exports: _,
       warnings: _,
     } = scan_result;
 
@@ -114,7 +115,7 @@ impl RuntimeNormalModuleTask {
     );
     let namespace_symbol = scanner.namespace_ref;
     program.hoist_import_export_from_stmts();
-    let scan_result = scanner.scan(program.program());
+    let scan_result = scanner.scan(program.program(), program.trivias());
 
     (program, ast_scope, scan_result, symbol_for_module, namespace_symbol)
   }
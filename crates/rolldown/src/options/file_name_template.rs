@@ -1,3 +1,25 @@
+use std::path::{Component, Path};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rolldown_error::BuildError;
+
+static HASH_PATTERN_RE: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"\[hash(?::(\d+))?\]").expect("Should be a valid regex"));
+
+static PLACEHOLDER_RE: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"\[(\w+)(:\d+)?\]").expect("Should be a valid regex"));
+
+pub const DEFAULT_HASH_LEN: usize = 8;
+pub const MIN_HASH_LEN: usize = 6;
+pub const MAX_HASH_LEN: usize = 22;
+
+/// Placeholders supported by `entry_file_names` and `chunk_file_names`.
+pub const CHUNK_PLACEHOLDERS: &[&str] = &["name", "hash", "format", "dir"];
+
+/// Placeholders supported by `asset_file_names`.
+pub const ASSET_PLACEHOLDERS: &[&str] = &["name", "hash", "ext", "extname"];
+
 #[derive(Debug)]
 pub struct FileNameTemplate {
   template: String,
@@ -19,6 +41,15 @@ impl From<String> for FileNameTemplate {
 #[derive(Debug, Default)]
 pub struct FileNameRenderOptions<'me> {
   pub name: Option<&'me str>,
+  /// Replaces every `[hash]`/`[hash:N]` pattern as-is. Callers are responsible for the length.
+  pub hash: Option<&'me str>,
+  pub format: Option<&'me str>,
+  /// The directory of the module the file is named after, relative to `cwd`. Rendered as `.` if empty.
+  pub dir: Option<&'me str>,
+  /// The extension without the leading dot, such as `css`.
+  pub ext: Option<&'me str>,
+  /// The extension with the leading dot, such as `.css`.
+  pub extname: Option<&'me str>,
 }
 
 impl FileNameTemplate {
@@ -27,6 +58,134 @@ impl FileNameTemplate {
     if let Some(name) = options.name {
       tmp = tmp.replace("[name]", name);
     }
-    tmp
+    if let Some(hash) = options.hash {
+      tmp = HASH_PATTERN_RE.replace_all(&tmp, regex::NoExpand(hash)).into_owned();
+    }
+    if let Some(format) = options.format {
+      tmp = tmp.replace("[format]", format);
+    }
+    if let Some(dir) = options.dir {
+      tmp = tmp.replace("[dir]", if dir.is_empty() { "." } else { dir });
+    }
+    if let Some(ext) = options.ext {
+      tmp = tmp.replace("[ext]", ext);
+    }
+    if let Some(extname) = options.extname {
+      tmp = tmp.replace("[extname]", extname);
+    }
+    // Drop `.` segments, which come from an empty `[dir]`.
+    tmp.split('/').filter(|seg| *seg != ".").collect::<Vec<_>>().join("/")
+  }
+
+  /// Returns the length requested by the `[hash]`/`[hash:N]` patterns, or `None` if the template has no
+  /// hash pattern. The template should already be checked by `FileNameTemplate::validate`, which ensures
+  /// that all of them request the same length.
+  pub fn hash_len(&self) -> Option<usize> {
+    HASH_PATTERN_RE.captures(&self.template).map(|captures| {
+      captures.get(1).and_then(|len| len.as_str().parse().ok()).unwrap_or(DEFAULT_HASH_LEN)
+    })
+  }
+
+  /// Rejects templates that use placeholders not in `placeholders`, whose hash lengths are out of range
+  /// or differ, or that would write outside of the output directory. `option` is the name of the option
+  /// used in errors.
+  pub fn validate(&self, option: &str, placeholders: &[&str]) -> Result<(), BuildError> {
+    if !is_path_fragment(&self.template) {
+      return Err(BuildError::invalid_file_name_pattern(option, &self.template));
+    }
+    for captures in PLACEHOLDER_RE.captures_iter(&self.template) {
+      let placeholder = &captures[1];
+      if !placeholders.contains(&placeholder)
+        || (captures.get(2).is_some() && placeholder != "hash")
+      {
+        return Err(BuildError::unknown_file_name_placeholder(option, &captures[0]));
+      }
+    }
+    let mut first_len = None;
+    for captures in HASH_PATTERN_RE.captures_iter(&self.template) {
+      let len = captures
+        .get(1)
+        .map_or(Ok(DEFAULT_HASH_LEN), |len| len.as_str().parse::<usize>())
+        .unwrap_or(usize::MAX);
+      if !(MIN_HASH_LEN..=MAX_HASH_LEN).contains(&len) {
+        return Err(BuildError::invalid_hash_length(option, &captures[0], len));
+      }
+      // Every hash pattern is rendered with the same hash, of the length of the first one
+      if *first_len.get_or_insert(len) != len {
+        return Err(BuildError::mixed_hash_lengths(option, &captures[0]));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Whether `path` stays inside the directory it's joined to, i.e. it's neither absolute nor contains `..`.
+pub fn is_path_fragment(path: &str) -> bool {
+  Path::new(path)
+    .components()
+    .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hash_len() {
+    assert_eq!(FileNameTemplate::new("[name].js".to_string()).hash_len(), None);
+    assert_eq!(FileNameTemplate::new("[name]-[hash].js".to_string()).hash_len(), Some(8));
+    assert_eq!(FileNameTemplate::new("[name]-[hash:12].js".to_string()).hash_len(), Some(12));
+  }
+
+  #[test]
+  fn validate() {
+    let validate = |template: &str| {
+      FileNameTemplate::new(template.to_string())
+        .validate("output.chunkFileNames", CHUNK_PLACEHOLDERS)
+    };
+    assert!(validate("[dir]/[name]-[hash:10].[format].js").is_ok());
+    assert!(validate("./chunks/[name].js").is_ok());
+    assert!(validate("[name]-[hash:4].js").is_err());
+    assert!(validate("[name]-[hash:30].js").is_err());
+    assert!(validate("[name]-[hash:8]-[hash].js").is_ok());
+    assert!(validate("[name]-[hash:6]-[hash:10].js").is_err());
+    assert!(validate("[name].[ext]").is_err());
+    assert!(validate("[name:3].js").is_err());
+    assert!(validate("/abs/[name].js").is_err());
+    assert!(validate("../[name].js").is_err());
+    assert!(validate("chunks/../../[name].js").is_err());
+  }
+
+  #[test]
+  fn render() {
+    let template = FileNameTemplate::new("[name]-[hash:6]/[hash:6].js".to_string());
+    assert_eq!(
+      template.render(&FileNameRenderOptions {
+        name: Some("main"),
+        hash: Some("abcdef"),
+        ..Default::default()
+      }),
+      "main-abcdef/abcdef.js"
+    );
+    let template = FileNameTemplate::new("[format]/[dir]/[name].js".to_string());
+    assert_eq!(
+      template.render(&FileNameRenderOptions {
+        name: Some("main"),
+        format: Some("cjs"),
+        dir: Some(""),
+        ..Default::default()
+      }),
+      "cjs/main.js"
+    );
+    let template = FileNameTemplate::new("assets/[name][extname]?[ext]".to_string());
+    assert_eq!(
+      template.render(&FileNameRenderOptions {
+        name: Some("style"),
+        ext: Some("css"),
+        extname: Some(".css"),
+        ..Default::default()
+      }),
+      "assets/style.css?css"
+    );
   }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -8,7 +9,7 @@ use rolldown_error::BuildError;
 
 use self::resolve_options::ResolveOptions;
 
-use super::types::input_item::InputItem;
+use super::types::{input_item::InputItem, treeshake_options::TreeshakeOptions};
 
 pub mod resolve_options;
 
@@ -60,6 +61,9 @@ pub struct InputOptions {
   pub input: Vec<InputItem>,
   pub cwd: Option<PathBuf>,
   pub external: Option<External>,
-  pub treeshake: Option<bool>,
+  pub treeshake: Option<TreeshakeOptions>,
   pub resolve: Option<ResolveOptions>,
+  /// Replaces global expressions like `process.env.NODE_ENV` or `import.meta.env.MODE` with the given values at
+  /// build time. Values are JSON literals or references, e.g. `"\"production\""` or `globalThis.__DEV__`.
+  pub define: Option<HashMap<String, String>>,
 }
@@ -1,13 +1,13 @@
 //! [crate::InputOptions] meant to provide dx-friendly options for the `rolldown` users, but it's not suitable for
 //! the `rolldown` internal use.
 
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use derivative::Derivative;
 
 use crate::External;
 
-use super::types::input_item::InputItem;
+use super::types::{input_item::InputItem, treeshake_options::ModuleSideEffects};
 
 pub type SharedNormalizedInputOptions = Arc<NormalizedInputOptions>;
 
@@ -18,4 +18,7 @@ pub struct NormalizedInputOptions {
   pub cwd: PathBuf,
   pub external: External,
   pub treeshake: bool,
+  pub module_side_effects: Option<ModuleSideEffects>,
+  pub unknown_global_side_effects: bool,
+  pub define: HashMap<String, String>,
 }
@@ -1,16 +1,40 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use super::output_options::SourceMapType;
-use crate::AddonOutputOption;
-use crate::{FileNameTemplate, OutputFormat};
+use crate::OutputFormat;
+use crate::{
+  AddonOutputOption, AdvancedChunksOptions, AmdOptions, FileNamesOutputOption, ManualChunksOption,
+  MinifyOptions, SourcemapIgnoreList, SourcemapPathTransform,
+};
 use derivative::Derivative;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct NormalizedOutputOptions {
-  pub entry_file_names: FileNameTemplate,
-  pub chunk_file_names: FileNameTemplate,
+  pub entry_file_names: FileNamesOutputOption,
+  pub chunk_file_names: FileNamesOutputOption,
+  pub asset_file_names: String,
   pub dir: String,
   pub format: OutputFormat,
-  pub sourcemap: SourceMapType,
+  /// `None` if no source maps are generated.
+  pub sourcemap: Option<SourceMapType>,
+  pub sourcemap_file: Option<String>,
+  pub sourcemap_ignore_list: SourcemapIgnoreList,
+  pub sourcemap_path_transform: Option<SourcemapPathTransform>,
+  pub sourcemap_exclude_sources: bool,
+  pub sourcemap_debug_ids: bool,
   pub banner: AddonOutputOption,
   pub footer: AddonOutputOption,
+  pub name: Option<String>,
+  pub globals: HashMap<String, String>,
+  pub amd: AmdOptions,
+  pub manual_chunks: Option<ManualChunksOption>,
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  pub experimental_min_chunk_size: usize,
+  pub preserve_modules: bool,
+  /// Absolute path of `preserve_modules_root`.
+  pub preserve_modules_root: Option<PathBuf>,
+  pub inline_dynamic_imports: bool,
+  pub minify: MinifyOptions,
 }
@@ -1,25 +1,68 @@
-use crate::AddonOutputOption;
+use std::collections::HashMap;
+
+use crate::{
+  AddonOutputOption, AdvancedChunksOptions, AmdOptions, FileNamesOutputOption, ManualChunksOption,
+  MinifyOptions, SourcemapIgnoreList, SourcemapPathTransform,
+};
 use derivative::Derivative;
+use rolldown_error::BuildError;
 
 #[derive(Debug)]
 pub enum OutputFormat {
   Esm,
   Cjs,
+  Iife,
+  Umd,
+  System,
+  Amd,
+}
+
+impl OutputFormat {
+  /// IIFE and UMD bundles are meant to be loaded by a single `<script>` tag, so they can't be split into multiple chunks.
+  pub fn supports_code_splitting(&self) -> bool {
+    !matches!(self, Self::Iife | Self::Umd)
+  }
+}
+
+impl std::fmt::Display for OutputFormat {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Esm => write!(f, "es"),
+      Self::Cjs => write!(f, "cjs"),
+      Self::Iife => write!(f, "iife"),
+      Self::Umd => write!(f, "umd"),
+      Self::System => write!(f, "system"),
+      Self::Amd => write!(f, "amd"),
+    }
+  }
+}
+
+impl TryFrom<String> for OutputFormat {
+  type Error = BuildError;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "es" | "esm" => Ok(OutputFormat::Esm),
+      "cjs" => Ok(OutputFormat::Cjs),
+      "iife" => Ok(OutputFormat::Iife),
+      "umd" => Ok(OutputFormat::Umd),
+      "system" | "systemjs" => Ok(OutputFormat::System),
+      "amd" => Ok(OutputFormat::Amd),
+      _ => Err(BuildError::unknown_output_format(value)),
+    }
+  }
 }
 
 #[derive(Debug)]
 pub enum SourceMapType {
+  /// Emits the map as `{chunk}.map` and references it with a `sourceMappingURL` comment.
   File,
+  /// Appends the map to the chunk as a data URL.
   Inline,
+  /// Emits the map as `{chunk}.map` without referencing it from the chunk.
   Hidden,
 }
 
-impl SourceMapType {
-  pub fn is_hidden(&self) -> bool {
-    matches!(self, Self::Hidden)
-  }
-}
-
 impl From<String> for SourceMapType {
   fn from(value: String) -> Self {
     match value.as_str() {
@@ -34,13 +77,50 @@ impl From<String> for SourceMapType {
 #[derive(Derivative, Default)]
 #[derivative(Debug)]
 pub struct OutputOptions {
-  pub entry_file_names: Option<String>,
-  pub chunk_file_names: Option<String>,
+  pub entry_file_names: Option<FileNamesOutputOption>,
+  pub chunk_file_names: Option<FileNamesOutputOption>,
+  /// The file names of the assets emitted by plugins. Supports the `[name]`, `[hash]`, `[ext]` and
+  /// `[extname]` placeholders. Defaults to `assets/[name]-[hash][extname]`.
+  pub asset_file_names: Option<String>,
   pub dir: Option<String>,
   pub format: Option<OutputFormat>,
+  /// No source maps are generated if `None`, which is the default.
   pub sourcemap: Option<SourceMapType>,
   pub banner: Option<AddonOutputOption>,
   pub footer: Option<AddonOutputOption>,
+  /// The path of the chunk the source map belongs to, relative to `dir`. It sets the `file` field of the
+  /// map, which is emitted next to it as `{sourcemap_file}.map`. Only supported for single-file builds.
+  pub sourcemap_file: Option<String>,
+  /// Defaults to ignoring sources from `node_modules`.
+  pub sourcemap_ignore_list: Option<SourcemapIgnoreList>,
+  pub sourcemap_path_transform: Option<SourcemapPathTransform>,
+  /// Leaves `sourcesContent` out of source maps, so they only reference the original sources.
+  pub sourcemap_exclude_sources: Option<bool>,
+  /// Adds a `debugId` derived from the code of the chunk to its source map, and references it from the
+  /// chunk with a `//# debugId=` comment.
+  pub sourcemap_debug_ids: Option<bool>,
+  /// The global variable name holding the exports of the bundle. Used by `OutputFormat::Iife` and `OutputFormat::Umd`.
+  pub name: Option<String>,
+  /// Maps external module ids to global variable names. Used by `OutputFormat::Iife` and `OutputFormat::Umd`.
+  pub globals: Option<HashMap<String, String>>,
+  /// Options for `OutputFormat::Amd` and the AMD loader part of `OutputFormat::Umd`.
+  pub amd: Option<AmdOptions>,
+  pub manual_chunks: Option<ManualChunksOption>,
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  /// Common chunks smaller than this many bytes are merged into another common chunk when that doesn't
+  /// run code for entries that wouldn't have loaded it before.
+  pub experimental_min_chunk_size: Option<usize>,
+  /// Emits every included module as its own chunk instead of merging modules into chunks. Chunks are named
+  /// after the path of their module relative to `preserve_modules_root`.
+  pub preserve_modules: Option<bool>,
+  /// The directory module paths are relative to when `preserve_modules` is enabled. Defaults to the common
+  /// ancestor directory of the user-defined entries.
+  pub preserve_modules_root: Option<String>,
+  /// Inlines dynamically imported modules into the chunk of their importer, so that each entry results in
+  /// a single file. Only supported for builds with one input.
+  pub inline_dynamic_imports: Option<bool>,
+  /// Minifies the code of chunks. Nothing is minified by default.
+  pub minify: Option<MinifyOptions>,
 }
 
 // impl Default for OutputOptions {
@@ -0,0 +1,27 @@
+use regex::Regex;
+
+#[derive(Debug, Default)]
+pub struct AdvancedChunksOptions {
+  /// Groups are applied after modules are assigned to chunks by the entries that reach them. Modules in
+  /// manual chunks and entry modules are never moved into a group.
+  pub groups: Vec<ChunkGroupOptions>,
+}
+
+#[derive(Debug, Default)]
+pub struct ChunkGroupOptions {
+  /// The name of the chunk. If the group is split because of `max_size`, the parts are named `name-0`,
+  /// `name-1` and so on.
+  pub name: String,
+  /// Only modules whose id matches belong to the group. Every module matches if not provided.
+  pub test: Option<Regex>,
+  /// A module matching several groups goes to the one with the highest priority, or the first one if
+  /// several groups have the same priority. Defaults to `0`.
+  pub priority: Option<i32>,
+  /// The group is ignored if the total size of its modules in bytes is smaller than this.
+  pub min_size: Option<usize>,
+  /// The group is split into several chunks, each no bigger than this many bytes unless a single module
+  /// is. Modules are assigned to parts in the order of their ids, so unrelated changes don't move them.
+  pub max_size: Option<usize>,
+  /// Only modules reached by at least this many entries belong to the group. Defaults to `1`.
+  pub min_share_count: Option<u32>,
+}
@@ -0,0 +1,10 @@
+#[derive(Debug, Default)]
+pub struct AmdOptions {
+  /// The id used for the AMD/UMD `define(id, deps, factory)` call. The module is anonymous if not provided.
+  pub id: Option<String>,
+  /// Use the file name of the chunk without extension as the id of `define(id, deps, factory)`.
+  /// Can't be used together with `id`.
+  pub auto_id: Option<bool>,
+  /// The name of the function that defines modules, `define` by default.
+  pub define: Option<String>,
+}
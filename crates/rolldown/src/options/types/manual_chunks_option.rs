@@ -0,0 +1,26 @@
+use futures::Future;
+use rolldown_error::BuildError;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+pub type ManualChunksFunction = dyn Fn(String) -> Pin<Box<(dyn Future<Output = Result<Option<String>, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Puts modules into named chunks. Modules that are imported only by modules of a manual chunk are pulled
+/// into the same chunk. Entry modules always stay in their own chunk.
+pub enum ManualChunksOption {
+  /// Maps chunk names to module ids. Module ids could be absolute paths or paths relative to `cwd`.
+  Object(Vec<(String, Vec<String>)>),
+  /// Called with the id of every included module and returns the name of the chunk the module goes to.
+  Fn(Box<ManualChunksFunction>),
+}
+
+impl Debug for ManualChunksOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Object(value) => write!(f, "ManualChunksOption::Object({value:?})"),
+      Self::Fn(_) => write!(f, "ManualChunksOption::Fn(...)"),
+    }
+  }
+}
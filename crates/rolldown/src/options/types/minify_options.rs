@@ -0,0 +1,22 @@
+/// Controls which parts of the generated code are minified.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MinifyOptions {
+  /// Print the code without unnecessary whitespace and the `// module path` comments.
+  pub whitespace: bool,
+  /// Rewrite the code into shorter equivalents with oxc's compressor, e.g. `true` into `!0`.
+  pub syntax: bool,
+  /// Rename symbols to the shortest names available. Top-level symbols that are referenced more often get
+  /// shorter names. Exported names are kept.
+  pub identifiers: bool,
+}
+
+impl MinifyOptions {
+  /// Enable all minifications.
+  pub fn all() -> Self {
+    Self { whitespace: true, syntax: true, identifiers: true }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.whitespace || self.syntax || self.identifiers
+  }
+}
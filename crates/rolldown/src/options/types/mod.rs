@@ -1,2 +1,9 @@
+pub mod advanced_chunks_options;
+pub mod amd_options;
 pub mod input_item;
+pub mod manual_chunks_option;
+pub mod minify_options;
 pub mod output_option;
+pub mod sourcemap_ignore_list;
+pub mod sourcemap_path_transform;
+pub mod treeshake_options;
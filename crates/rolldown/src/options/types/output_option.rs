@@ -4,6 +4,8 @@ use rolldown_error::BuildError;
 use std::fmt::Debug;
 use std::pin::Pin;
 
+use crate::PreRenderedChunk;
+
 pub type AddonFunction = dyn Fn(
     RenderedChunk,
   ) -> Pin<Box<(dyn Future<Output = Result<Option<String>, BuildError>> + Send + 'static)>>
@@ -38,3 +40,39 @@ impl AddonOutputOption {
     }
   }
 }
+
+pub type FileNamesFunction = dyn Fn(
+    PreRenderedChunk,
+  ) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// A file name template, or a function returning the template for a given chunk.
+pub enum FileNamesOutputOption {
+  String(String),
+  Fn(Box<FileNamesFunction>),
+}
+
+impl Debug for FileNamesOutputOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::String(value) => write!(f, "FileNamesOutputOption::String({value:?})"),
+      Self::Fn(_) => write!(f, "FileNamesOutputOption::Fn(...)"),
+    }
+  }
+}
+
+impl From<String> for FileNamesOutputOption {
+  fn from(value: String) -> Self {
+    Self::String(value)
+  }
+}
+
+impl FileNamesOutputOption {
+  pub async fn call(&self, chunk: PreRenderedChunk) -> Result<String, BuildError> {
+    match self {
+      Self::String(value) => Ok(value.clone()),
+      Self::Fn(value) => value(chunk).await,
+    }
+  }
+}
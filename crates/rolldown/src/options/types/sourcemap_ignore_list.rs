@@ -0,0 +1,35 @@
+use futures::Future;
+use rolldown_error::BuildError;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+pub type SourcemapIgnoreListFunction = dyn Fn(String, String) -> Pin<Box<(dyn Future<Output = Result<bool, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Decides which sources of a source map are listed in `x_google_ignoreList`. Called with the path of the
+/// source relative to the source map, and the absolute path of the source map.
+pub struct SourcemapIgnoreList(Box<SourcemapIgnoreListFunction>);
+
+impl SourcemapIgnoreList {
+  pub fn new(value: Box<SourcemapIgnoreListFunction>) -> Self {
+    Self(value)
+  }
+
+  pub async fn call(&self, source: &str, sourcemap_path: &str) -> Result<bool, BuildError> {
+    (self.0)(source.to_string(), sourcemap_path.to_string()).await
+  }
+}
+
+impl Debug for SourcemapIgnoreList {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SourcemapIgnoreList::Fn(...)")
+  }
+}
+
+/// Ignores sources from `node_modules`, like Rollup.
+impl Default for SourcemapIgnoreList {
+  fn default() -> Self {
+    Self(Box::new(|source, _| Box::pin(async move { Ok(source.contains("node_modules")) })))
+  }
+}
@@ -0,0 +1,28 @@
+use futures::Future;
+use rolldown_error::BuildError;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+pub type SourcemapPathTransformFunction = dyn Fn(String, String) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Rewrites the sources of a source map. Called with the path of the source relative to the source map,
+/// and the absolute path of the source map. Returns the path written to `sources`.
+pub struct SourcemapPathTransform(Box<SourcemapPathTransformFunction>);
+
+impl SourcemapPathTransform {
+  pub fn new(value: Box<SourcemapPathTransformFunction>) -> Self {
+    Self(value)
+  }
+
+  pub async fn call(&self, source: &str, sourcemap_path: &str) -> Result<String, BuildError> {
+    (self.0)(source.to_string(), sourcemap_path.to_string()).await
+  }
+}
+
+impl Debug for SourcemapPathTransform {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SourcemapPathTransform::Fn(...)")
+  }
+}
@@ -0,0 +1,100 @@
+use futures::Future;
+use rolldown_error::BuildError;
+use std::fmt::Debug;
+use std::path::Path;
+use std::pin::Pin;
+use sugar_path::SugarPath;
+
+/// `false` disables tree shaking. `true` is the same as the default options.
+#[derive(Debug)]
+pub enum TreeshakeOptions {
+  Boolean(bool),
+  Options(InnerTreeshakeOptions),
+}
+
+impl Default for TreeshakeOptions {
+  fn default() -> Self {
+    Self::Boolean(true)
+  }
+}
+
+impl From<bool> for TreeshakeOptions {
+  fn from(value: bool) -> Self {
+    Self::Boolean(value)
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct InnerTreeshakeOptions {
+  /// Whether modules have side effects, for the modules the plugin hooks don't decide. It overrides the
+  /// `sideEffects` field of package.json. A module without side effects is dropped if none of its exports
+  /// are used.
+  pub module_side_effects: Option<ModuleSideEffects>,
+  /// Whether reading a global variable that isn't a known global of the language, like `window`, is a side
+  /// effect, since it throws if the variable doesn't exist. Defaults to `true`.
+  pub unknown_global_side_effects: Option<bool>,
+}
+
+pub type ModuleSideEffectsFn = dyn Fn(String) -> Pin<Box<(dyn Future<Output = Result<Option<bool>, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+pub enum ModuleSideEffects {
+  /// Whether every module has side effects.
+  Boolean(bool),
+  /// Only the listed modules have side effects. Module ids could be absolute paths or paths relative to `cwd`.
+  IdList(Vec<String>),
+  /// Called with the id of every module the plugin hooks don't decide. Returning `None` falls back to
+  /// package.json.
+  Fn(Box<ModuleSideEffectsFn>),
+}
+
+impl Debug for ModuleSideEffects {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Boolean(value) => write!(f, "ModuleSideEffects::Boolean({value:?})"),
+      Self::IdList(value) => write!(f, "ModuleSideEffects::IdList({value:?})"),
+      Self::Fn(_) => write!(f, "ModuleSideEffects::Fn(...)"),
+    }
+  }
+}
+
+impl ModuleSideEffects {
+  pub async fn call(&self, id: &str, cwd: &Path) -> Result<Option<bool>, BuildError> {
+    match self {
+      Self::Boolean(value) => Ok(Some(*value)),
+      Self::IdList(ids) => {
+        Ok(Some(ids.iter().any(|item| cwd.join(item).normalize() == Path::new(id))))
+      }
+      Self::Fn(value) => value(id.to_string()).await,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn call() {
+    let cwd = Path::new("/root");
+    let call = |option: ModuleSideEffects, id: &'static str| async move {
+      option.call(id, cwd).await.expect("should not fail")
+    };
+
+    assert_eq!(call(ModuleSideEffects::Boolean(false), "/root/a.js").await, Some(false));
+
+    let ids = || ModuleSideEffects::IdList(vec!["./a.js".to_string(), "/root/b.js".to_string()]);
+    assert_eq!(call(ids(), "/root/a.js").await, Some(true));
+    assert_eq!(call(ids(), "/root/b.js").await, Some(true));
+    assert_eq!(call(ids(), "/root/c.js").await, Some(false));
+
+    let func = || {
+      ModuleSideEffects::Fn(Box::new(|id| {
+        Box::pin(async move { Ok(id.contains("/styles/").then_some(true)) })
+      }))
+    };
+    assert_eq!(call(func(), "/root/styles/a.js").await, Some(true));
+    assert_eq!(call(func(), "/root/a.js").await, None);
+  }
+}
@@ -0,0 +1,101 @@
+use std::cmp::Reverse;
+
+use index_vec::IndexVec;
+use rolldown_common::NormalModuleId;
+use rolldown_utils::BitSet;
+use rustc_hash::FxHashSet;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Assigns modules that don't belong to a named chunk yet to the groups of `advanced_chunks`, by setting
+  /// the name of the chunk they go to in `module_to_name`. Modules for which `is_excluded` returns `true`
+  /// are left untouched.
+  pub(super) fn apply_advanced_chunks(
+    &self,
+    module_to_bits: &IndexVec<NormalModuleId, BitSet>,
+    is_excluded: impl Fn(NormalModuleId) -> bool,
+    module_to_name: &mut IndexVec<NormalModuleId, Option<String>>,
+  ) {
+    let Some(advanced_chunks) = &self.output_options.advanced_chunks else {
+      return;
+    };
+    let modules = &self.link_output.module_table.normal_modules;
+
+    let mut modules_of_groups = vec![vec![]; advanced_chunks.groups.len()];
+    modules
+      .iter()
+      .filter(|module| {
+        module.is_included && !is_excluded(module.id) && module_to_name[module.id].is_none()
+      })
+      .for_each(|module| {
+        let module_id = module.resource_id.expect_file().as_str();
+        let share_count = module_to_bits[module.id].count_ones();
+        let matched = advanced_chunks
+          .groups
+          .iter()
+          .enumerate()
+          .filter(|(_, group)| {
+            group.test.as_ref().map_or(true, |test| test.is_match(module_id))
+              && share_count >= group.min_share_count.unwrap_or(1)
+          })
+          .max_by_key(|(index, group)| (group.priority.unwrap_or(0), Reverse(*index)));
+        if let Some((index, _)) = matched {
+          modules_of_groups[index].push(module.id);
+        }
+      });
+
+    // The parts of a split group are named `{name}-{index}`, skipping the names of other chunks
+    let mut used_names = advanced_chunks
+      .groups
+      .iter()
+      .map(|group| group.name.clone())
+      .chain(module_to_name.iter().flatten().cloned())
+      .collect::<FxHashSet<_>>();
+    advanced_chunks.groups.iter().zip(modules_of_groups).for_each(|(group, mut group_modules)| {
+      let size_of = |module_id: NormalModuleId| modules[module_id].source.len();
+      let total_size = group_modules.iter().map(|module_id| size_of(*module_id)).sum::<usize>();
+      if group_modules.is_empty() || total_size < group.min_size.unwrap_or(0) {
+        return;
+      }
+
+      group_modules.sort_by(|a, b| {
+        modules[*a].resource_id.expect_file().cmp(modules[*b].resource_id.expect_file())
+      });
+      let mut parts: Vec<Vec<NormalModuleId>> = vec![];
+      let mut part_size = 0;
+      group_modules.into_iter().for_each(|module_id| {
+        let size = size_of(module_id);
+        match parts.last_mut() {
+          Some(part) if group.max_size.map_or(true, |max_size| part_size + size <= max_size) => {
+            part.push(module_id);
+            part_size += size;
+          }
+          _ => {
+            parts.push(vec![module_id]);
+            part_size = size;
+          }
+        }
+      });
+
+      let is_split = parts.len() > 1;
+      let mut next_index = 0;
+      parts.into_iter().for_each(|part| {
+        let name = if is_split {
+          loop {
+            let name = format!("{}-{next_index}", group.name);
+            next_index += 1;
+            if used_names.insert(name.clone()) {
+              break name;
+            }
+          }
+        } else {
+          group.name.clone()
+        };
+        part.into_iter().for_each(|module_id| {
+          module_to_name[module_id] = Some(name.clone());
+        });
+      });
+    });
+  }
+}
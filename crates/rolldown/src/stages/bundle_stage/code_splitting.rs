@@ -2,8 +2,9 @@ use std::hash::BuildHasherDefault;
 
 use index_vec::IndexVec;
 use rolldown_common::{ChunkId, ChunkKind, ImportKind, ModuleId, NormalModuleId};
+use rolldown_error::BuildError;
 use rolldown_utils::BitSet;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
   chunk::{Chunk, ChunksVec},
@@ -14,6 +15,15 @@ use crate::{
 use super::BundleStage;
 
 impl<'a> BundleStage<'a> {
+  /// If we are in test environment, to make the runtime module always fall into a standalone chunk,
+  /// we create a facade entry point for it. This doesn't apply to formats that don't support code splitting
+  /// and to `inline_dynamic_imports`, which always produce a single chunk.
+  pub fn is_runtime_in_standalone_chunk(&self) -> bool {
+    is_in_rust_test_mode()
+      && self.output_options.format.supports_code_splitting()
+      && !self.output_options.inline_dynamic_imports
+  }
+
   fn determine_reachable_modules_for_entry(
     &self,
     module_id: NormalModuleId,
@@ -34,8 +44,8 @@ impl<'a> BundleStage<'a> {
     module.import_records.iter().for_each(|rec| {
       if let ModuleId::Normal(importee_id) = rec.resolved_module {
         // Module imported dynamically will be considered as an entry,
-        // so we don't need to include it in this chunk
-        if rec.kind != ImportKind::DynamicImport {
+        // so we don't need to include it in this chunk, unless dynamic imports are inlined
+        if rec.kind != ImportKind::DynamicImport || self.output_options.inline_dynamic_imports {
           self.determine_reachable_modules_for_entry(importee_id, entry_index, module_to_bits);
         }
       }
@@ -56,12 +66,32 @@ impl<'a> BundleStage<'a> {
     });
   }
 
-  pub fn generate_chunks(&self) -> ChunkGraph {
+  /// Returns the name of the chunk each module is put into by `manual_chunks` or `advanced_chunks`.
+  async fn determine_named_chunks(
+    &self,
+    module_to_bits: &IndexVec<NormalModuleId, BitSet>,
+  ) -> Result<IndexVec<NormalModuleId, Option<String>>, BuildError> {
+    let entry_modules =
+      self.link_output.entries.iter().map(|entry_point| entry_point.id).collect::<FxHashSet<_>>();
+    let is_excluded_from_named_chunks = |module_id| {
+      entry_modules.contains(&module_id)
+        || (self.is_runtime_in_standalone_chunk() && module_id == self.link_output.runtime.id())
+    };
+    let mut module_to_named_chunk =
+      self.determine_manual_chunks(is_excluded_from_named_chunks).await?;
+    self.apply_advanced_chunks(
+      module_to_bits,
+      is_excluded_from_named_chunks,
+      &mut module_to_named_chunk,
+    );
+    Ok(module_to_named_chunk)
+  }
+
+  pub async fn generate_chunks(&self) -> Result<ChunkGraph, BuildError> {
     let entries_len: u32 =
       self.link_output.entries.len().try_into().expect("Too many entries, u32 overflowed.");
-    // If we are in test environment, to make the runtime module always fall into a standalone chunk,
-    // we create a facade entry point for it.
-    let entries_len = if is_in_rust_test_mode() { entries_len + 1 } else { entries_len };
+    let entries_len =
+      if self.is_runtime_in_standalone_chunk() { entries_len + 1 } else { entries_len };
 
     let mut module_to_bits = index_vec::index_vec![BitSet::new(entries_len); self.link_output.module_table.normal_modules.len()];
     let mut bits_to_chunk = FxHashMap::with_capacity_and_hasher(
@@ -89,7 +119,7 @@ impl<'a> BundleStage<'a> {
       bits_to_chunk.insert(bits, chunk);
     }
 
-    if is_in_rust_test_mode() {
+    if self.is_runtime_in_standalone_chunk() {
       self.determine_reachable_modules_for_entry(
         self.link_output.runtime.id(),
         entries_len - 1,
@@ -106,11 +136,20 @@ impl<'a> BundleStage<'a> {
       );
     });
 
+    if self.output_options.preserve_modules {
+      let module_to_chunk = self.generate_preserved_module_chunks(&module_to_bits, &mut chunks);
+      tracing::trace!("Generated chunks: {:#?}", chunks);
+      return Ok(ChunkGraph { chunks, module_to_chunk });
+    }
+
     let mut module_to_chunk: IndexVec<NormalModuleId, Option<ChunkId>> = index_vec::index_vec![
       None;
       self.link_output.module_table.normal_modules.len()
     ];
 
+    let module_to_named_chunk = self.determine_named_chunks(&module_to_bits).await?;
+    let mut named_chunks = FxHashMap::<&str, ChunkId>::default();
+
     // 1. Assign modules to corresponding chunks
     // 2. Create shared chunks to store modules that belong to multiple chunks.
     for normal_module in &self.link_output.module_table.normal_modules {
@@ -123,7 +162,20 @@ impl<'a> BundleStage<'a> {
         !bits.is_empty(),
         "Empty bits means the module is not reachable, so it should bail out with `is_included: false`"
       );
-      if let Some(chunk_id) = bits_to_chunk.get(bits).copied() {
+      if let Some(name) = &module_to_named_chunk[normal_module.id] {
+        let chunk_id = *named_chunks.entry(name.as_str()).or_insert_with(|| {
+          chunks.push(Chunk::new(
+            Some(name.clone()),
+            BitSet::new(entries_len),
+            vec![],
+            ChunkKind::Common,
+          ))
+        });
+        // The entries that load any module of a named chunk all need to load the chunk.
+        chunks[chunk_id].bits.union(bits);
+        chunks[chunk_id].modules.push(normal_module.id);
+        module_to_chunk[normal_module.id] = Some(chunk_id);
+      } else if let Some(chunk_id) = bits_to_chunk.get(bits).copied() {
         chunks[chunk_id].modules.push(normal_module.id);
         module_to_chunk[normal_module.id] = Some(chunk_id);
       } else {
@@ -134,6 +186,8 @@ impl<'a> BundleStage<'a> {
       }
     }
 
+    self.merge_small_chunks(&mut chunks, &mut module_to_chunk);
+
     // Sort modules in each chunk by execution order
     chunks.iter_mut().for_each(|chunk| {
       chunk.modules.sort_by_key(|module_id| {
@@ -143,6 +197,6 @@ impl<'a> BundleStage<'a> {
 
     tracing::trace!("Generated chunks: {:#?}", chunks);
 
-    ChunkGraph { chunks, module_to_chunk }
+    Ok(ChunkGraph { chunks, module_to_chunk })
   }
 }
@@ -92,7 +92,14 @@ impl<'a> BundleStage<'a> {
           let entry_module = &self.link_output.module_table.normal_modules[*entry_module_id];
           let entry_linking_info = &self.link_output.metas[entry_module.id];
           if matches!(entry_module.exports_kind, ExportsKind::CommonJs)
-            && matches!(self.output_options.format, OutputFormat::Esm)
+            && matches!(
+              self.output_options.format,
+              OutputFormat::Esm
+                | OutputFormat::Iife
+                | OutputFormat::Umd
+                | OutputFormat::System
+                | OutputFormat::Amd
+            )
           {
             chunk_meta_imports
               .insert(entry_linking_info.wrapper_ref.expect("cjs should be wrapped in esm output"));
@@ -111,6 +118,44 @@ impl<'a> BundleStage<'a> {
     tracing::info!("collect_potential_chunk_imports end");
   }
 
+  /// Chunks that `chunk_id` needs to import, even if no bindings are imported from them, so they are
+  /// evaluated for their side effects.
+  fn side_effect_importees(&self, chunk_graph: &ChunkGraph, chunk_id: ChunkId) -> Vec<ChunkId> {
+    let chunk = &chunk_graph.chunks[chunk_id];
+    if self.output_options.preserve_modules {
+      // Every chunk holds a single module, so import the chunks of its static dependencies.
+      return chunk
+        .modules
+        .iter()
+        .flat_map(|module_id| {
+          &self.link_output.module_table.normal_modules[*module_id].import_records
+        })
+        .filter(|rec| rec.kind != ImportKind::DynamicImport)
+        .filter_map(|rec| rec.resolved_module.as_normal())
+        .filter_map(|importee_id| chunk_graph.module_to_chunk[importee_id])
+        .filter(|importee_chunk_id| *importee_chunk_id != chunk_id)
+        .collect();
+    }
+    // If this is an entry point, make sure we import all chunks belonging to
+    // this entry point, even if there are no imports.
+    let ChunkKind::EntryPoint { bit: importer_chunk_bit, .. } = &chunk.kind else {
+      return vec![];
+    };
+    chunk_graph
+      .chunks
+      .iter_enumerated()
+      .filter(|(id, _)| *id != chunk_id)
+      .filter(|(_, importee_chunk)| importee_chunk.bits.has_bit(*importer_chunk_bit))
+      .filter(|(_, importee_chunk)| {
+        // If we are in test environment, to get a cleaner output in snapshot, no need to import
+        // the runtime chunk as it for sure has no side effects.
+        is_in_rust_test_mode()
+          && importee_chunk.modules.first().copied() != Some(self.link_output.runtime.id())
+      })
+      .map(|(importee_chunk_id, _)| importee_chunk_id)
+      .collect()
+  }
+
   pub fn compute_cross_chunk_links(&mut self, chunk_graph: &mut ChunkGraph) {
     let mut chunk_meta_imports_vec: ChunkMetaImports =
       index_vec![FxHashSet::<SymbolRef>::default(); chunk_graph.chunks.len()];
@@ -131,7 +176,7 @@ impl<'a> BundleStage<'a> {
 
     tracing::info!("calculate cross chunk imports");
     // - Find out what imports are actually come from other chunks
-    chunk_graph.chunks.iter_enumerated().for_each(|(chunk_id, chunk)| {
+    chunk_graph.chunks.indices().for_each(|chunk_id| {
       let chunk_meta_imports = &chunk_meta_imports_vec[chunk_id];
       for import_ref in chunk_meta_imports.iter().copied() {
         let import_symbol = self.link_output.symbols.get(import_ref);
@@ -155,25 +200,8 @@ impl<'a> BundleStage<'a> {
         }
       }
 
-      // If this is an entry point, make sure we import all chunks belonging to
-      // this entry point, even if there are no imports. We need to make sure
-      // these chunks are evaluated for their side effects too.
-      if let ChunkKind::EntryPoint { bit: importer_chunk_bit, .. } = &chunk.kind {
-        chunk_graph
-          .chunks
-          .iter_enumerated()
-          .filter(|(id, _)| *id != chunk_id)
-          .filter(|(_, importee_chunk)| importee_chunk.bits.has_bit(*importer_chunk_bit))
-          .filter(|(_, importee_chunk)| {
-            // If we are in test environment, to get a cleaner output in snapshot, no need to import
-            // the runtime chunk as it for sure has no side effects.
-            is_in_rust_test_mode()
-              && importee_chunk.modules.first().copied() != Some(self.link_output.runtime.id())
-          })
-          .for_each(|(importee_chunk_id, _)| {
-            let imports_from_other_chunks = &mut imports_from_other_chunks_vec[chunk_id];
-            imports_from_other_chunks.entry(importee_chunk_id).or_default();
-          });
+      for importee_chunk_id in self.side_effect_importees(chunk_graph, chunk_id) {
+        imports_from_other_chunks_vec[chunk_id].entry(importee_chunk_id).or_default();
       }
     });
 
@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use index_vec::{index_vec, IndexVec};
+use rolldown_common::{ImportKind, ModuleId, NormalModuleId};
+use rolldown_error::BuildError;
+use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::SugarPath;
+
+use crate::ManualChunksOption;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Included modules that `module_id` depends on statically. This follows the same edges as
+  /// `determine_reachable_modules_for_entry`.
+  pub(super) fn static_dependencies_of(
+    &self,
+    module_id: NormalModuleId,
+  ) -> FxHashSet<NormalModuleId> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let module = &modules[module_id];
+    let mut dependencies = module
+      .import_records
+      .iter()
+      .filter(|rec| rec.kind != ImportKind::DynamicImport)
+      .filter_map(|rec| match rec.resolved_module {
+        ModuleId::Normal(importee_id) => Some(importee_id),
+        ModuleId::External(_) => None,
+      })
+      .collect::<FxHashSet<_>>();
+    module.stmt_infos.iter().filter(|stmt_info| stmt_info.is_included).for_each(|stmt_info| {
+      stmt_info.referenced_symbols.iter().for_each(|symbol_ref| {
+        dependencies.insert(self.link_output.symbols.par_canonical_ref_for(*symbol_ref).owner);
+      });
+    });
+    dependencies.remove(&module_id);
+    dependencies.retain(|id| modules[*id].is_included);
+    dependencies
+  }
+
+  /// Returns the name of the manual chunk each module belongs to. Modules for which `is_excluded` returns
+  /// `true`, such as entry modules, are never put into manual chunks.
+  pub(super) async fn determine_manual_chunks(
+    &self,
+    is_excluded: impl Fn(NormalModuleId) -> bool,
+  ) -> Result<IndexVec<NormalModuleId, Option<String>>, BuildError> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let mut module_to_name: IndexVec<NormalModuleId, Option<String>> =
+      index_vec![None; modules.len()];
+    let Some(manual_chunks) = &self.output_options.manual_chunks else {
+      return Ok(module_to_name);
+    };
+    let candidates = modules
+      .iter()
+      .filter(|module| module.is_included && !is_excluded(module.id))
+      .collect::<Vec<_>>();
+
+    match manual_chunks {
+      ManualChunksOption::Object(groups) => {
+        let mut id_to_name = FxHashMap::<PathBuf, &String>::default();
+        groups.iter().for_each(|(name, ids)| {
+          ids.iter().for_each(|id| {
+            id_to_name
+              .entry(self.input_options.cwd.join(id).normalize().into_owned())
+              .or_insert(name);
+          });
+        });
+        candidates.iter().for_each(|module| {
+          let path = Path::new(module.resource_id.expect_file().as_str());
+          module_to_name[module.id] = id_to_name.get(path).map(|name| (*name).clone());
+        });
+      }
+      ManualChunksOption::Fn(func) => {
+        for module in &candidates {
+          module_to_name[module.id] = func(module.resource_id.expect_file().to_string()).await?;
+        }
+      }
+    }
+
+    // Pull in modules that are only imported by modules of a single manual chunk. Pulling in a module could
+    // make its own dependencies qualify, so repeat until nothing changes.
+    let mut importers: IndexVec<NormalModuleId, Vec<NormalModuleId>> =
+      index_vec![vec![]; modules.len()];
+    modules.iter().filter(|module| module.is_included).for_each(|module| {
+      self.static_dependencies_of(module.id).into_iter().for_each(|dependency| {
+        importers[dependency].push(module.id);
+      });
+    });
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for module in &candidates {
+        if module_to_name[module.id].is_some() {
+          continue;
+        }
+        let mut names = importers[module.id].iter().map(|importer| &module_to_name[*importer]);
+        let Some(Some(first)) = names.next() else {
+          continue;
+        };
+        let name = names.all(|name| name.as_ref() == Some(first)).then(|| first.clone());
+        if name.is_some() {
+          module_to_name[module.id] = name;
+          changed = true;
+        }
+      }
+    }
+
+    Ok(module_to_name)
+  }
+}
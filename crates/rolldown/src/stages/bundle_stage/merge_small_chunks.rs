@@ -0,0 +1,152 @@
+use index_vec::IndexVec;
+use rolldown_common::{ChunkId, ChunkKind, NormalModuleId};
+use rustc_hash::FxHashSet;
+
+use crate::chunk::{Chunk, ChunksVec};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  fn has_side_effects(&self, module_id: NormalModuleId) -> bool {
+    self.link_output.module_table.normal_modules[module_id]
+      .stmt_infos
+      .iter()
+      .any(|stmt_info| stmt_info.is_included && stmt_info.side_effect)
+  }
+
+  /// The chunks that each chunk statically depends on, excluding itself.
+  fn chunk_dependencies(
+    &self,
+    chunks: &ChunksVec,
+    module_to_chunk: &IndexVec<NormalModuleId, Option<ChunkId>>,
+  ) -> IndexVec<ChunkId, FxHashSet<ChunkId>> {
+    chunks
+      .iter_enumerated()
+      .map(|(chunk_id, chunk)| {
+        chunk
+          .modules
+          .iter()
+          .flat_map(|module_id| self.static_dependencies_of(*module_id))
+          .filter_map(|importee_id| module_to_chunk[importee_id])
+          .filter(|importee_chunk_id| *importee_chunk_id != chunk_id)
+          .collect()
+      })
+      .collect()
+  }
+
+  /// Merges common chunks smaller than `experimental_min_chunk_size` into other common chunks.
+  ///
+  /// A small chunk `a` is only merged into a chunk `b` that is loaded by every entry that loads `a`, so no
+  /// entry has to load `b` in addition. The entries that only load `b` would now also run the code of `a`,
+  /// so `a` must be free of side effects. The merge is also skipped if `a` and `b` depend on each other
+  /// through a third chunk, since the merged chunk would then import itself in a cycle. Among the allowed
+  /// targets, the one that is loaded by the fewest additional entries wins.
+  /// Merged chunks are removed and `module_to_chunk` is updated to point to the remaining chunks.
+  pub(super) fn merge_small_chunks(
+    &self,
+    chunks: &mut ChunksVec,
+    module_to_chunk: &mut IndexVec<NormalModuleId, Option<ChunkId>>,
+  ) {
+    let min_chunk_size = self.output_options.experimental_min_chunk_size;
+    if min_chunk_size == 0 {
+      return;
+    }
+    let modules = &self.link_output.module_table.normal_modules;
+    // Named chunks are controlled by the user, so they are neither merged nor merged into.
+    let is_mergeable = |chunk: &Chunk| {
+      matches!(chunk.kind, ChunkKind::Common) && chunk.name.is_none() && !chunk.modules.is_empty()
+    };
+    let size_of = |chunk: &Chunk| {
+      chunk.modules.iter().map(|module_id| modules[*module_id].source.len()).sum::<usize>()
+    };
+    let mut sizes = chunks.iter().map(size_of).collect::<IndexVec<ChunkId, _>>();
+    let pure = chunks
+      .iter()
+      .map(|chunk| !chunk.modules.iter().any(|module_id| self.has_side_effects(*module_id)))
+      .collect::<IndexVec<ChunkId, _>>();
+    let mut dependencies = self.chunk_dependencies(chunks, module_to_chunk);
+
+    let mut small_chunks = chunks
+      .iter_enumerated()
+      .filter(|(chunk_id, chunk)| is_mergeable(chunk) && sizes[*chunk_id] < min_chunk_size)
+      .map(|(chunk_id, _)| chunk_id)
+      .collect::<Vec<_>>();
+    small_chunks.sort_by_key(|chunk_id| (sizes[*chunk_id], *chunk_id));
+
+    let mut merged = false;
+    for source_id in small_chunks {
+      // The chunk might have grown by receiving other chunks in the meantime.
+      if sizes[source_id] >= min_chunk_size || !pure[source_id] {
+        continue;
+      }
+      let source = &chunks[source_id];
+      let target = chunks
+        .iter_enumerated()
+        .filter(|(target_id, target)| *target_id != source_id && is_mergeable(target))
+        .filter(|(target_id, target)| {
+          target.bits.is_superset_of(&source.bits)
+            && !depends_indirectly(&dependencies, source_id, *target_id)
+            && !depends_indirectly(&dependencies, *target_id, source_id)
+        })
+        .min_by_key(|(target_id, target)| {
+          (target.bits.count_ones() - source.bits.count_ones(), *target_id)
+        })
+        .map(|(target_id, _)| target_id);
+      let Some(target_id) = target else {
+        continue;
+      };
+
+      let source = std::mem::take(&mut chunks[source_id].modules);
+      source.iter().for_each(|module_id| module_to_chunk[*module_id] = Some(target_id));
+      chunks[target_id].modules.extend(source);
+      sizes[target_id] += sizes[source_id];
+      let source_dependencies = std::mem::take(&mut dependencies[source_id]);
+      dependencies[target_id].extend(source_dependencies);
+      dependencies[target_id].remove(&target_id);
+      dependencies.iter_mut_enumerated().for_each(|(chunk_id, chunk_dependencies)| {
+        if chunk_dependencies.remove(&source_id) && chunk_id != target_id {
+          chunk_dependencies.insert(target_id);
+        }
+      });
+      merged = true;
+    }
+
+    // Remove the chunks that were merged away, and remap the ids of the remaining ones.
+    if !merged {
+      return;
+    }
+    let mut id_map: IndexVec<ChunkId, Option<ChunkId>> = index_vec::index_vec![None; chunks.len()];
+    let mut remaining = ChunksVec::with_capacity(chunks.len());
+    for (chunk_id, chunk) in std::mem::take(chunks).into_iter_enumerated() {
+      if chunk.modules.is_empty() && matches!(chunk.kind, ChunkKind::Common) {
+        continue;
+      }
+      id_map[chunk_id] = Some(remaining.push(chunk));
+    }
+    *chunks = remaining;
+    module_to_chunk.iter_mut().for_each(|chunk_id| {
+      *chunk_id = chunk_id.and_then(|chunk_id| id_map[chunk_id]);
+    });
+  }
+}
+
+/// Whether `from` depends on `to` through at least one other chunk.
+fn depends_indirectly(
+  dependencies: &IndexVec<ChunkId, FxHashSet<ChunkId>>,
+  from: ChunkId,
+  to: ChunkId,
+) -> bool {
+  let mut visited = FxHashSet::default();
+  let mut stack =
+    dependencies[from].iter().copied().filter(|chunk_id| *chunk_id != to).collect::<Vec<_>>();
+  while let Some(chunk_id) = stack.pop() {
+    if chunk_id == to {
+      return true;
+    }
+    if chunk_id == from || !visited.insert(chunk_id) {
+      continue;
+    }
+    stack.extend(dependencies[chunk_id].iter().copied());
+  }
+  false
+}
@@ -1,25 +1,53 @@
-use std::sync::Arc;
+use std::{
+  borrow::Cow,
+  path::{Component, Path},
+  sync::Arc,
+};
 
 use crate::{
-  chunk::ChunkRenderReturn,
+  chunk::{Chunk, ChunkRenderReturn},
   chunk_graph::ChunkGraph,
   error::BatchedResult,
   finalizer::FinalizerContext,
   options::{
-    file_name_template::FileNameRenderOptions, normalized_input_options::NormalizedInputOptions,
-    normalized_output_options::NormalizedOutputOptions, output_options::SourceMapType,
+    file_name_template::{
+      is_path_fragment, FileNameRenderOptions, ASSET_PLACEHOLDERS, CHUNK_PLACEHOLDERS,
+    },
+    normalized_input_options::NormalizedInputOptions,
+    normalized_output_options::NormalizedOutputOptions,
+    output_options::SourceMapType,
   },
   stages::link_stage::LinkStageOutput,
-  utils::{finalize_normal_module, is_in_rust_test_mode, render_chunks::render_chunks},
+  utils::{
+    finalize_normal_module,
+    hash_placeholder::{replace_hash_placeholders, HashPlaceholderGenerator},
+    render_chunks::render_chunks,
+    reserved_names::RESERVED_NAMES,
+  },
+  FileNameTemplate, OutputFormat,
+};
+use rolldown_utils::{
+  block_on_spawn_all,
+  xxhash::{xxhash_base64_url, xxhash_uuid},
 };
-use rolldown_utils::block_on_spawn_all;
 
-use rolldown_common::{ChunkKind, Output, OutputAsset, OutputChunk};
+use index_vec::IndexVec;
+use oxc::syntax::identifier::is_identifier_name;
+use rolldown_common::{ChunkId, ChunkKind, Output, OutputAsset, OutputChunk};
 use rolldown_error::BuildError;
 use rolldown_plugin::SharedPluginDriver;
-use rustc_hash::FxHashSet;
+use rolldown_sourcemap::{
+  replace_sources, to_data_url_with_extensions, to_json_string_with_extensions, SourceMap,
+  SourceMapExtensions,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::SugarPath;
+mod advanced_chunks;
 mod code_splitting;
 mod compute_cross_chunk_links;
+mod manual_chunks;
+mod merge_small_chunks;
+mod preserve_modules;
 
 pub struct BundleStage<'a> {
   link_output: &'a mut LinkStageOutput,
@@ -38,22 +66,110 @@ impl<'a> BundleStage<'a> {
     Self { link_output, output_options, input_options, plugin_driver }
   }
 
+  /// Rejects combinations of `preserve_modules`, `inline_dynamic_imports` and the chunking options that
+  /// contradict each other.
+  fn validate_chunking_options(&self) -> Result<(), BuildError> {
+    let options = self.output_options;
+    let mode = if options.preserve_modules {
+      "output.preserveModules"
+    } else if options.inline_dynamic_imports {
+      "output.inlineDynamicImports"
+    } else {
+      return Ok(());
+    };
+    if options.preserve_modules && options.inline_dynamic_imports {
+      return Err(BuildError::incompatible_options("output.inlineDynamicImports", mode));
+    }
+    if options.manual_chunks.is_some() {
+      return Err(BuildError::incompatible_options("output.manualChunks", mode));
+    }
+    if options.advanced_chunks.is_some() {
+      return Err(BuildError::incompatible_options("output.advancedChunks", mode));
+    }
+    let modules = &self.link_output.module_table.normal_modules;
+    if options.inline_dynamic_imports
+      && self
+        .link_output
+        .entries
+        .iter()
+        .filter(|entry| modules[entry.id].is_user_defined_entry)
+        .count()
+        > 1
+    {
+      return Err(BuildError::inline_dynamic_imports_with_multiple_inputs());
+    }
+    Ok(())
+  }
+
+  /// Rejects an `output.name` that isn't an identifier, since the IIFE wrapper declares it as a variable.
+  /// Namespaced names like `a.b` aren't supported. UMD applies the same rule, so both formats accept the
+  /// same names.
+  fn validate_output_name(&self) -> Result<(), BuildError> {
+    match &self.output_options.name {
+      Some(name)
+        if matches!(self.output_options.format, OutputFormat::Iife | OutputFormat::Umd)
+          && (!is_identifier_name(name) || RESERVED_NAMES.contains(&name.as_str())) =>
+      {
+        Err(BuildError::invalid_output_name(name))
+      }
+      _ => Ok(()),
+    }
+  }
+
   #[tracing::instrument(skip_all)]
   pub async fn bundle(&mut self) -> BatchedResult<Vec<Output>> {
     use rayon::prelude::*;
     tracing::info!("Start bundle stage");
-    let mut chunk_graph = self.generate_chunks();
+    self.validate_chunking_options()?;
+    self.validate_output_name()?;
+    let mut chunk_graph = self.generate_chunks().await?;
 
-    self.generate_chunk_filenames(&mut chunk_graph);
-    tracing::info!("generate_chunk_filenames");
+    if chunk_graph.chunks.len() > 1 && !self.output_options.format.supports_code_splitting() {
+      return Err(
+        BuildError::unsupported_code_splitting_format(self.output_options.format.to_string())
+          .into(),
+      );
+    }
+    if matches!(self.output_options.format, OutputFormat::Amd)
+      && self.output_options.amd.id.is_some()
+    {
+      if matches!(self.output_options.amd.auto_id, Some(true)) {
+        return Err(BuildError::amd_id_with_auto_id().into());
+      }
+      if chunk_graph.chunks.len() > 1 {
+        return Err(BuildError::amd_id_with_code_splitting().into());
+      }
+    }
+    if self.output_options.sourcemap_file.is_some() && chunk_graph.chunks.len() > 1 {
+      return Err(BuildError::sourcemap_file_with_code_splitting().into());
+    }
 
     self.compute_cross_chunk_links(&mut chunk_graph);
     tracing::info!("compute_cross_chunk_links");
 
+    // File names are generated after cross-chunk links, so the exports of common chunks are known when
+    // `PreRenderedChunk` is passed to the file name functions.
+    self.generate_chunk_filenames(&mut chunk_graph).await?;
+    tracing::info!("generate_chunk_filenames");
+
+    let chunk_representative_names =
+      chunk_graph.chunks.iter().map(Chunk::representative_name).collect::<IndexVec<ChunkId, _>>();
     chunk_graph.chunks.iter_mut().par_bridge().for_each(|chunk| {
-      chunk.de_conflict(self.link_output);
+      chunk.de_conflict(self.link_output, self.output_options, &chunk_representative_names);
     });
 
+    let live_binding_exports = chunk_graph
+      .chunks
+      .iter()
+      .map(|chunk| {
+        if matches!(self.output_options.format, OutputFormat::System | OutputFormat::Amd) {
+          chunk.get_exported_names_by_local(self.link_output)
+        } else {
+          FxHashMap::default()
+        }
+      })
+      .collect::<IndexVec<ChunkId, _>>();
+
     self
       .link_output
       .ast_table
@@ -77,92 +193,248 @@ impl<'a> BundleStage<'a> {
             linking_infos: &self.link_output.metas,
             runtime: &self.link_output.runtime,
             chunk_graph: &chunk_graph,
+            output_options: self.output_options,
+            live_binding_exports: &live_binding_exports[chunk_id],
           },
           ast,
         );
       });
     tracing::info!("finalizing modules");
 
+    if self.output_options.name.is_none() {
+      let has_exports = chunk_graph
+        .chunks
+        .iter()
+        .any(|chunk| !chunk.get_export_names(self.link_output, self.output_options).is_empty());
+      if has_exports {
+        match self.output_options.format {
+          OutputFormat::Iife => {
+            self
+              .link_output
+              .warnings
+              .push(BuildError::missing_name_option_for_iife_export().with_severity_warning());
+          }
+          OutputFormat::Umd => {
+            return Err(BuildError::missing_name_option_for_umd_export().into());
+          }
+          OutputFormat::Esm | OutputFormat::Cjs | OutputFormat::System | OutputFormat::Amd => {}
+        }
+      }
+    }
+
     let chunks = block_on_spawn_all(chunk_graph.chunks.iter().map(|c| async {
       c.render(self.input_options, self.link_output, &chunk_graph, self.output_options).await
     }))
     .into_iter()
     .collect::<Result<Vec<_>, _>>()?;
 
+    let mut chunks = render_chunks(self.plugin_driver, chunks).await?;
+    replace_hash_placeholders(&mut chunks);
+
     let mut assets = vec![];
 
-    render_chunks(self.plugin_driver, chunks).await?.into_iter().try_for_each(
-      |chunk| -> Result<(), BuildError> {
-        let ChunkRenderReturn { mut map, rendered_chunk, mut code } = chunk;
-        if let Some(map) = map.as_mut() {
-          map.set_file(rendered_chunk.file_name.as_str());
-          match self.output_options.sourcemap {
-            SourceMapType::File => {
-              let map_file_name = format!("{}.map", rendered_chunk.file_name);
-              assets.push(Output::Asset(Arc::new(OutputAsset {
-                file_name: map_file_name.clone(),
-                source: map.to_json_string(),
-              })));
-              code.push_str(&format!("\n//# sourceMappingURL={map_file_name}"));
-            }
-            SourceMapType::Inline => {
-              let data_url = map.to_data_url();
-              code.push_str(&format!("\n//# sourceMappingURL={data_url}"));
-            }
-            SourceMapType::Hidden => {}
-          }
-        }
-        let sourcemap_file_name = map.as_ref().map(|_| format!("{}.map", rendered_chunk.file_name));
-        assets.push(Output::Chunk(Arc::new(OutputChunk {
-          file_name: rendered_chunk.file_name,
-          code,
-          is_entry: rendered_chunk.is_entry,
-          is_dynamic_entry: rendered_chunk.is_dynamic_entry,
-          facade_module_id: rendered_chunk.facade_module_id,
-          modules: rendered_chunk.modules,
-          exports: rendered_chunk.exports,
-          module_ids: rendered_chunk.module_ids,
-          map,
-          sourcemap_file_name,
-        })));
-        Ok(())
-      },
-    )?;
+    for chunk in chunks {
+      let ChunkRenderReturn { mut map, rendered_chunk, mut code } = chunk;
+      let mut sourcemap_file_name = None;
+      if let Some(map) = map.as_mut() {
+        sourcemap_file_name =
+          self.emit_sourcemap(map, &rendered_chunk.file_name, &mut code, &mut assets).await?;
+      }
+      assets.push(Output::Chunk(Arc::new(OutputChunk {
+        file_name: rendered_chunk.file_name,
+        code,
+        is_entry: rendered_chunk.is_entry,
+        is_dynamic_entry: rendered_chunk.is_dynamic_entry,
+        facade_module_id: rendered_chunk.facade_module_id,
+        modules: rendered_chunk.modules,
+        exports: rendered_chunk.exports,
+        module_ids: rendered_chunk.module_ids,
+        map,
+        sourcemap_file_name,
+      })));
+    }
 
     tracing::info!("rendered chunks");
 
+    self.emit_plugin_assets(&mut assets)?;
+
     Ok(assets)
   }
 
-  fn generate_chunk_filenames(&self, chunk_graph: &mut ChunkGraph) {
+  /// Adds the assets emitted by plugins to `assets`, named after `asset_file_names` unless they have a
+  /// file name. Like Rollup, a number is appended to the name of an asset if the file name is taken.
+  fn emit_plugin_assets(&self, assets: &mut Vec<Output>) -> Result<(), BuildError> {
+    const OPTION: &str = "output.assetFileNames";
+    let emitted_assets = self.plugin_driver.take_emitted_assets();
+    if emitted_assets.is_empty() {
+      return Ok(());
+    }
+    let file_name_tmp = FileNameTemplate::from(self.output_options.asset_file_names.clone());
+    file_name_tmp.validate(OPTION, ASSET_PLACEHOLDERS)?;
+    let mut used_file_names =
+      assets.iter().map(|asset| asset.file_name().to_lowercase()).collect::<FxHashSet<_>>();
+
+    for asset in emitted_assets {
+      let file_name = if let Some(file_name) = asset.file_name {
+        if !is_path_fragment(&file_name) {
+          return Err(BuildError::invalid_file_name_pattern("fileName", file_name));
+        }
+        file_name
+      } else {
+        let name = asset.name.as_deref().unwrap_or("asset");
+        let path = Path::new(name);
+        let stem = path.file_stem().map_or(Cow::Borrowed(""), |stem| stem.to_string_lossy());
+        let ext = path.extension().map_or(Cow::Borrowed(""), |ext| ext.to_string_lossy());
+        let extname = if ext.is_empty() { String::new() } else { format!(".{ext}") };
+        if !is_path_fragment(&stem) {
+          return Err(BuildError::invalid_file_name_substitution(OPTION, "[name]", stem));
+        }
+        let hash = file_name_tmp
+          .hash_len()
+          .map(|len| xxhash_base64_url(asset.source.as_bytes())[..len].to_string());
+        let file_name = file_name_tmp.render(&FileNameRenderOptions {
+          name: Some(&stem),
+          hash: hash.as_deref(),
+          ext: Some(&ext),
+          extname: Some(&extname),
+          ..Default::default()
+        });
+        make_unique_file_name(file_name, &used_file_names)
+      };
+      used_file_names.insert(file_name.to_lowercase());
+      assets.push(Output::Asset(Arc::new(OutputAsset { file_name, source: asset.source })));
+    }
+    Ok(())
+  }
+
+  /// Attaches the source map of the chunk `file_name` according to `sourcemap`. `File` and `Hidden` maps
+  /// are emitted as assets, whose file name is returned. Only `File` maps are referenced from the code.
+  async fn emit_sourcemap(
+    &self,
+    map: &mut SourceMap,
+    file_name: &str,
+    code: &mut String,
+    assets: &mut Vec<Output>,
+  ) -> Result<Option<String>, BuildError> {
+    let Some(sourcemap) = self.output_options.sourcemap.as_ref() else {
+      return Ok(None);
+    };
+    let sourcemap_file = self.output_options.sourcemap_file.as_deref().unwrap_or(file_name);
+    let map_file_name = format!("{sourcemap_file}.map");
+    let ignore_list = self.finalize_sourcemap_sources(map, &map_file_name).await?;
+    map.set_file(
+      Path::new(sourcemap_file).file_name().and_then(|name| name.to_str()).unwrap_or(file_name),
+    );
+    let debug_id = self.output_options.sourcemap_debug_ids.then(|| {
+      let debug_id = xxhash_uuid(code.as_bytes());
+      code.push_str(&format!("\n//# debugId={debug_id}"));
+      debug_id
+    });
+    let extensions = SourceMapExtensions { ignore_list, debug_id };
+    match sourcemap {
+      SourceMapType::Inline => {
+        let data_url = to_data_url_with_extensions(map, &extensions);
+        code.push_str(&format!("\n//# sourceMappingURL={data_url}"));
+        return Ok(None);
+      }
+      SourceMapType::File => {
+        // The comment is resolved relative to the chunk.
+        let base = &self.input_options.cwd;
+        let chunk_path = base.join(file_name);
+        let url = base
+          .join(&map_file_name)
+          .relative(chunk_path.parent().unwrap_or(base))
+          .to_string_lossy()
+          .replace('\\', "/");
+        code.push_str(&format!("\n//# sourceMappingURL={url}"));
+      }
+      SourceMapType::Hidden => {}
+    }
+    assets.push(Output::Asset(Arc::new(OutputAsset {
+      file_name: map_file_name.clone(),
+      source: to_json_string_with_extensions(map, &extensions),
+    })));
+    Ok(Some(map_file_name))
+  }
+
+  /// Makes the sources of `map` relative to the source map `map_file_name`, applies
+  /// `sourcemap_path_transform` to them and drops their content for `sourcemap_exclude_sources`. Returns the indices of the sources matched by
+  /// `sourcemap_ignore_list`, which are checked before the transform, like Rollup does.
+  async fn finalize_sourcemap_sources(
+    &self,
+    map: &mut SourceMap,
+    map_file_name: &str,
+  ) -> Result<Vec<u32>, BuildError> {
+    let cwd = &self.input_options.cwd;
+    let sourcemap_path = cwd.join(&self.output_options.dir).join(map_file_name);
+    let sourcemap_dir = sourcemap_path.parent().unwrap_or(cwd);
+    // Sources of rendered modules are relative to `cwd`. Plugins might also return absolute ones.
+    let relative_sources = map
+      .get_sources()
+      .map(|source| cwd.join(source).relative(sourcemap_dir).to_string_lossy().replace('\\', "/"))
+      .collect::<Vec<_>>();
+    let sourcemap_path = sourcemap_path.to_string_lossy();
+
+    let mut ignore_list = vec![];
+    let mut sources = Vec::with_capacity(relative_sources.len());
+    for (index, source) in (0u32..).zip(relative_sources) {
+      if self.output_options.sourcemap_ignore_list.call(&source, &sourcemap_path).await? {
+        ignore_list.push(index);
+      }
+      sources.push(match &self.output_options.sourcemap_path_transform {
+        Some(transform) => transform.call(&source, &sourcemap_path).await?,
+        None => source,
+      });
+    }
+    *map = replace_sources(map, sources, self.output_options.sourcemap_exclude_sources);
+    Ok(ignore_list)
+  }
+
+  async fn generate_chunk_filenames(&self, chunk_graph: &mut ChunkGraph) -> Result<(), BuildError> {
+    let format = self.output_options.format.to_string();
     let mut used_chunk_names = FxHashSet::default();
-    chunk_graph.chunks.iter_mut().for_each(|chunk| {
+    let mut hash_placeholder_generator = HashPlaceholderGenerator::default();
+    let chunk_count = chunk_graph.chunks.len();
+    for chunk in &mut chunk_graph.chunks {
       let runtime_id = self.link_output.runtime.id();
 
-      let file_name_tmp = chunk.file_name_template(self.output_options);
-      let chunk_name =
-        if is_in_rust_test_mode() && chunk.modules.first().copied() == Some(runtime_id) {
-          "$runtime$".to_string()
-        } else {
-          chunk.name.clone().unwrap_or_else(|| {
-            let module_id =
-              if let ChunkKind::EntryPoint { module: entry_module_id, is_user_defined, .. } =
-                &chunk.kind
-              {
-                debug_assert!(
-                  !*is_user_defined,
-                  "User-defined entry point should always have a name"
-                );
-                *entry_module_id
-              } else {
-                // TODO: we currently use the first executed module to calculate the chunk name for common chunks
-                // This is not perfect, should investigate more to find a better solution
-                chunk.modules.first().copied().unwrap()
-              };
-            let module = &self.link_output.module_table.normal_modules[module_id];
-            module.resource_id.expect_file().unique(&self.input_options.cwd)
-          })
-        };
+      let (option, file_names) = chunk.file_names_option(self.output_options);
+      let file_name_tmp = FileNameTemplate::from(
+        file_names
+          .call(chunk.get_pre_rendered_chunk_info(self.link_output, self.output_options))
+          .await?,
+      );
+      file_name_tmp.validate(option, CHUNK_PLACEHOLDERS)?;
+      let is_runtime_chunk =
+        self.is_runtime_in_standalone_chunk() && chunk.modules.first().copied() == Some(runtime_id);
+      // The module the chunk is named after. For common chunks, we currently use the first executed module.
+      // TODO: This is not perfect, should investigate more to find a better solution
+      let naming_module = match &chunk.kind {
+        _ if is_runtime_chunk => None,
+        ChunkKind::EntryPoint { module: entry_module_id, .. } => {
+          Some(&self.link_output.module_table.normal_modules[*entry_module_id])
+        }
+        ChunkKind::Common => chunk
+          .modules
+          .first()
+          .map(|module_id| &self.link_output.module_table.normal_modules[*module_id]),
+      };
+
+      let chunk_name = if is_runtime_chunk {
+        "$runtime$".to_string()
+      } else {
+        chunk.name.clone().unwrap_or_else(|| {
+          debug_assert!(
+            !matches!(chunk.kind, ChunkKind::EntryPoint { is_user_defined: true, .. }),
+            "User-defined entry point should always have a name"
+          );
+          let module = naming_module.expect("Chunk should contain at least one module");
+          module.resource_id.expect_file().unique(&self.input_options.cwd)
+        })
+      };
+      if !is_path_fragment(&chunk_name) {
+        return Err(BuildError::invalid_file_name_substitution(option, "[name]", chunk_name));
+      }
 
       let mut chunk_name = chunk_name;
       while used_chunk_names.contains(&chunk_name) {
@@ -170,8 +442,64 @@ impl<'a> BundleStage<'a> {
       }
       used_chunk_names.insert(chunk_name.clone());
 
-      chunk.file_name =
-        Some(file_name_tmp.render(&FileNameRenderOptions { name: Some(&chunk_name) }));
-    });
+      let dir = naming_module
+        .and_then(|module| {
+          let relative_path =
+            module.resource_id.expect_file().relative_path(&self.input_options.cwd);
+          relative_path.parent().map(|dir| {
+            dir
+              .components()
+              .filter_map(|component| match component {
+                Component::Normal(seg) => seg.to_str(),
+                _ => None,
+              })
+              .collect::<Vec<_>>()
+              .join("/")
+          })
+        })
+        .unwrap_or_default();
+
+      let hash_placeholder = file_name_tmp
+        .hash_len()
+        .map(|len| {
+          hash_placeholder_generator.generate(len).ok_or_else(|| {
+            BuildError::hash_too_short(
+              option,
+              chunk_count,
+              HashPlaceholderGenerator::min_hash_len(chunk_count),
+              len,
+            )
+          })
+        })
+        .transpose()?;
+      chunk.file_name = Some(file_name_tmp.render(&FileNameRenderOptions {
+        name: Some(&chunk_name),
+        hash: hash_placeholder.as_deref(),
+        format: Some(&format),
+        dir: Some(&dir),
+        ..Default::default()
+      }));
+    }
+    Ok(())
+  }
+}
+
+/// Appends a number to the stem of `file_name`, like `style2.css`, until it's not in `used_file_names`,
+/// which are lowercase to account for case-insensitive file systems.
+fn make_unique_file_name(file_name: String, used_file_names: &FxHashSet<String>) -> String {
+  if !used_file_names.contains(&file_name.to_lowercase()) {
+    return file_name;
+  }
+  let (stem, extname) = match file_name.rfind('.') {
+    Some(idx) if idx > file_name.rfind('/').map_or(0, |idx| idx + 1) => file_name.split_at(idx),
+    _ => (file_name.as_str(), ""),
+  };
+  let mut count = 2;
+  loop {
+    let candidate = format!("{stem}{count}{extname}");
+    if !used_file_names.contains(&candidate.to_lowercase()) {
+      return candidate;
+    }
+    count += 1;
   }
 }
@@ -0,0 +1,100 @@
+use std::path::{Component, Path, PathBuf};
+
+use index_vec::IndexVec;
+use rolldown_common::{ChunkId, ChunkKind, NormalModule, NormalModuleId};
+use rolldown_utils::BitSet;
+use rustc_hash::FxHashMap;
+
+use crate::chunk::{Chunk, ChunksVec};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// `preserve_modules_root`, or the common ancestor directory of the user-defined entries.
+  fn preserve_modules_root(&self) -> PathBuf {
+    if let Some(root) = &self.output_options.preserve_modules_root {
+      return root.clone();
+    }
+    let modules = &self.link_output.module_table.normal_modules;
+    let mut entry_dirs = self
+      .link_output
+      .entries
+      .iter()
+      .filter(|entry_point| modules[entry_point.id].is_user_defined_entry)
+      .filter_map(|entry_point| {
+        Path::new(modules[entry_point.id].resource_id.expect_file().as_str()).parent()
+      });
+    let Some(first) = entry_dirs.next() else {
+      return self.input_options.cwd.clone();
+    };
+    entry_dirs.fold(first.to_path_buf(), |common, dir| {
+      common
+        .components()
+        .zip(dir.components())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .collect()
+    })
+  }
+
+  /// The name of the chunk of `module`: its path relative to `root` without the extension. Modules outside
+  /// of `root` get a `_` for every `..` segment, so their chunks still stay inside the output directory.
+  fn preserved_module_name(&self, module: &NormalModule, root: &Path) -> String {
+    if module.id == self.link_output.runtime.id() {
+      return "_virtual/rolldown_runtime".to_string();
+    }
+    let mut relative = module.resource_id.expect_file().relative_path(root);
+    relative.set_extension("");
+    relative
+      .components()
+      .filter_map(|component| match component {
+        Component::Normal(seg) => seg.to_str(),
+        Component::ParentDir => Some("_"),
+        _ => None,
+      })
+      .collect::<Vec<_>>()
+      .join("/")
+  }
+
+  /// Puts every included module into its own chunk. Entry modules keep the entry chunks that are already
+  /// created, all other modules get a common chunk. Chunks are created in execution order.
+  pub(super) fn generate_preserved_module_chunks(
+    &self,
+    module_to_bits: &IndexVec<NormalModuleId, BitSet>,
+    chunks: &mut ChunksVec,
+  ) -> IndexVec<NormalModuleId, Option<ChunkId>> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let root = self.preserve_modules_root();
+    let mut module_to_chunk: IndexVec<NormalModuleId, Option<ChunkId>> =
+      index_vec::index_vec![None; modules.len()];
+
+    let mut entry_chunks = FxHashMap::default();
+    chunks.iter_enumerated().for_each(|(chunk_id, chunk)| {
+      if let ChunkKind::EntryPoint { module, .. } = chunk.kind {
+        entry_chunks.entry(module).or_insert(chunk_id);
+      }
+    });
+
+    let mut included_modules =
+      modules.iter().filter(|module| module.is_included).collect::<Vec<_>>();
+    included_modules.sort_by_key(|module| module.exec_order);
+    for module in included_modules {
+      let name = self.preserved_module_name(module, &root);
+      let chunk_id = if let Some(chunk_id) = entry_chunks.get(&module.id).copied() {
+        chunks[chunk_id].name = Some(name);
+        chunks[chunk_id].modules.push(module.id);
+        chunk_id
+      } else {
+        chunks.push(Chunk::new(
+          Some(name),
+          module_to_bits[module.id].clone(),
+          vec![module.id],
+          ChunkKind::Common,
+        ))
+      };
+      module_to_chunk[module.id] = Some(chunk_id);
+    }
+
+    module_to_chunk
+  }
+}
@@ -1,11 +1,13 @@
 // TODO: The current implementation for matching imports is enough so far but incomplete. It needs to be refactored
 // if we want more enhancements related to exports.
 
+use index_vec::IndexVec;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
-  ExportsKind, ModuleId, NamedImport, NormalModule, NormalModuleId, ResolvedExport, Specifier,
-  SymbolRef,
+  ExportsKind, ModuleId, NamedImport, NamespaceMemberRef, NormalModule, NormalModuleId,
+  ResolvedExport, Specifier, SymbolRef,
 };
+use rustc_hash::FxHashMap;
 
 use crate::types::{
   linking_metadata::{LinkingMetadata, LinkingMetadataVec},
@@ -97,6 +99,53 @@ impl<'a> LinkStage<'a> {
     });
   }
 
+  /// Resolves static member accesses on namespace imports, like `ns.foo`, to the exports they read. The
+  /// namespace object is referenced instead if the export isn't known statically, like the exports of
+  /// commonjs modules.
+  pub fn resolve_namespace_members(&mut self) {
+    let resolved_namespace_members = self
+      .module_table
+      .normal_modules
+      .iter()
+      .map(|module| {
+        module
+          .stmt_infos
+          .iter()
+          .flat_map(|stmt_info| &stmt_info.referenced_namespace_members)
+          .filter_map(|member| Some((member.span, self.resolve_namespace_member(member)?)))
+          .collect::<FxHashMap<_, _>>()
+      })
+      .collect::<IndexVec<NormalModuleId, _>>();
+
+    self.module_table.normal_modules.iter_mut().zip(resolved_namespace_members).for_each(
+      |(module, resolved_namespace_members)| {
+        module.stmt_infos.iter_mut().for_each(|stmt_info| {
+          // The canonical namespace is referenced, so tree shaking can tell the property accesses of the
+          // namespace object of a commonjs module from other uses
+          let referenced = stmt_info.referenced_namespace_members.iter().map(|member| {
+            resolved_namespace_members
+              .get(&member.span)
+              .copied()
+              .unwrap_or_else(|| self.symbols.par_canonical_ref_for(member.namespace_ref))
+          });
+          stmt_info.referenced_symbols.extend(referenced);
+        });
+        self.metas[module.id].resolved_namespace_members = resolved_namespace_members;
+      },
+    );
+  }
+
+  fn resolve_namespace_member(&self, member: &NamespaceMemberRef) -> Option<SymbolRef> {
+    let namespace_ref = self.symbols.par_canonical_ref_for(member.namespace_ref);
+    let importee = &self.module_table.normal_modules[namespace_ref.owner];
+    if importee.namespace_symbol != namespace_ref
+      || !matches!(importee.exports_kind, ExportsKind::Esm)
+    {
+      return None;
+    }
+    self.metas[importee.id].canonical_export(&member.property_name).map(|export| export.symbol_ref)
+  }
+
   pub fn match_import_with_export(
     importer: &NormalModule,
     importee: &NormalModule,
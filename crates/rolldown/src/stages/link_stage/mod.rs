@@ -3,13 +3,17 @@ use std::{ptr::addr_of, sync::Mutex};
 use index_vec::IndexVec;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
-  EntryPoint, ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId, StmtInfo, WrapKind,
+  EntryPoint, EntryPointKind, ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId,
+  StmtInfo, WrapKind,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::OxcProgram;
 
 use crate::{
-  options::normalized_input_options::NormalizedInputOptions,
+  options::{
+    normalized_input_options::NormalizedInputOptions,
+    normalized_output_options::NormalizedOutputOptions,
+  },
   runtime::RuntimeModuleBrief,
   types::{
     linking_metadata::{LinkingMetadata, LinkingMetadataVec},
@@ -50,12 +54,14 @@ pub struct LinkStage<'a> {
   pub warnings: Vec<BuildError>,
   pub ast_table: IndexVec<NormalModuleId, OxcProgram>,
   pub input_options: &'a NormalizedInputOptions,
+  pub output_options: &'a NormalizedOutputOptions,
 }
 
 impl<'a> LinkStage<'a> {
   pub fn new(
     scan_stage_output: ScanStageOutput,
     input_options: &'a NormalizedInputOptions,
+    output_options: &'a NormalizedOutputOptions,
   ) -> Self {
     Self {
       sorted_modules: Vec::new(),
@@ -72,6 +78,7 @@ impl<'a> LinkStage<'a> {
       warnings: scan_stage_output.warnings,
       ast_table: scan_stage_output.ast_table,
       input_options,
+      output_options,
     }
   }
 
@@ -98,6 +105,8 @@ impl<'a> LinkStage<'a> {
           declared_symbols: vec![module.namespace_symbol],
           referenced_symbols,
           side_effect: false,
+          side_effect_calls: vec![],
+          referenced_namespace_members: vec![],
           is_included: false,
           import_records: Vec::new(),
           debug_label: None,
@@ -114,10 +123,17 @@ impl<'a> LinkStage<'a> {
   pub fn link(mut self) -> LinkStageOutput {
     tracing::info!("Start link stage");
     self.sort_modules();
+    if self.output_options.inline_dynamic_imports {
+      // Dynamically imported modules are bundled into the chunk of their importer instead of
+      // becoming entries. Modules are sorted before, so they are still executed after the
+      // statically imported ones.
+      self.entries.retain(|entry| matches!(entry.kind, EntryPointKind::UserDefined));
+    }
 
     self.determine_module_exports_kind();
     self.wrap_modules();
     self.bind_imports_and_exports();
+    self.resolve_namespace_members();
     tracing::debug!("linking modules {:#?}", self.metas);
     self.create_exports_for_modules();
     self.reference_needed_symbols();
@@ -276,7 +292,26 @@ impl<'a> LinkStage<'a> {
                 stmt_info.referenced_symbols.push(importee.namespace_symbol);
               }
             },
-            ImportKind::DynamicImport => {}
+            ImportKind::DynamicImport => {
+              if self.output_options.inline_dynamic_imports {
+                // something like `Promise.resolve().then(() => foo_exports)`, with `init_foo()` or
+                // `__toESM(require_foo())` for wrapped modules
+                let importee = &self.module_table.normal_modules[importee_id];
+                match importee_linking_info.wrap_kind {
+                  WrapKind::None => {
+                    stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                  }
+                  WrapKind::Cjs => {
+                    stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                    stmt_info.referenced_symbols.push(self.runtime.resolve_symbol("__toESM"));
+                  }
+                  WrapKind::Esm => {
+                    stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                    stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                  }
+                }
+              }
+            }
           }
         });
       });
@@ -301,6 +336,8 @@ pub fn init_entry_point_stmt_info(module: &mut NormalModule, meta: &mut LinkingM
     referenced_symbols,
     // Yeah, it has side effects
     side_effect: true,
+    side_effect_calls: vec![],
+    referenced_namespace_members: vec![],
     is_included: false,
     import_records: Vec::new(),
     debug_label: None,
@@ -1,5 +1,9 @@
 use index_vec::IndexVec;
-use rolldown_common::{NormalModule, NormalModuleId, StmtInfoId, SymbolRef};
+use rolldown_common::{
+  ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId, StmtInfoId, SymbolRef,
+};
+use rolldown_rstr::Rstr;
+use rustc_hash::FxHashMap;
 
 use crate::types::{module_table::NormalModuleVec, symbols::Symbols};
 
@@ -12,6 +16,9 @@ struct Context<'a> {
   is_module_included_vec: &'a mut IndexVec<NormalModuleId, bool>,
   tree_shaking: bool,
   runtime_id: NormalModuleId,
+  /// The commonjs modules imported by `import` statements, keyed by the namespace symbols created for the
+  /// import records, like `import_foo` of `var import_foo = __toESM(require_foo())`.
+  commonjs_namespace_refs: &'a FxHashMap<SymbolRef, NormalModuleId>,
 }
 
 fn include_module(ctx: &mut Context, module: &NormalModule) {
@@ -37,12 +44,55 @@ fn include_module(ctx: &mut Context, module: &NormalModule) {
     });
   }
 
-  module.import_records.iter().for_each(|import_record| match import_record.resolved_module {
-    rolldown_common::ModuleId::Normal(importee_id) => {
-      let importee = &ctx.modules[importee_id];
-      include_module(ctx, importee);
+  module.import_records.iter_enumerated().for_each(|(rec_id, import_record)| {
+    match import_record.resolved_module {
+      ModuleId::Normal(importee_id) => {
+        let importee = &ctx.modules[importee_id];
+        // A module without side effects is included by `include_symbol` once one of its exports is used.
+        // `require()` and `import()` still need the module to exist.
+        let is_side_effect_free = ctx.tree_shaking
+          && !importee.side_effects
+          && matches!(import_record.kind, ImportKind::Import);
+        if !is_side_effect_free {
+          include_module(ctx, importee);
+        }
+        // The whole exports object is returned by `require()` and `import()` and copied by `export *`
+        if !matches!(import_record.kind, ImportKind::Import)
+          || module.star_exports.contains(&rec_id)
+        {
+          include_all_commonjs_exports(ctx, importee);
+        }
+      }
+      ModuleId::External(_) => {}
+    }
+  });
+}
+
+/// Includes the assignments of the export `property_name` of the commonjs module whose namespace is
+/// `namespace_ref`. `None` means that the namespace object is used as a value, so every export is included.
+fn include_commonjs_export(
+  ctx: &mut Context,
+  namespace_ref: SymbolRef,
+  property_name: Option<&Rstr>,
+) {
+  let Some(importee_id) = ctx.commonjs_namespace_refs.get(&namespace_ref) else {
+    return;
+  };
+  let importee = &ctx.modules[*importee_id];
+  // The default export is the exports object itself, unless the module is marked with `__esModule`
+  match property_name.filter(|name| name.as_str() != "default") {
+    Some(name) => {
+      importee.commonjs_exports.get(name).into_iter().flatten().for_each(|stmt_info_id| {
+        include_statement(ctx, importee, *stmt_info_id);
+      });
     }
-    rolldown_common::ModuleId::External(_) => {}
+    None => include_all_commonjs_exports(ctx, importee),
+  }
+}
+
+fn include_all_commonjs_exports(ctx: &mut Context, module: &NormalModule) {
+  module.commonjs_exports.values().flatten().for_each(|stmt_info_id| {
+    include_statement(ctx, module, *stmt_info_id);
   });
 }
 
@@ -52,6 +102,11 @@ fn include_symbol(ctx: &mut Context, symbol_ref: SymbolRef) {
   let canonical_ref_symbol = ctx.symbols.get(canonical_ref);
   if let Some(namespace_alias) = &canonical_ref_symbol.namespace_alias {
     canonical_ref = namespace_alias.namespace_ref;
+    include_commonjs_export(ctx, canonical_ref, Some(&namespace_alias.property_name));
+  } else if symbol_ref != canonical_ref {
+    // Something like `import * as ns from './foo.cjs'; console.log(ns)`. The canonical namespace itself is
+    // only referenced by the import statement and by accesses like `ns.foo`, which include their export.
+    include_commonjs_export(ctx, canonical_ref, None);
   }
   include_module(ctx, canonical_ref_module);
   canonical_ref_module
@@ -75,18 +130,50 @@ fn include_statement(ctx: &mut Context, module: &NormalModule, stmt_info_id: Stm
 
   let stmt_info = module.stmt_infos.get(stmt_info_id);
 
-  // include statements that are referenced by this statement
-  stmt_info.declared_symbols.iter().chain(stmt_info.referenced_symbols.iter()).for_each(
-    |symbol_ref| {
+  // include statements that are referenced by this statement. The local bindings of import declarations are
+  // skipped, because an included import statement doesn't use the symbols it imports by itself.
+  stmt_info
+    .declared_symbols
+    .iter()
+    .filter(|symbol_ref| {
+      symbol_ref.owner != module.id || !module.named_imports.contains_key(&symbol_ref.symbol)
+    })
+    .chain(stmt_info.referenced_symbols.iter())
+    .for_each(|symbol_ref| {
       include_symbol(ctx, *symbol_ref);
-    },
-  );
+    });
+
+  // `ns.foo` isn't resolved at link time if `ns` is the namespace of a commonjs module
+  stmt_info.referenced_namespace_members.iter().for_each(|member| {
+    let namespace_ref = ctx.symbols.par_canonical_ref_for(member.namespace_ref);
+    include_commonjs_export(ctx, namespace_ref, Some(&member.property_name));
+  });
 }
 
 impl LinkStage<'_> {
+  /// Statements that only call functions annotated with `@__NO_SIDE_EFFECTS__` have no side effects. This
+  /// can't be determined while scanning, since the called functions may be imported.
+  fn remove_side_effects_of_no_side_effects_calls(&mut self) {
+    let symbols = &self.symbols;
+    self.module_table.normal_modules.iter_mut().for_each(|module| {
+      module.stmt_infos.iter_mut().for_each(|stmt_info| {
+        if !stmt_info.side_effect_calls.is_empty()
+          && stmt_info.side_effect_calls.iter().all(|callee| {
+            let canonical_ref = symbols.par_canonical_ref_for(*callee);
+            symbols.get(canonical_ref).no_side_effects
+          })
+        {
+          stmt_info.side_effect = false;
+        }
+      });
+    });
+  }
+
   pub fn include_statements(&mut self) {
     use rayon::prelude::*;
 
+    self.remove_side_effects_of_no_side_effects_calls();
+
     let mut is_included_vec: IndexVec<NormalModuleId, IndexVec<StmtInfoId, bool>> = self
       .module_table
       .normal_modules
@@ -97,6 +184,22 @@ impl LinkStage<'_> {
     let mut is_module_included_vec: IndexVec<NormalModuleId, bool> =
       index_vec::index_vec![false; self.module_table.normal_modules.len()];
 
+    let commonjs_namespace_refs = self
+      .module_table
+      .normal_modules
+      .iter()
+      .flat_map(|importer| &importer.import_records)
+      .filter_map(|rec| match rec.resolved_module {
+        ModuleId::Normal(importee_id)
+          if self.module_table.normal_modules[importee_id].exports_kind
+            == ExportsKind::CommonJs =>
+        {
+          Some((rec.namespace_ref, importee_id))
+        }
+        _ => None,
+      })
+      .collect::<FxHashMap<_, _>>();
+
     let context = &mut Context {
       modules: &self.module_table.normal_modules,
       symbols: &self.symbols,
@@ -104,12 +207,14 @@ impl LinkStage<'_> {
       is_module_included_vec: &mut is_module_included_vec,
       tree_shaking: self.input_options.treeshake,
       runtime_id: self.runtime.id(),
+      commonjs_namespace_refs: &commonjs_namespace_refs,
     };
 
     self.entries.iter().for_each(|entry| {
       let module = &self.module_table.normal_modules[entry.id];
 
       include_module(context, module);
+      include_all_commonjs_exports(context, module);
     });
 
     self.module_table.normal_modules.iter_mut().par_bridge().for_each(|module| {
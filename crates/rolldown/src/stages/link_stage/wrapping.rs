@@ -155,6 +155,8 @@ pub fn create_wrapper(
         declared_symbols: vec![wrapper_ref],
         referenced_symbols: vec![runtime.resolve_symbol("__commonJSMin")],
         side_effect: false,
+        side_effect_calls: vec![],
+        referenced_namespace_members: vec![],
         is_included: false,
         import_records: Vec::new(),
         debug_label: None,
@@ -180,6 +182,8 @@ pub fn create_wrapper(
         declared_symbols: vec![wrapper_ref],
         referenced_symbols: vec![runtime.resolve_symbol("__esmMin")],
         side_effect: false,
+        side_effect_calls: vec![],
+        referenced_namespace_members: vec![],
         is_included: false,
         import_records: Vec::new(),
         debug_label: None,
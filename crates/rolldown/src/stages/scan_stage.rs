@@ -16,7 +16,7 @@ use crate::{
   types::{
     module_table::ModuleTable, resolved_request_info::ResolvedRequestInfo, symbols::Symbols,
   },
-  utils::resolve_id::resolve_id,
+  utils::{define::Defines, resolve_id::resolve_id},
   SharedResolver,
 };
 
@@ -52,11 +52,14 @@ impl ScanStage {
     tracing::info!("Start scan stage");
     assert!(!self.input_options.input.is_empty(), "You must supply options.input to rolldown");
 
+    let defines = Defines::new(&self.input_options.define)?;
+
     let mut module_loader = ModuleLoader::new(
       Arc::clone(&self.input_options),
       Arc::clone(&self.plugin_driver),
       self.fs.clone(),
       Arc::clone(&self.resolver),
+      defines,
     );
 
     module_loader.try_spawn_runtime_module_task();
@@ -3,6 +3,7 @@ use oxc::{
   semantic::{ScopeId, SymbolFlags, SymbolId, SymbolTable},
   span::{CompactStr as CompactString, Span},
 };
+use rustc_hash::FxHashSet;
 
 #[derive(Debug, Default)]
 pub struct AstSymbols {
@@ -10,12 +11,20 @@ pub struct AstSymbols {
   pub scope_ids: IndexVec<SymbolId, ScopeId>,
   pub spans: IndexVec<SymbolId, Span>,
   pub flags: IndexVec<SymbolId, SymbolFlags>,
+  /// Functions annotated with `@__NO_SIDE_EFFECTS__`
+  pub no_side_effects_functions: FxHashSet<SymbolId>,
 }
 
 impl AstSymbols {
   pub fn from_symbol_table(table: SymbolTable) -> Self {
     debug_assert!(table.references.is_empty());
-    Self { names: table.names, scope_ids: table.scope_ids, spans: table.spans, flags: table.flags }
+    Self {
+      names: table.names,
+      scope_ids: table.scope_ids,
+      spans: table.spans,
+      flags: table.flags,
+      no_side_effects_functions: FxHashSet::default(),
+    }
   }
 
   pub fn create_symbol(&mut self, name: CompactString, scope_id: ScopeId) -> SymbolId {
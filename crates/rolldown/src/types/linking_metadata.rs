@@ -1,4 +1,5 @@
 use index_vec::IndexVec;
+use oxc::span::Span;
 use rolldown_common::{NormalModuleId, ResolvedExport, StmtInfoId, SymbolRef, WrapKind};
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
@@ -43,6 +44,10 @@ pub struct LinkingMetadata {
   // The unknown export name will be resolved at runtime.
   // esbuild add it to `ExportKind`, but the linker shouldn't mutate the module.
   pub has_dynamic_exports: bool,
+  /// The exports read by static member accesses on namespace imports, keyed by the span of the member
+  /// expression. `ns.foo` is rewritten to the canonical name of `foo`, so the namespace object is only
+  /// included if it's used as a value.
+  pub resolved_namespace_members: FxHashMap<Span, SymbolRef>,
 }
 
 impl LinkingMetadata {
@@ -53,6 +58,14 @@ impl LinkingMetadata {
       .map(|name| (name, &self.resolved_exports[name]))
   }
 
+  pub fn canonical_export(&self, name: &Rstr) -> Option<&ResolvedExport> {
+    self
+      .sorted_and_non_ambiguous_resolved_exports
+      .binary_search_by(|probe| probe.as_str().cmp(name.as_str()))
+      .ok()
+      .map(|_| &self.resolved_exports[name])
+  }
+
   pub fn canonical_exports_len(&self) -> usize {
     self.sorted_and_non_ambiguous_resolved_exports.len()
   }
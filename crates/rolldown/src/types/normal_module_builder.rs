@@ -4,7 +4,7 @@ use index_vec::IndexVec;
 use oxc::{semantic::SymbolId, span::Span};
 use rolldown_common::{
   AstScope, ExportsKind, ImportRecord, ImportRecordId, LocalExport, ModuleType, NamedImport,
-  NormalModule, NormalModuleId, ResourceId, StmtInfos, SymbolRef,
+  NormalModule, NormalModuleId, ResourceId, StmtInfoId, StmtInfos, SymbolRef,
 };
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
@@ -25,10 +25,12 @@ pub struct NormalModuleBuilder {
   pub default_export_ref: Option<SymbolRef>,
   pub namespace_symbol: Option<SymbolRef>,
   pub exports_kind: Option<ExportsKind>,
+  pub commonjs_exports: Option<FxHashMap<Rstr, Vec<StmtInfoId>>>,
   pub module_type: ModuleType,
   pub is_user_defined_entry: Option<bool>,
   pub pretty_path: Option<String>,
   pub sourcemap_chain: Vec<rolldown_sourcemap::SourceMap>,
+  pub side_effects: Option<bool>,
 }
 
 impl NormalModuleBuilder {
@@ -49,10 +51,12 @@ impl NormalModuleBuilder {
       scope: self.scope.unwrap(),
       namespace_symbol: self.namespace_symbol.unwrap(),
       exports_kind: self.exports_kind.unwrap_or(ExportsKind::Esm),
+      commonjs_exports: self.commonjs_exports.unwrap_or_default(),
       module_type: self.module_type,
       is_user_defined_entry: self.is_user_defined_entry.unwrap(),
       pretty_path: self.pretty_path.unwrap(),
       sourcemap_chain: self.sourcemap_chain,
+      side_effects: self.side_effects.unwrap_or(true),
       is_included: false,
     }
   }
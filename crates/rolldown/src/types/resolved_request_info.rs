@@ -5,4 +5,8 @@ pub struct ResolvedRequestInfo {
   pub path: ResolvedPath,
   pub module_type: ModuleType,
   pub is_external: bool,
+  /// Whether the module has side effects, as returned by the `resolve_id` hook.
+  pub side_effects: Option<bool>,
+  /// Whether the module has side effects, as declared by `sideEffects` of the package.json owning it.
+  pub package_json_side_effects: Option<bool>,
 }
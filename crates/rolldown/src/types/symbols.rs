@@ -17,6 +17,8 @@ pub struct Symbol {
   pub link: Option<SymbolRef>,
   /// The chunk that this symbol is defined in.
   pub chunk_id: Option<ChunkId>,
+  /// Whether calling this function has no side effects, because it's annotated with `@__NO_SIDE_EFFECTS__`.
+  pub no_side_effects: bool,
 }
 
 // Information about symbols for all modules
@@ -32,14 +34,25 @@ impl Symbols {
   pub fn add_ast_symbol(&mut self, module_id: NormalModuleId, ast_symbol: AstSymbols) {
     self.inner[module_id] = ast_symbol
       .names
-      .into_iter()
-      .map(|name| Symbol { name, link: None, chunk_id: None, namespace_alias: None })
+      .into_iter_enumerated()
+      .map(|(symbol_id, name)| Symbol {
+        name,
+        link: None,
+        chunk_id: None,
+        namespace_alias: None,
+        no_side_effects: ast_symbol.no_side_effects_functions.contains(&symbol_id),
+      })
       .collect();
   }
 
   pub fn create_symbol(&mut self, owner: NormalModuleId, name: CompactString) -> SymbolRef {
-    let symbol_id =
-      self.inner[owner].push(Symbol { name, link: None, chunk_id: None, namespace_alias: None });
+    let symbol_id = self.inner[owner].push(Symbol {
+      name,
+      link: None,
+      chunk_id: None,
+      namespace_alias: None,
+      no_side_effects: false,
+    });
     SymbolRef { owner, symbol: symbol_id }
   }
 
@@ -0,0 +1,191 @@
+use oxc::{
+  allocator::{self, Allocator},
+  ast::{
+    ast,
+    visit::walk_mut::{
+      walk_call_expression_mut, walk_expression_mut, walk_statement_mut, walk_statements_mut,
+      walk_tagged_template_expression_mut, walk_unary_expression_mut,
+    },
+    Visit, VisitMut,
+  },
+  semantic::ScopeFlags,
+  span::SPAN,
+  syntax::operator::UnaryOperator,
+};
+use rolldown_oxc_utils::{AstSnippet, BindingPatternExt, Dummy, IntoIn, OxcProgram, TakeIn};
+
+use super::static_value::{get_static_boolean, StaticPrimitive};
+
+/// Removes the code of the module that never runs because of a constant condition, like the consequent of
+/// `if (process.env.NODE_ENV !== 'production')` once `define` replaced `process.env.NODE_ENV`.
+///
+/// This runs before the semantic analysis, so the scanner never sees the dead code. Its references and
+/// imports aren't recorded, and tree shaking removes what only this code used.
+pub fn fold_dead_branches(ast: &mut OxcProgram) {
+  let (program, alloc) = ast.program_mut_and_allocator();
+  let mut folder = DeadBranchFolder { alloc, snippet: AstSnippet::new(alloc) };
+  folder.visit_program(program);
+}
+
+struct DeadBranchFolder<'ast> {
+  alloc: &'ast Allocator,
+  snippet: AstSnippet<'ast>,
+}
+
+impl<'ast> DeadBranchFolder<'ast> {
+  /// Replace `if` statements with a constant test by the branch that runs. The `var`s of the other branch
+  /// are still declared, since they are hoisted out of it.
+  fn try_fold_constant_if_statement(&self, stmt: &mut ast::Statement<'ast>) -> bool {
+    let ast::Statement::IfStatement(if_stmt) = stmt else {
+      return false;
+    };
+    let Some(test) = get_static_boolean(&if_stmt.test) else {
+      return false;
+    };
+    let consequent = Some(if_stmt.consequent.take_in(self.alloc));
+    let alternate = if_stmt.alternate.take();
+    let (live, dead) = if test { (consequent, alternate) } else { (alternate, consequent) };
+
+    let mut hoisted_vars = HoistedVarsCollector::default();
+    if let Some(dead) = &dead {
+      hoisted_vars.visit_statement(dead);
+    }
+    let hoisted_var_decl = (!hoisted_vars.0.is_empty()).then(|| {
+      let mut declarations = allocator::Vec::new_in(self.alloc);
+      declarations.extend(hoisted_vars.0.into_iter().map(|id| ast::VariableDeclarator {
+        id: ast::BindingPattern {
+          kind: ast::BindingPatternKind::BindingIdentifier(id.into_in(self.alloc)),
+          ..Dummy::dummy(self.alloc)
+        },
+        kind: ast::VariableDeclarationKind::Var,
+        ..Dummy::dummy(self.alloc)
+      }));
+      ast::Statement::Declaration(ast::Declaration::VariableDeclaration(
+        ast::VariableDeclaration {
+          declarations,
+          kind: ast::VariableDeclarationKind::Var,
+          ..Dummy::dummy(self.alloc)
+        }
+        .into_in(self.alloc),
+      ))
+    });
+
+    *stmt = match (live, hoisted_var_decl) {
+      (Some(live), None) => live,
+      (None, Some(hoisted_var_decl)) => hoisted_var_decl,
+      (Some(live), Some(hoisted_var_decl)) => {
+        let mut body = allocator::Vec::new_in(self.alloc);
+        body.extend([hoisted_var_decl, live]);
+        ast::Statement::BlockStatement(ast::BlockStatement { span: SPAN, body }.into_in(self.alloc))
+      }
+      // An empty statement, which is removed from the statement list containing it
+      (None, None) => ast::Statement::dummy(self.alloc),
+    };
+    true
+  }
+
+  /// Replace `test ? a : b`, `a && b`, `a || b` and `a ?? b` with a constant condition by the operand that
+  /// is evaluated.
+  fn try_fold_constant_condition(&self, expr: &mut ast::Expression<'ast>) -> bool {
+    let kept = match expr {
+      ast::Expression::ConditionalExpression(cond_expr) => {
+        match get_static_boolean(&cond_expr.test) {
+          Some(true) => cond_expr.consequent.take_in(self.alloc),
+          Some(false) => cond_expr.alternate.take_in(self.alloc),
+          None => return false,
+        }
+      }
+      ast::Expression::LogicalExpression(logic_expr) => {
+        let Some(left) = StaticPrimitive::from_expression(&logic_expr.left) else {
+          return false;
+        };
+        if left.short_circuits(logic_expr.operator) {
+          logic_expr.left.take_in(self.alloc)
+        } else {
+          logic_expr.right.take_in(self.alloc)
+        }
+      }
+      _ => return false,
+    };
+    *expr = kept;
+    true
+  }
+
+  /// Folds the operand of a call, a tagged template or `delete`, whose meaning depends on whether it's a
+  /// reference. `(true && a.b)()` needs to become `(0, a.b)()`, since `a.b()` would be called with `a` as
+  /// `this`. Likewise, `delete (true && a.b)` doesn't delete anything, while `delete a.b` does.
+  fn fold_constant_reference_operand(&self, operand: &mut ast::Expression<'ast>, is_deleted: bool) {
+    let mut operand = operand;
+    while let ast::Expression::ParenthesizedExpression(paren_expr) = operand {
+      operand = &mut paren_expr.expression;
+    }
+    let mut is_folded = false;
+    while self.try_fold_constant_condition(operand) {
+      is_folded = true;
+    }
+    let is_reference = match operand {
+      ast::Expression::MemberExpression(_) | ast::Expression::ChainExpression(_) => true,
+      // `eval(code)` is a direct eval, unlike `(0, eval)(code)`
+      ast::Expression::Identifier(ident) => is_deleted || ident.name == "eval",
+      _ => is_deleted,
+    };
+    if is_folded && is_reference {
+      *operand =
+        self.snippet.seq2_in_paren_expr(self.snippet.number_expr(0.0), operand.take_in(self.alloc));
+    }
+  }
+}
+
+impl<'ast> VisitMut<'ast> for DeadBranchFolder<'ast> {
+  fn visit_statements(&mut self, stmts: &mut allocator::Vec<'ast, ast::Statement<'ast>>) {
+    walk_statements_mut(self, stmts);
+    stmts.retain(|stmt| !matches!(stmt, ast::Statement::EmptyStatement(_)));
+  }
+
+  fn visit_statement(&mut self, stmt: &mut ast::Statement<'ast>) {
+    while self.try_fold_constant_if_statement(stmt) {}
+    walk_statement_mut(self, stmt);
+  }
+
+  fn visit_expression(&mut self, expr: &mut ast::Expression<'ast>) {
+    while self.try_fold_constant_condition(expr) {}
+    walk_expression_mut(self, expr);
+  }
+
+  fn visit_call_expression(&mut self, expr: &mut ast::CallExpression<'ast>) {
+    self.fold_constant_reference_operand(&mut expr.callee, false);
+    walk_call_expression_mut(self, expr);
+  }
+
+  fn visit_tagged_template_expression(&mut self, expr: &mut ast::TaggedTemplateExpression<'ast>) {
+    self.fold_constant_reference_operand(&mut expr.tag, false);
+    walk_tagged_template_expression_mut(self, expr);
+  }
+
+  fn visit_unary_expression(&mut self, expr: &mut ast::UnaryExpression<'ast>) {
+    if expr.operator == UnaryOperator::Delete {
+      self.fold_constant_reference_operand(&mut expr.argument, true);
+    }
+    walk_unary_expression_mut(self, expr);
+  }
+}
+
+/// Collects the identifiers declared by `var`s, without looking into nested functions and classes.
+#[derive(Default)]
+struct HoistedVarsCollector<'ast>(Vec<ast::BindingIdentifier<'ast>>);
+
+impl<'ast> Visit<'ast> for HoistedVarsCollector<'ast> {
+  fn visit_variable_declaration(&mut self, decl: &ast::VariableDeclaration<'ast>) {
+    if decl.kind.is_var() {
+      for declarator in &decl.declarations {
+        self.0.extend(declarator.id.binding_identifiers().into_iter().map(|id| (**id).clone()));
+      }
+    }
+  }
+
+  fn visit_function(&mut self, _func: &ast::Function<'ast>, _flags: Option<ScopeFlags>) {}
+
+  fn visit_arrow_expression(&mut self, _expr: &ast::ArrowFunctionExpression<'ast>) {}
+
+  fn visit_class(&mut self, _class: &ast::Class<'ast>) {}
+}
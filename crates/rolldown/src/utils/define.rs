@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use oxc::{
+  allocator::Allocator,
+  ast::{
+    ast::{self, Expression, IdentifierReference, MemberExpression},
+    visit::walk_mut::{walk_expression_mut, walk_object_property_mut},
+    VisitMut,
+  },
+  parser::Parser,
+  semantic::ReferenceId,
+  span::{GetSpan, SourceType, Span, SPAN},
+  syntax::operator::UnaryOperator,
+};
+use rolldown_error::BuildError;
+use rolldown_oxc_utils::{AstSnippet, IntoIn, OxcProgram};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The validated `InputOptions::define`, keyed by the dotted path of the replaced expression.
+#[derive(Debug, Default)]
+pub struct Defines {
+  values: FxHashMap<String, DefineValue>,
+}
+
+/// Like esbuild, only literals and references can be used as values, since they don't need source positions.
+#[derive(Debug)]
+enum DefineValue {
+  String(String),
+  Number(f64),
+  Boolean(bool),
+  Null,
+  /// `a.b.c` or `import.meta.a`
+  Reference(Vec<String>),
+}
+
+impl Defines {
+  pub fn new(define: &HashMap<String, String>) -> Result<Self, BuildError> {
+    // Sorted, so that the reported error and the winner of keys that normalize to the same path, like
+    // `a.b` and `a . b`, don't depend on the iteration order of `define`
+    let mut entries = define.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    let values = entries
+      .into_iter()
+      .map(|(key, value)| {
+        let alloc = Allocator::default();
+        let key_path = parse_expression(&alloc, key)
+          .and_then(|expr| member_chain(&expr).map(|(names, _)| names.join(".")))
+          .ok_or_else(|| BuildError::invalid_define_key(key))?;
+        let value = parse_expression(&alloc, value)
+          .and_then(|expr| DefineValue::from_expression(&expr))
+          .ok_or_else(|| BuildError::invalid_define_value(key, value))?;
+        Ok((key_path, value))
+      })
+      .collect::<Result<_, BuildError>>()?;
+    Ok(Self { values })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  /// Replaces the expressions of the module that match a key of `define` and aren't shadowed by a local
+  /// binding. The semantic information of `ast` needs to be rebuilt afterwards.
+  pub fn replace(&self, ast: &mut OxcProgram, source_type: SourceType) {
+    let semantic = ast.make_semantic(source_type);
+    let unresolved_references = semantic
+      .symbols()
+      .references
+      .iter_enumerated()
+      .filter(|(_, reference)| reference.symbol_id().is_none())
+      .map(|(reference_id, _)| reference_id)
+      .collect::<FxHashSet<_>>();
+    drop(semantic);
+
+    let (program, alloc) = ast.program_mut_and_allocator();
+    let mut replacer =
+      DefineReplacer { defines: self, unresolved_references, snippet: AstSnippet::new(alloc) };
+    replacer.visit_program(program);
+  }
+
+  fn find(
+    &self,
+    expr: &Expression,
+    unresolved_references: &FxHashSet<ReferenceId>,
+  ) -> Option<&DefineValue> {
+    let (names, root) = member_chain(expr)?;
+    let is_global = root.map_or(true, |ident| {
+      ident
+        .reference_id
+        .get()
+        .is_some_and(|reference_id| unresolved_references.contains(&reference_id))
+    });
+    if is_global {
+      self.values.get(&names.join("."))
+    } else {
+      None
+    }
+  }
+}
+
+impl DefineValue {
+  fn from_expression(expr: &Expression) -> Option<Self> {
+    match expr {
+      Expression::StringLiteral(lit) => Some(Self::String(lit.value.to_string())),
+      Expression::NumericLiteral(lit) => Some(Self::Number(lit.value)),
+      Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::UnaryNegation => {
+        match &unary.argument {
+          Expression::NumericLiteral(lit) => Some(Self::Number(-lit.value)),
+          _ => None,
+        }
+      }
+      Expression::BooleanLiteral(lit) => Some(Self::Boolean(lit.value)),
+      Expression::NullLiteral(_) => Some(Self::Null),
+      _ => member_chain(expr)
+        .map(|(names, _)| Self::Reference(names.into_iter().map(str::to_string).collect())),
+    }
+  }
+
+  /// Literals keep the span of the replaced expression, so source maps point to it.
+  fn to_expression<'ast>(&self, snippet: &AstSnippet<'ast>, span: Span) -> Expression<'ast> {
+    match self {
+      Self::String(value) => snippet.string_literal_expr(value, span),
+      Self::Number(value) if value.is_sign_negative() => Expression::UnaryExpression(
+        ast::UnaryExpression {
+          span,
+          operator: UnaryOperator::UnaryNegation,
+          argument: snippet.number_expr(-value),
+        }
+        .into_in(snippet.alloc),
+      ),
+      Self::Number(value) => snippet.number_expr(*value),
+      Self::Boolean(value) => Expression::BooleanLiteral(
+        ast::BooleanLiteral { span, value: *value }.into_in(snippet.alloc),
+      ),
+      Self::Null => Expression::NullLiteral(ast::NullLiteral { span }.into_in(snippet.alloc)),
+      // Identifiers get an empty span, since they don't have the name of the replaced expression.
+      Self::Reference(names) => {
+        let (mut expr, properties) =
+          if names.len() >= 2 && names[0] == "import" && names[1] == "meta" {
+            let meta = ast::MetaProperty {
+              span: SPAN,
+              meta: snippet.id_name("import", SPAN),
+              property: snippet.id_name("meta", SPAN),
+            };
+            (Expression::MetaProperty(meta.into_in(snippet.alloc)), &names[2..])
+          } else {
+            (snippet.id_ref_expr(&names[0], SPAN), &names[1..])
+          };
+        for property in properties {
+          expr = Expression::MemberExpression(
+            MemberExpression::StaticMemberExpression(ast::StaticMemberExpression {
+              span: SPAN,
+              object: expr,
+              property: snippet.id_name(property, SPAN),
+              optional: false,
+            })
+            .into_in(snippet.alloc),
+          );
+        }
+        expr
+      }
+    }
+  }
+}
+
+/// Parses `source` as a single expression.
+fn parse_expression<'a>(alloc: &'a Allocator, source: &str) -> Option<Expression<'a>> {
+  // Wrap it in parentheses, so that a string isn't parsed as a directive.
+  let source = alloc.alloc(format!("({source})"));
+  let mut ret = Parser::new(alloc, source, SourceType::default()).parse();
+  if !ret.errors.is_empty() || ret.program.body.len() != 1 {
+    return None;
+  }
+  match ret.program.body.pop()? {
+    ast::Statement::ExpressionStatement(stmt) => match stmt.unbox().expression {
+      Expression::ParenthesizedExpression(paren) => Some(paren.unbox().expression),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+/// The names of `a.b.c` or `import.meta.a`, and the identifier `a.b.c` starts with.
+pub fn member_chain<'e, 'ast>(
+  mut expr: &'e Expression<'ast>,
+) -> Option<(Vec<&'e str>, Option<&'e IdentifierReference<'ast>>)> {
+  let mut names = vec![];
+  loop {
+    match expr {
+      Expression::MemberExpression(member_expr) => match &**member_expr {
+        MemberExpression::StaticMemberExpression(member_expr) if !member_expr.optional => {
+          names.push(member_expr.property.name.as_str());
+          expr = &member_expr.object;
+        }
+        _ => return None,
+      },
+      Expression::Identifier(ident) => {
+        names.push(ident.name.as_str());
+        names.reverse();
+        return Some((names, Some(ident)));
+      }
+      Expression::MetaProperty(meta)
+        if meta.meta.name == "import" && meta.property.name == "meta" =>
+      {
+        names.extend(["meta", "import"]);
+        names.reverse();
+        return Some((names, None));
+      }
+      _ => return None,
+    }
+  }
+}
+
+struct DefineReplacer<'a, 'ast> {
+  defines: &'a Defines,
+  unresolved_references: FxHashSet<ReferenceId>,
+  snippet: AstSnippet<'ast>,
+}
+
+impl<'a, 'ast> VisitMut<'ast> for DefineReplacer<'a, 'ast> {
+  fn visit_expression(&mut self, expr: &mut Expression<'ast>) {
+    if let Some(value) = self.defines.find(expr, &self.unresolved_references) {
+      *expr = value.to_expression(&self.snippet, expr.span());
+    } else {
+      walk_expression_mut(self, expr);
+    }
+  }
+
+  fn visit_object_property(&mut self, prop: &mut ast::ObjectProperty<'ast>) {
+    // `{ __DEV__ }` needs to become `{ __DEV__: true }`
+    if prop.shorthand && self.defines.find(&prop.value, &self.unresolved_references).is_some() {
+      prop.shorthand = false;
+    }
+    walk_object_property_mut(self, prop);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new_reports_the_first_invalid_key() {
+    let define = ["c-", "a-", "b-"]
+      .into_iter()
+      .map(|key| (key.to_string(), "true".to_string()))
+      .collect::<HashMap<_, _>>();
+    let err = Defines::new(&define).expect_err("should be invalid");
+    assert!(err.to_string().contains("\"a-\""), "{err}");
+  }
+
+  #[test]
+  fn new_keeps_the_last_of_equivalent_keys() {
+    let define = [("a . b", "'first'"), ("a.b", "'second'")]
+      .into_iter()
+      .map(|(key, value)| (key.to_string(), value.to_string()))
+      .collect::<HashMap<_, _>>();
+    let defines = Defines::new(&define).expect("should be valid");
+    assert!(
+      matches!(defines.values.get("a.b"), Some(DefineValue::String(value)) if value == "second")
+    );
+  }
+}
@@ -0,0 +1,39 @@
+use oxc::syntax::identifier::is_identifier_name;
+use rustc_hash::FxHashSet;
+
+use super::reserved_names::RESERVED_NAMES;
+
+/// Render `obj.prop` or `obj["prop"]` depending on whether `prop` is a valid identifier.
+pub fn property_access_str(obj: &str, prop: &str) -> String {
+  if is_identifier_name(prop) {
+    format!("{obj}.{prop}")
+  } else {
+    format!("{obj}[{}]", string_literal_str(prop))
+  }
+}
+
+/// Render `prop` as the key of an object literal, quoting it if it isn't a valid identifier.
+pub fn property_key_str(prop: &str) -> String {
+  if is_identifier_name(prop) {
+    prop.to_string()
+  } else {
+    string_literal_str(prop)
+  }
+}
+
+/// Render `value` as a double-quoted string literal, escaping quotes, backslashes and control characters.
+pub fn string_literal_str(value: &str) -> String {
+  serde_json::to_string(value).expect("should be valid json string")
+}
+
+/// Find a name based on `base` that isn't in `used_names`, and mark it as used.
+pub fn generate_unique_name(base: &str, used_names: &mut FxHashSet<String>) -> String {
+  let mut name = base.to_string();
+  let mut count = 0;
+  while used_names.contains(&name) || RESERVED_NAMES.contains(&name.as_str()) {
+    count += 1;
+    name = format!("{base}${count}");
+  }
+  used_names.insert(name.clone());
+  name
+}
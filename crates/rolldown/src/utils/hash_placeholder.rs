@@ -0,0 +1,144 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rolldown_utils::xxhash::xxhash_base64_url;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::chunk::ChunkRenderReturn;
+
+// Content hashes are only known after every chunk is rendered, but chunks reference each other by file
+// name. Until then, `[hash]` patterns are rendered as placeholders like `!~{00}~`, which are replaced once
+// all hashes are computed.
+
+const CHARS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+const PLACEHOLDER_PREFIX: &str = "!~{";
+const PLACEHOLDER_SUFFIX: &str = "}~";
+
+static PLACEHOLDER_RE: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"!~\{[0-9a-zA-Z_$]+\}~").expect("Should be a valid regex"));
+
+#[derive(Debug, Default)]
+pub struct HashPlaceholderGenerator {
+  next_index: usize,
+}
+
+impl HashPlaceholderGenerator {
+  /// Generates a unique placeholder of `hash_len` characters. Returns `None` once the available indexes
+  /// for that length run out, since a longer placeholder would produce a longer hash than requested.
+  pub fn generate(&mut self, hash_len: usize) -> Option<String> {
+    let index = to_digits(self.next_index);
+    let padding =
+      hash_len.checked_sub(PLACEHOLDER_PREFIX.len() + PLACEHOLDER_SUFFIX.len() + index.len())?;
+    self.next_index += 1;
+    Some(format!("{PLACEHOLDER_PREFIX}{}{index}{PLACEHOLDER_SUFFIX}", "0".repeat(padding)))
+  }
+
+  /// The shortest hash length that leaves room for the placeholders of `count` chunks.
+  pub fn min_hash_len(count: usize) -> usize {
+    PLACEHOLDER_PREFIX.len() + PLACEHOLDER_SUFFIX.len() + to_digits(count.saturating_sub(1)).len()
+  }
+}
+
+fn to_digits(mut index: usize) -> String {
+  let mut digits = vec![];
+  loop {
+    digits.push(CHARS[index % CHARS.len()]);
+    index /= CHARS.len();
+    if index == 0 {
+      break;
+    }
+  }
+  digits.reverse();
+  String::from_utf8(digits).expect("Should be ascii")
+}
+
+/// Replaces the hash placeholders in the file names and code of `chunks` with content hashes.
+///
+/// The hash of a chunk covers its own code and the code of every chunk it transitively references
+/// through a placeholder, so a change in an imported chunk also changes the hash of its importers.
+pub fn replace_hash_placeholders(chunks: &mut [ChunkRenderReturn]) {
+  let placeholder_to_chunk = chunks
+    .iter()
+    .enumerate()
+    .filter_map(|(idx, chunk)| {
+      PLACEHOLDER_RE.find(&chunk.rendered_chunk.file_name).map(|m| (m.as_str().to_string(), idx))
+    })
+    .collect::<FxHashMap<_, _>>();
+
+  if placeholder_to_chunk.is_empty() {
+    return;
+  }
+
+  // Placeholders are numbered in chunk order, so they are hashed as a fixed string. Otherwise, adding an
+  // unrelated chunk would change the hashes of the chunks after it. Dependencies are covered below.
+  let content_hashes = chunks
+    .iter()
+    .map(|chunk| {
+      let code = PLACEHOLDER_RE
+        .replace_all(&chunk.code, |captures: &regex::Captures| "0".repeat(captures[0].len()));
+      xxhash_base64_url(code.as_bytes())
+    })
+    .collect::<Vec<_>>();
+  let dependencies = chunks
+    .iter()
+    .map(|chunk| {
+      PLACEHOLDER_RE
+        .find_iter(&chunk.code)
+        .filter_map(|m| placeholder_to_chunk.get(m.as_str()).copied())
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  let final_hashes = placeholder_to_chunk
+    .iter()
+    .map(|(placeholder, &idx)| {
+      let mut visited = FxHashSet::default();
+      let mut stack = vec![idx];
+      while let Some(idx) = stack.pop() {
+        if visited.insert(idx) {
+          stack.extend(dependencies[idx].iter().copied());
+        }
+      }
+      // Sorted by content rather than chunk index, which isn't stable across builds either
+      let mut hashes =
+        visited.into_iter().map(|idx| content_hashes[idx].as_str()).collect::<Vec<_>>();
+      hashes.sort_unstable();
+      let combined = hashes.concat();
+      let hash = xxhash_base64_url(combined.as_bytes());
+      (placeholder.as_str(), hash[..placeholder.len().min(hash.len())].to_string())
+    })
+    .collect::<FxHashMap<_, _>>();
+
+  let replace = |source: &str| {
+    PLACEHOLDER_RE
+      .replace_all(source, |captures: &regex::Captures| {
+        final_hashes.get(&captures[0]).cloned().unwrap_or_else(|| captures[0].to_string())
+      })
+      .into_owned()
+  };
+
+  for chunk in chunks.iter_mut() {
+    chunk.code = replace(&chunk.code);
+    chunk.rendered_chunk.file_name = replace(&chunk.rendered_chunk.file_name);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn generate() {
+    let mut generator = HashPlaceholderGenerator::default();
+    assert_eq!(generator.generate(8).as_deref(), Some("!~{000}~"));
+    (1..CHARS.len()).for_each(|_| assert_eq!(generator.generate(6).map(|p| p.len()), Some(6)));
+    assert_eq!(generator.generate(6), None);
+    assert_eq!(generator.generate(7).as_deref(), Some("!~{10}~"));
+  }
+
+  #[test]
+  fn min_hash_len() {
+    assert_eq!(HashPlaceholderGenerator::min_hash_len(1), 6);
+    assert_eq!(HashPlaceholderGenerator::min_hash_len(64), 6);
+    assert_eq!(HashPlaceholderGenerator::min_hash_len(65), 7);
+  }
+}
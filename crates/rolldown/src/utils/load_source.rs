@@ -10,12 +10,16 @@ pub async fn load_source(
   resolved_path: &ResolvedPath,
   fs: &dyn rolldown_fs::FileSystem,
   sourcemap_chain: &mut Vec<SourceMap>,
+  module_side_effects: &mut Option<bool>,
 ) -> Result<String, BatchedErrors> {
   let source =
     if let Some(r) = plugin_driver.load(&HookLoadArgs { id: &resolved_path.path }).await? {
       if let Some(map) = r.map {
         sourcemap_chain.push(map);
       }
+      if r.module_side_effects.is_some() {
+        *module_side_effects = r.module_side_effects;
+      }
       r.code
     } else if resolved_path.ignored {
       String::new()
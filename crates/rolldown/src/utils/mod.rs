@@ -1,9 +1,17 @@
-use oxc::ast::VisitMut;
+use oxc::{
+  ast::{ast, VisitMut},
+  minifier::{CompressOptions, Compressor},
+};
 use rolldown_common::NormalModule;
 use rolldown_oxc_utils::{AstSnippet, OxcProgram};
 
 use super::finalizer::{Finalizer, FinalizerContext};
+use crate::OutputFormat;
 
+pub mod dead_branch;
+pub mod define;
+pub mod ecma_script;
+pub mod hash_placeholder;
 pub mod load_source;
 pub mod normalize_options;
 pub mod renamer;
@@ -11,6 +19,7 @@ pub mod render_chunks;
 pub mod render_normal_module;
 pub mod reserved_names;
 pub mod resolve_id;
+pub mod static_value;
 pub mod transform_source;
 
 pub(crate) fn is_in_rust_test_mode() -> bool {
@@ -24,10 +33,37 @@ pub fn finalize_normal_module(
   ctx: FinalizerContext<'_>,
   ast: &mut OxcProgram,
 ) {
+  let minify_syntax = ctx.output_options.minify.syntax;
+  let is_system = matches!(ctx.output_options.format, OutputFormat::System);
   let (oxc_program, alloc) = ast.program_mut_and_allocator();
 
   let mut finalizer =
     Finalizer { alloc, ctx, scope: &module.scope, snippet: &AstSnippet::new(alloc) };
 
   finalizer.visit_program(oxc_program);
+
+  if minify_syntax {
+    // `typeof x == "undefined"` can't become `x === void 0`, which throws if `x` is an undeclared global.
+    let options = CompressOptions { typeofs: false, ..CompressOptions::default() };
+    Compressor::new(alloc, options).build(oxc_program);
+  }
+
+  if is_system {
+    // The finalizer moved the declarations to the top of the module. They are rendered in the
+    // `System.register` callback, outside `execute`.
+    let hoisted_len = ast
+      .program()
+      .body
+      .iter()
+      .take_while(|stmt| {
+        matches!(
+          stmt,
+          ast::Statement::Declaration(
+            ast::Declaration::FunctionDeclaration(_) | ast::Declaration::VariableDeclaration(_)
+          )
+        )
+      })
+      .count();
+    ast.split_off_hoisted_statements(hoisted_len);
+  }
 }
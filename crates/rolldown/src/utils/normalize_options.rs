@@ -1,8 +1,9 @@
 use rolldown_resolver::EnforceExtension;
+use sugar_path::SugarPath;
 
 use crate::options::{
   normalized_input_options::NormalizedInputOptions,
-  normalized_output_options::NormalizedOutputOptions, output_options::SourceMapType,
+  normalized_output_options::NormalizedOutputOptions, types::treeshake_options::TreeshakeOptions,
 };
 
 #[allow(clippy::struct_field_names)]
@@ -61,28 +62,58 @@ pub fn normalize_options(
 
   // Normalize input options
 
+  let (treeshake, module_side_effects, unknown_global_side_effects) =
+    match raw_input.treeshake.unwrap_or_default() {
+      TreeshakeOptions::Boolean(value) => (value, None, true),
+      TreeshakeOptions::Options(options) => {
+        (true, options.module_side_effects, options.unknown_global_side_effects.unwrap_or(true))
+      }
+    };
+
   let input_options = NormalizedInputOptions {
     input: raw_input.input,
     cwd: raw_input
       .cwd
       .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current dir")),
     external: raw_input.external.unwrap_or_default(),
-    treeshake: raw_input.treeshake.unwrap_or(true),
+    treeshake,
+    module_side_effects,
+    unknown_global_side_effects,
+    define: raw_input.define.unwrap_or_default(),
   };
 
   // Normalize output options
 
   let output_options: NormalizedOutputOptions = NormalizedOutputOptions {
-    entry_file_names: raw_output.entry_file_names.unwrap_or_else(|| "[name].js".to_string()).into(),
+    entry_file_names: raw_output.entry_file_names.unwrap_or_else(|| "[name].js".to_string().into()),
     chunk_file_names: raw_output
       .chunk_file_names
-      .unwrap_or_else(|| "[name]-[hash].js".to_string())
-      .into(),
+      .unwrap_or_else(|| "[name]-[hash].js".to_string().into()),
+    asset_file_names: raw_output
+      .asset_file_names
+      .unwrap_or_else(|| "assets/[name]-[hash][extname]".to_string()),
     banner: raw_output.banner.unwrap_or_default(),
     footer: raw_output.footer.unwrap_or_default(),
     dir: raw_output.dir.unwrap_or_else(|| "dist".to_string()),
     format: raw_output.format.unwrap_or(crate::OutputFormat::Esm),
-    sourcemap: raw_output.sourcemap.unwrap_or(SourceMapType::Hidden),
+    sourcemap: raw_output.sourcemap,
+    sourcemap_file: raw_output.sourcemap_file,
+    sourcemap_ignore_list: raw_output.sourcemap_ignore_list.unwrap_or_default(),
+    sourcemap_path_transform: raw_output.sourcemap_path_transform,
+    sourcemap_exclude_sources: raw_output.sourcemap_exclude_sources.unwrap_or(false),
+    sourcemap_debug_ids: raw_output.sourcemap_debug_ids.unwrap_or(false),
+    name: raw_output.name,
+    globals: raw_output.globals.unwrap_or_default(),
+    amd: raw_output.amd.unwrap_or_default(),
+    manual_chunks: raw_output.manual_chunks,
+    advanced_chunks: raw_output.advanced_chunks,
+    experimental_min_chunk_size: raw_output.experimental_min_chunk_size.unwrap_or(0),
+    preserve_modules: raw_output.preserve_modules.unwrap_or(false),
+    preserve_modules_root: raw_output
+      .preserve_modules_root
+      .map(|root| input_options.cwd.join(root).normalize().into_owned()),
+    inline_dynamic_imports: raw_output.inline_dynamic_imports.unwrap_or(false),
+    minify: raw_output.minify.unwrap_or_default(),
   };
 
   NormalizeOptionsReturn { input_options, output_options, resolve_options }
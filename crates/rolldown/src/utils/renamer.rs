@@ -13,14 +13,19 @@ pub struct Renamer<'name> {
   used_canonical_names: FxHashSet<Cow<'name, Rstr>>,
   canonical_names: FxHashMap<SymbolRef, Rstr>,
   symbols: &'name Symbols,
+  /// Give symbols the shortest available names instead of keeping their original names.
+  mangle: bool,
+  next_mangled_name_index: usize,
 }
 
 impl<'name> Renamer<'name> {
-  pub fn new(symbols: &'name Symbols, _modules_len: usize) -> Self {
+  pub fn new(symbols: &'name Symbols, _modules_len: usize, mangle: bool) -> Self {
     Self {
       canonical_names: FxHashMap::default(),
       symbols,
       used_canonical_names: RESERVED_NAMES.iter().map(|&s| Cow::Owned(s.into())).collect(),
+      mangle,
+      next_mangled_name_index: 0,
     }
   }
 
@@ -28,12 +33,24 @@ impl<'name> Renamer<'name> {
     self.used_canonical_names.insert(name);
   }
 
+  /// In mangle mode, symbols get shorter names the earlier they are added.
   pub fn add_top_level_symbol(&mut self, symbol_ref: SymbolRef) {
     let canonical_ref = self.symbols.par_canonical_ref_for(symbol_ref);
     let original_name: Cow<'_, Rstr> =
       Cow::Owned(self.symbols.get_original_name(canonical_ref).to_rstr());
 
     match self.canonical_names.entry(canonical_ref) {
+      std::collections::hash_map::Entry::Vacant(vacant) if self.mangle => {
+        let candidate_name = loop {
+          let candidate_name = Cow::Owned(mangled_name(self.next_mangled_name_index));
+          self.next_mangled_name_index += 1;
+          if !self.used_canonical_names.contains(&candidate_name) {
+            break candidate_name;
+          }
+        };
+        self.used_canonical_names.insert(candidate_name.clone());
+        vacant.insert(candidate_name.into_owned());
+      }
       std::collections::hash_map::Entry::Vacant(vacant) => {
         let mut count = 0;
         let mut candidate_name = original_name.clone();
@@ -50,7 +67,21 @@ impl<'name> Renamer<'name> {
     }
   }
 
+  /// Create a top-level name, which doesn't belong to a symbol, based on `name`. Used for the parameters of
+  /// the AMD factory.
+  pub fn create_top_level_name(&mut self, name: &str) -> Rstr {
+    let mut count = 0;
+    let mut candidate_name: Cow<'_, Rstr> = Cow::Owned(name.into());
+    while self.used_canonical_names.contains(&candidate_name) {
+      count += 1;
+      candidate_name = Cow::Owned(format!("{name}${count}").into());
+    }
+    self.used_canonical_names.insert(candidate_name.clone());
+    candidate_name.into_owned()
+  }
+
   // non-top-level symbols won't be linked cross-module. So the canonical `SymbolRef` for them are themselves.
+  /// In mangle mode, bindings get the shortest names that don't shadow a name used by an enclosing scope.
   pub fn rename_non_top_level_symbol(
     &mut self,
     modules_in_chunk: &[NormalModuleId],
@@ -63,14 +94,36 @@ impl<'name> Renamer<'name> {
       scope_id: ScopeId,
       stack: &mut Vec<Cow<FxHashSet<Cow<'name, Rstr>>>>,
       canonical_names: &mut FxHashMap<SymbolRef, Rstr>,
+      mangle: bool,
     ) {
       let bindings = module.scope.get_bindings(scope_id);
       let mut used_canonical_names_for_this_scope = FxHashSet::default();
       used_canonical_names_for_this_scope.shrink_to(bindings.len());
+      let mut next_mangled_name_index = 0;
       bindings.iter().for_each(|(binding_name, symbol_id)| {
-        used_canonical_names_for_this_scope.insert(Cow::Owned(binding_name.to_rstr()));
         let binding_ref: SymbolRef = (module.id, *symbol_id).into();
 
+        if mangle {
+          if let std::collections::hash_map::Entry::Vacant(slot) =
+            canonical_names.entry(binding_ref)
+          {
+            let candidate_name = loop {
+              let candidate_name = Cow::Owned(mangled_name(next_mangled_name_index));
+              next_mangled_name_index += 1;
+              if !stack
+                .iter()
+                .any(|used_canonical_names| used_canonical_names.contains(&candidate_name))
+              {
+                break candidate_name;
+              }
+            };
+            used_canonical_names_for_this_scope.insert(candidate_name.clone());
+            slot.insert(candidate_name.into_owned());
+          }
+          return;
+        }
+
+        used_canonical_names_for_this_scope.insert(Cow::Owned(binding_name.to_rstr()));
         let mut count = 1;
         let mut candidate_name = Cow::Owned(binding_name.to_rstr());
         match canonical_names.entry(binding_ref) {
@@ -97,11 +150,12 @@ impl<'name> Renamer<'name> {
       stack.push(Cow::Owned(used_canonical_names_for_this_scope));
       let child_scopes = module.scope.get_child_ids(scope_id).cloned().unwrap_or_default();
       child_scopes.into_iter().for_each(|scope_id| {
-        rename_symbols_of_nested_scopes(module, scope_id, stack, canonical_names);
+        rename_symbols_of_nested_scopes(module, scope_id, stack, canonical_names, mangle);
       });
       stack.pop();
     }
 
+    let mangle = self.mangle;
     let canonical_names_of_nested_scopes = modules_in_chunk
       .par_iter()
       .copied()
@@ -118,6 +172,7 @@ impl<'name> Renamer<'name> {
             *child_scope_id,
             &mut stack,
             &mut canonical_names,
+            mangle,
           );
           canonical_names
         })
@@ -134,3 +189,18 @@ impl<'name> Renamer<'name> {
     self.canonical_names
   }
 }
+
+/// The `index`-th shortest identifier: `a`, `b`, ..., `$`, `_`, `aa`, `ba`, ...
+fn mangled_name(mut index: usize) -> Rstr {
+  const HEAD: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+  const TAIL: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+  let mut name = String::new();
+  name.push(HEAD[index % HEAD.len()] as char);
+  index /= HEAD.len();
+  while index > 0 {
+    index -= 1;
+    name.push(TAIL[index % TAIL.len()] as char);
+    index /= TAIL.len();
+  }
+  name.into()
+}
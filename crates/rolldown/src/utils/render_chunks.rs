@@ -1,5 +1,6 @@
 use rolldown_common::IntoBatchedResult;
 use rolldown_plugin::{HookRenderChunkArgs, SharedPluginDriver};
+use rolldown_sourcemap::collapse_sourcemaps;
 use rolldown_utils::block_on_spawn_all;
 
 use crate::{chunk::ChunkRenderReturn, error::BatchedErrors};
@@ -8,18 +9,22 @@ pub async fn render_chunks<'a>(
   plugin_driver: &SharedPluginDriver,
   chunks: Vec<ChunkRenderReturn>,
 ) -> Result<Vec<ChunkRenderReturn>, BatchedErrors> {
-  // TODO support `render_chunk` hook return map
   let result = block_on_spawn_all(chunks.into_iter().map(|chunk| async move {
     tracing::info!("render_chunks");
-    match plugin_driver
+    let (code, sourcemap_chain) = plugin_driver
       .render_chunk(HookRenderChunkArgs { code: chunk.code, chunk: &chunk.rendered_chunk })
-      .await
-    {
-      Ok(code) => {
-        Ok(ChunkRenderReturn { code, map: chunk.map, rendered_chunk: chunk.rendered_chunk })
+      .await?;
+    // Maps returned by plugins describe how they transformed the chunk, so they're collapsed on top of the
+    // map of the chunk itself.
+    let map = match chunk.map {
+      Some(map) if !sourcemap_chain.is_empty() => {
+        let mut maps = vec![&map];
+        maps.extend(sourcemap_chain.iter());
+        collapse_sourcemaps(maps)?
       }
-      Err(e) => Err(e),
-    }
+      map => map,
+    };
+    Ok(ChunkRenderReturn { code, map, rendered_chunk: chunk.rendered_chunk })
   }));
 
   result.into_batched_result()
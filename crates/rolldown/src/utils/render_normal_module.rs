@@ -1,5 +1,11 @@
-use oxc::codegen::CodegenReturn;
+use oxc::{
+  ast::{ast, Visit},
+  codegen::CodegenReturn,
+  span::Span,
+};
 use rolldown_oxc_utils::{OxcCompiler, OxcProgram};
+use rolldown_sourcemap::fill_token_names;
+use rustc_hash::FxHashMap;
 
 use crate::types::module_render_context::ModuleRenderContext;
 
@@ -8,10 +14,122 @@ pub fn render_normal_module(
   ast: &OxcProgram,
   source_name: &str,
   enable_sourcemap: bool,
+  minify_whitespace: bool,
 ) -> Option<CodegenReturn> {
   if ast.program().body.is_empty() {
     None
   } else {
-    Some(OxcCompiler::print(ast, source_name, enable_sourcemap))
+    let ret = if minify_whitespace {
+      OxcCompiler::print_minified(ast, source_name, enable_sourcemap)
+    } else {
+      OxcCompiler::print(ast, source_name, enable_sourcemap)
+    };
+    let renamed = enable_sourcemap.then(|| RenamedIdentifiers::collect(ast));
+    Some(fill_renamed_identifiers(renamed.as_ref(), ret))
+  }
+}
+
+/// Render the declarations that the finalizer hoisted out of the module for `OutputFormat::System`, see
+/// `OxcProgram::split_off_hoisted_statements`.
+pub fn render_hoisted_declarations(
+  _ctx: &ModuleRenderContext<'_>,
+  ast: &OxcProgram,
+  source_name: &str,
+  enable_sourcemap: bool,
+  minify_whitespace: bool,
+) -> Option<CodegenReturn> {
+  let ret = if minify_whitespace {
+    OxcCompiler::print_hoisted_minified(ast, source_name, enable_sourcemap)
+  } else {
+    OxcCompiler::print_hoisted(ast, source_name, enable_sourcemap)
+  }?;
+  let renamed = enable_sourcemap.then(|| RenamedIdentifiers::collect(ast));
+  Some(fill_renamed_identifiers(renamed.as_ref(), ret))
+}
+
+fn fill_renamed_identifiers(
+  renamed: Option<&RenamedIdentifiers>,
+  mut ret: CodegenReturn,
+) -> CodegenReturn {
+  if let (Some(map), Some(renamed)) = (&ret.source_map, renamed) {
+    ret.source_map = Some(fill_token_names(map, |line, col| renamed.0.get(&(line, col)).cloned()));
+  }
+  ret
+}
+
+/// Original names of the identifiers that were renamed by the finalizer, keyed by their original line and
+/// UTF-16 column.
+///
+/// The printer records the original name of a renamed identifier only if no other token starts at the same
+/// position. For example, the token of the call expression `foo$1()` hides the one of `foo$1`. The names of
+/// these tokens are filled in afterwards, so devtools show the names of the source.
+struct RenamedIdentifiers(FxHashMap<(u32, u32), String>);
+
+impl RenamedIdentifiers {
+  fn collect(ast: &OxcProgram) -> Self {
+    let source = ast.source();
+    let mut collector = RenamedIdentifiersCollector { source, spans: vec![] };
+    collector.visit_program(ast.program());
+    if let Some(hoisted_program) = ast.hoisted_program() {
+      collector.visit_program(hoisted_program);
+    }
+    let line_starts = line_starts(source);
+    let names = collector
+      .spans
+      .into_iter()
+      .map(|span| {
+        let start = span.start as usize;
+        let line = line_starts.partition_point(|line_start| *line_start <= start) - 1;
+        let col = source[line_starts[line]..start].encode_utf16().count();
+        let position = (u32::try_from(line).unwrap(), u32::try_from(col).unwrap());
+        (position, source[start..span.end as usize].to_string())
+      })
+      .collect();
+    Self(names)
+  }
+}
+
+/// Byte offsets of the lines of `source`, using the line terminators of ECMAScript.
+fn line_starts(source: &str) -> Vec<usize> {
+  let mut line_starts = vec![0];
+  let mut chars = source.char_indices().peekable();
+  while let Some((offset, char)) = chars.next() {
+    match char {
+      '\r' if matches!(chars.peek(), Some((_, '\n'))) => {}
+      '\n' | '\r' | '\u{2028}' | '\u{2029}' => line_starts.push(offset + char.len_utf8()),
+      _ => {}
+    }
+  }
+  line_starts
+}
+
+struct RenamedIdentifiersCollector<'a> {
+  source: &'a str,
+  spans: Vec<Span>,
+}
+
+impl<'a> RenamedIdentifiersCollector<'a> {
+  fn add(&mut self, name: &str, span: Span) {
+    // Identifiers created by the finalizer have an empty span.
+    if span.start == span.end {
+      return;
+    }
+    if self
+      .source
+      .get(span.start as usize..span.end as usize)
+      .is_some_and(|original| original != name)
+    {
+      self.spans.push(span);
+    }
+  }
+}
+
+impl<'a, 'ast> Visit<'ast> for RenamedIdentifiersCollector<'a> {
+  fn visit_identifier_reference(&mut self, ident: &ast::IdentifierReference<'ast>) {
+    self.add(&ident.name, ident.span);
+  }
+
+  fn visit_binding_identifier(&mut self, ident: &ast::BindingIdentifier<'ast>) {
+    self.add(&ident.name, ident.span);
   }
 }
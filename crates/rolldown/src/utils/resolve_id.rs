@@ -35,6 +35,8 @@ pub async fn resolve_id(
       path: r.id.into(),
       module_type: ModuleType::Unknown,
       is_external: matches!(r.external, Some(true)),
+      side_effects: r.module_side_effects,
+      package_json_side_effects: None,
     });
   }
 
@@ -44,6 +46,8 @@ pub async fn resolve_id(
       path: request.to_string().into(),
       module_type: ModuleType::Unknown,
       is_external: true,
+      side_effects: None,
+      package_json_side_effects: None,
     });
   }
 
@@ -55,5 +59,7 @@ pub async fn resolve_id(
     path: resolved.resolved,
     module_type: resolved.module_type,
     is_external: false,
+    side_effects: None,
+    package_json_side_effects: resolved.side_effects,
   })
 }
@@ -0,0 +1,97 @@
+use oxc::{
+  ast::ast::Expression,
+  syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+};
+
+/// A primitive value of an expression that is known at build time, like the `'production'` that
+/// `process.env.NODE_ENV` is replaced with by `define`.
+///
+/// Dead branches are found with this before scanning. The side effect detector also uses it to know that
+/// comparing constants has no side effects.
+#[derive(Debug, PartialEq)]
+pub enum StaticPrimitive {
+  String(String),
+  Number(f64),
+  Boolean(bool),
+  Null,
+}
+
+impl StaticPrimitive {
+  pub fn from_expression(expr: &Expression) -> Option<Self> {
+    match expr {
+      Expression::StringLiteral(lit) => Some(Self::String(lit.value.to_string())),
+      Expression::NumericLiteral(lit) => Some(Self::Number(lit.value)),
+      Expression::BooleanLiteral(lit) => Some(Self::Boolean(lit.value)),
+      Expression::NullLiteral(_) => Some(Self::Null),
+      Expression::ParenthesizedExpression(paren_expr) => {
+        Self::from_expression(&paren_expr.expression)
+      }
+      Expression::UnaryExpression(unary_expr) => match unary_expr.operator {
+        UnaryOperator::LogicalNot => {
+          get_static_boolean(&unary_expr.argument).map(|value| Self::Boolean(!value))
+        }
+        UnaryOperator::UnaryNegation => match Self::from_expression(&unary_expr.argument)? {
+          Self::Number(value) => Some(Self::Number(-value)),
+          _ => None,
+        },
+        _ => None,
+      },
+      Expression::BinaryExpression(binary_expr) => {
+        let left = Self::from_expression(&binary_expr.left)?;
+        let right = Self::from_expression(&binary_expr.right)?;
+        // Loose equality only agrees with strict equality for values of the same type
+        let is_equal = match binary_expr.operator {
+          BinaryOperator::StrictEquality | BinaryOperator::StrictInequality => left == right,
+          BinaryOperator::Equality | BinaryOperator::Inequality
+            if std::mem::discriminant(&left) == std::mem::discriminant(&right) =>
+          {
+            left == right
+          }
+          _ => return None,
+        };
+        let is_negated = matches!(
+          binary_expr.operator,
+          BinaryOperator::StrictInequality | BinaryOperator::Inequality
+        );
+        Some(Self::Boolean(is_equal != is_negated))
+      }
+      Expression::LogicalExpression(logic_expr) => {
+        let left = Self::from_expression(&logic_expr.left)?;
+        if left.short_circuits(logic_expr.operator) {
+          Some(left)
+        } else {
+          Self::from_expression(&logic_expr.right)
+        }
+      }
+      _ => None,
+    }
+  }
+
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      Self::String(value) => !value.is_empty(),
+      Self::Number(value) => *value != 0.0 && !value.is_nan(),
+      Self::Boolean(value) => *value,
+      Self::Null => false,
+    }
+  }
+
+  /// Whether `self <operator> right` evaluates to `self` without evaluating `right`.
+  pub fn short_circuits(&self, operator: LogicalOperator) -> bool {
+    match operator {
+      LogicalOperator::And => !self.is_truthy(),
+      LogicalOperator::Or => self.is_truthy(),
+      LogicalOperator::Coalesce => !matches!(self, Self::Null),
+    }
+  }
+}
+
+/// Whether `expr` is always truthy or always falsy.
+pub fn get_static_boolean(expr: &Expression) -> Option<bool> {
+  StaticPrimitive::from_expression(expr).map(|value| value.is_truthy())
+}
+
+/// Whether the right side of the logical expression never runs.
+pub fn is_short_circuited(left: &Expression, operator: LogicalOperator) -> bool {
+  StaticPrimitive::from_expression(left).is_some_and(|left| left.short_circuits(operator))
+}
@@ -9,9 +9,11 @@ pub async fn transform_source(
   resolved_path: &ResolvedPath,
   source: String,
   sourcemap_chain: &mut Vec<SourceMap>,
+  module_side_effects: &mut Option<bool>,
 ) -> Result<String, BatchedErrors> {
-  let (code, map_chain) =
-    plugin_driver.transform(&HookTransformArgs { id: &resolved_path.path, code: &source }).await?;
+  let (code, map_chain) = plugin_driver
+    .transform(&HookTransformArgs { id: &resolved_path.path, code: &source }, module_side_effects)
+    .await?;
 
   sourcemap_chain.extend(map_chain);
 
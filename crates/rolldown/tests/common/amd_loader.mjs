@@ -0,0 +1,41 @@
+// A minimal AMD loader to execute the outputs of `format: "amd"` in fixtures. Only named modules, like the
+// ones generated with `amd.autoId`, are supported. Module ids are resolved against the working directory,
+// which is the `dist` folder of the fixture.
+import path from 'node:path'
+import { pathToFileURL } from 'node:url'
+
+const modules = new Map()
+const resolvers = new Map()
+
+function resolveId(id, importerId) {
+  return id.startsWith('.') ? path.posix.join(path.posix.dirname(importerId), id) : id
+}
+
+function load(id) {
+  if (!modules.has(id)) {
+    modules.set(id, new Promise((resolve) => resolvers.set(id, resolve)))
+    import(pathToFileURL(path.resolve(id)).href)
+  }
+  return modules.get(id)
+}
+
+globalThis.define = (id, deps, factory) => {
+  if (typeof id !== 'string') {
+    throw new Error('Anonymous AMD modules are not supported')
+  }
+  const exports = {}
+  const require = (ids, callback) =>
+    Promise.all(ids.map((dep) => load(resolveId(dep, id)))).then((deps) => callback(...deps))
+  const module = Promise.all(
+    deps.map((dep) => {
+      if (dep === 'exports') return exports
+      if (dep === 'require') return require
+      return load(resolveId(dep, id))
+    }),
+  ).then((args) => factory(...args) ?? exports)
+  if (resolvers.has(id)) {
+    resolvers.get(id)(module)
+  } else {
+    modules.set(id, module)
+  }
+}
@@ -0,0 +1,27 @@
+use std::borrow::Cow;
+
+use rolldown_plugin::{EmittedAsset, HookNoopReturn, Plugin, SharedPluginContext};
+
+/// Emits the `emittedAssets` of the test config at the start of the build.
+#[derive(Debug)]
+pub struct EmitAssetsPlugin {
+  pub assets: Vec<rolldown_testing::EmittedAsset>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for EmitAssetsPlugin {
+  fn name(&self) -> Cow<'static, str> {
+    "emit-assets".into()
+  }
+
+  async fn build_start(&self, ctx: &SharedPluginContext) -> HookNoopReturn {
+    self.assets.iter().for_each(|asset| {
+      ctx.emit_file(EmittedAsset {
+        name: asset.name.clone(),
+        file_name: asset.file_name.clone(),
+        source: asset.source.clone(),
+      });
+    });
+    Ok(())
+  }
+}
@@ -4,9 +4,13 @@ use std::{
   process::Command,
 };
 
-use rolldown::{Bundler, External, InputOptions, OutputOptions, RolldownOutput};
+use rolldown::{AddonOutputOption, Bundler, External, InputOptions, OutputOptions, RolldownOutput};
 use rolldown_error::BuildError;
-use rolldown_testing::TestConfig;
+use rolldown_testing::{ModuleSideEffects, TestConfig, TreeshakeOptions};
+
+use super::{
+  emit_assets_plugin::EmitAssetsPlugin, load_side_effects_plugin::LoadSideEffectsPlugin,
+};
 
 fn default_test_input_item() -> rolldown_testing::InputItem {
   rolldown_testing::InputItem { name: "main".to_string(), import: "./main.js".to_string() }
@@ -16,6 +20,21 @@ pub struct Fixture {
   fixture_path: PathBuf,
 }
 
+fn to_treeshake_options(treeshake: TreeshakeOptions) -> rolldown::TreeshakeOptions {
+  match treeshake {
+    TreeshakeOptions::Boolean(value) => value.into(),
+    TreeshakeOptions::Options(options) => {
+      rolldown::TreeshakeOptions::Options(rolldown::InnerTreeshakeOptions {
+        module_side_effects: options.module_side_effects.map(|value| match value {
+          ModuleSideEffects::Boolean(value) => rolldown::ModuleSideEffects::Boolean(value),
+          ModuleSideEffects::IdList(ids) => rolldown::ModuleSideEffects::IdList(ids),
+        }),
+        unknown_global_side_effects: options.unknown_global_side_effects,
+      })
+    }
+  }
+}
+
 impl Fixture {
   pub fn new(fixture_path: PathBuf) -> Self {
     Self { fixture_path }
@@ -59,14 +78,16 @@ impl Fixture {
       .collect::<Vec<_>>();
 
     let mut command = Command::new("node");
+    if test_config.output.format == "amd" {
+      // AMD modules are defined by calling the global `define`, which node doesn't provide
+      command.arg("--import");
+      command
+        .arg(file_url(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/common/amd_loader.mjs")));
+      command.current_dir(&dist_folder);
+    }
     compiled_entries.iter().for_each(|entry| {
       command.arg("--import");
-      if cfg!(target_os = "windows") {
-        // Only URLs with a scheme in: file, data, and node are supported by the default ESM loader. On Windows, absolute paths must be valid file:// URLs.
-        command.arg(format!("file://{}", entry.to_str().expect("should be valid utf8")));
-      } else {
-        command.arg(entry);
-      }
+      command.arg(file_url(entry));
     });
 
     if test_script.exists() {
@@ -97,7 +118,7 @@ impl Fixture {
       test_config.input.input = Some(vec![default_test_input_item()]);
     }
 
-    let mut bundler = Bundler::new(
+    let mut bundler = Bundler::with_plugins(
       InputOptions {
         input: test_config
           .input
@@ -111,7 +132,7 @@ impl Fixture {
           .unwrap(),
         cwd: Some(fixture_path.to_path_buf()),
         external: Some(test_config.input.external.map(External::ArrayString).unwrap_or_default()),
-        treeshake: Some(test_config.input.treeshake.unwrap_or(true)),
+        treeshake: Some(test_config.input.treeshake.map_or(true.into(), to_treeshake_options)),
         resolve: test_config.input.resolve.map(|value| rolldown::ResolveOptions {
           alias: value.alias.map(|alias| alias.into_iter().collect::<Vec<_>>()),
           alias_fields: value.alias_fields,
@@ -123,12 +144,67 @@ impl Fixture {
           modules: value.modules,
           symlinks: value.symlinks,
         }),
+        define: test_config.input.define,
       },
       OutputOptions {
-        entry_file_names: "[name].mjs".to_string().into(),
-        chunk_file_names: "[name].mjs".to_string().into(),
+        entry_file_names: Some("[name].mjs".to_string().into()),
+        chunk_file_names: Some(
+          test_config.output.chunk_file_names.unwrap_or_else(|| "[name].mjs".to_string()).into(),
+        ),
+        asset_file_names: test_config.output.asset_file_names,
+        format: Some(test_config.output.format.try_into().expect("Invalid output format")),
+        name: test_config.output.name,
+        globals: test_config.output.globals,
+        amd: test_config.output.amd.map(|amd| rolldown::AmdOptions {
+          id: amd.id,
+          auto_id: amd.auto_id,
+          define: amd.define,
+        }),
+        manual_chunks: test_config.output.manual_chunks.map(|manual_chunks| {
+          let mut manual_chunks = manual_chunks.into_iter().collect::<Vec<_>>();
+          manual_chunks.sort_by(|a, b| a.0.cmp(&b.0));
+          rolldown::ManualChunksOption::Object(manual_chunks)
+        }),
+        advanced_chunks: test_config.output.advanced_chunks.map(|advanced_chunks| {
+          rolldown::AdvancedChunksOptions {
+            groups: advanced_chunks
+              .groups
+              .into_iter()
+              .map(|group| rolldown::ChunkGroupOptions {
+                name: group.name,
+                test: group.test.map(|test| regex::Regex::new(&test).expect("Invalid regex")),
+                priority: group.priority,
+                min_size: group.min_size,
+                max_size: group.max_size,
+                min_share_count: group.min_share_count,
+              })
+              .collect(),
+          }
+        }),
+        experimental_min_chunk_size: test_config.output.experimental_min_chunk_size,
+        preserve_modules: test_config.output.preserve_modules,
+        preserve_modules_root: test_config.output.preserve_modules_root,
+        inline_dynamic_imports: test_config.output.inline_dynamic_imports,
+        sourcemap: test_config.output.sourcemap.map(Into::into),
+        sourcemap_file: test_config.output.sourcemap_file,
+        sourcemap_exclude_sources: test_config.output.sourcemap_exclude_sources,
+        sourcemap_debug_ids: test_config.output.sourcemap_debug_ids,
+        banner: test_config.output.banner.map(|banner| AddonOutputOption::String(Some(banner))),
+        footer: test_config.output.footer.map(|footer| AddonOutputOption::String(Some(footer))),
+        minify: test_config.output.minify.map(|minify| rolldown::MinifyOptions {
+          whitespace: minify.whitespace.unwrap_or(false),
+          syntax: minify.syntax.unwrap_or(false),
+          identifiers: minify.identifiers.unwrap_or(false),
+        }),
         ..Default::default()
       },
+      vec![
+        Box::new(EmitAssetsPlugin { assets: test_config.emitted_assets }),
+        Box::new(LoadSideEffectsPlugin {
+          cwd: fixture_path.to_path_buf(),
+          side_effects: test_config.load_side_effects,
+        }),
+      ],
     );
 
     if fixture_path.join("dist").is_dir() {
@@ -139,3 +215,12 @@ impl Fixture {
     Ok(value)
   }
 }
+
+fn file_url(path: &Path) -> String {
+  if cfg!(target_os = "windows") {
+    // Only URLs with a scheme in: file, data, and node are supported by the default ESM loader. On Windows, absolute paths must be valid file:// URLs.
+    format!("file://{}", path.to_str().expect("should be valid utf8"))
+  } else {
+    path.to_str().expect("should be valid utf8").to_string()
+  }
+}
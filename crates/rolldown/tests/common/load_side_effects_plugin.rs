@@ -0,0 +1,30 @@
+use std::{borrow::Cow, collections::HashMap, path::PathBuf};
+
+use rolldown_plugin::{HookLoadArgs, HookLoadOutput, HookLoadReturn, Plugin, SharedPluginContext};
+
+/// Loads the modules listed in `loadSideEffects` of the test config, returning the given `module_side_effects`.
+#[derive(Debug)]
+pub struct LoadSideEffectsPlugin {
+  pub cwd: PathBuf,
+  pub side_effects: HashMap<String, bool>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for LoadSideEffectsPlugin {
+  fn name(&self) -> Cow<'static, str> {
+    "load-side-effects".into()
+  }
+
+  async fn load(&self, _ctx: &SharedPluginContext, args: &HookLoadArgs) -> HookLoadReturn {
+    let Some(side_effects) =
+      self.side_effects.iter().find(|(path, _)| self.cwd.join(path) == PathBuf::from(args.id))
+    else {
+      return Ok(None);
+    };
+    Ok(Some(HookLoadOutput {
+      code: std::fs::read_to_string(args.id).expect("Failed to read the module"),
+      map: None,
+      module_side_effects: Some(*side_effects.1),
+    }))
+  }
+}
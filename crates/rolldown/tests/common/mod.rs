@@ -1,4 +1,6 @@
 mod case;
+mod emit_assets_plugin;
 mod fixture;
+mod load_side_effects_plugin;
 
 pub use case::Case;
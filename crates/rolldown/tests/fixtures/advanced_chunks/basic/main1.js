@@ -0,0 +1,4 @@
+import { a } from './node_modules/a/index.js'
+import { shared } from './shared.js'
+
+console.log(a, shared)
@@ -0,0 +1,5 @@
+import { b } from './node_modules/b/index.js'
+import { shared } from './shared.js'
+import { own } from './own.js'
+
+console.log(b, shared, own)
@@ -0,0 +1,5 @@
+import { a } from './vendor/a.js'
+import { b } from './vendor/b.js'
+import { c } from './vendor/c.js'
+
+console.log(a, b, c)
@@ -0,0 +1,7 @@
+import { a } from './vendor/a.js'
+import { b } from './vendor/b.js'
+import { c } from './vendor/c.js'
+import { d } from './lib/d.js'
+import { e } from './e.js'
+
+console.log(a, b, c, d, e)
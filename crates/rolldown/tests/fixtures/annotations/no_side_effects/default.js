@@ -0,0 +1,3 @@
+export default /*#__NO_SIDE_EFFECTS__*/ function () {
+  globalThis.called = true
+}
@@ -0,0 +1,12 @@
+/*#__NO_SIDE_EFFECTS__*/
+export function create() {
+  globalThis.called = true
+}
+
+export const createArrow = /* @__NO_SIDE_EFFECTS__ */ () => {
+  globalThis.called = true
+}
+
+export function impure() {
+  globalThis.impureCalled = true
+}
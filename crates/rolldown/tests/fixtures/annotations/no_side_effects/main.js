@@ -0,0 +1,17 @@
+import assert from 'node:assert'
+import { create, createArrow, impure } from './lib'
+import createDefault from './default'
+
+create()
+createArrow()
+createDefault()
+const unused = local()
+
+/*#__NO_SIDE_EFFECTS__*/
+function local() {
+  globalThis.called = true
+}
+
+impure()
+assert.equal(globalThis.called, undefined)
+assert.equal(globalThis.impureCalled, true)
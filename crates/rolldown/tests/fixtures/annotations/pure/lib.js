@@ -0,0 +1,3 @@
+export function createThing(name) {
+  return { name }
+}
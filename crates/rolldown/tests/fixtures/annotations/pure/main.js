@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { createThing } from './lib'
+
+const unused = /*#__PURE__*/ createThing('unused')
+/*#__PURE__*/ createThing('statement')
+/* @__PURE__ */ new Map()
+
+const used = /*#__PURE__*/ createThing('used')
+assert.deepEqual(used, { name: 'used' })
@@ -0,0 +1,3 @@
+import { pure } from './pure.js'
+import { shared } from './shared.js'
+console.log(pure, shared)
@@ -0,0 +1,4 @@
+import { pure } from './pure.js'
+import { shared } from './shared.js'
+import { impure } from './impure.js'
+console.log(pure, shared, impure)
@@ -0,0 +1,3 @@
+import { shared } from './shared.js'
+import { impure } from './impure.js'
+console.log(shared, impure)
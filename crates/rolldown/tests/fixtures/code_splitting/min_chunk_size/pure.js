@@ -0,0 +1 @@
+export const pure = 'pure'
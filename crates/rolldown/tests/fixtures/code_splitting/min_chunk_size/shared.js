@@ -0,0 +1,2 @@
+// Imported by every entry. This chunk is large enough to not be merged into another chunk itself.
+export const shared = 'shared'
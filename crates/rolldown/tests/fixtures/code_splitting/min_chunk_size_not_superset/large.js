@@ -0,0 +1 @@
+export const large = 'A shared module that is large enough to not be merged into another chunk by itself.';
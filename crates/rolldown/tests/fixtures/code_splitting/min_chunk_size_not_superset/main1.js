@@ -0,0 +1,2 @@
+import { small } from './small.js';
+console.log(small);
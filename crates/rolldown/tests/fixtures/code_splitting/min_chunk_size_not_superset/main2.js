@@ -0,0 +1,3 @@
+import { small } from './small.js';
+import { large } from './large.js';
+console.log(small, large);
@@ -0,0 +1,2 @@
+import { large } from './large.js';
+console.log(large);
@@ -0,0 +1 @@
+export const small = 'small';
@@ -0,0 +1,10 @@
+Object.defineProperty(exports, '__esModule', { value: true })
+exports.e = exports.unused = void 0
+
+exports.a = 'a'
+module.exports.b = 'b'
+Object.defineProperty(exports, 'c', { enumerable: true, get: () => 'c' })
+exports.unused = 'unused'
+exports.e = 'e'
+
+exports.sideEffect = (globalThis.sideEffect = 'side effect')
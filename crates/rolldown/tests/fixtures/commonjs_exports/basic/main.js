@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { a, c } from './foo'
+import * as foo from './foo'
+
+assert.equal(a, 'a')
+assert.equal(foo.b, 'b')
+assert.equal(c, 'c')
+assert.equal(foo.e, 'e')
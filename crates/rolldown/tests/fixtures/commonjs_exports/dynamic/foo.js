@@ -0,0 +1,5 @@
+exports.a = 'a'
+exports.b = 'b'
+
+const name = 'c'
+exports[name] = 'c'
@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+
+const foo = require('./foo')
+assert.deepEqual(Object.keys(foo), ['a', 'b'])
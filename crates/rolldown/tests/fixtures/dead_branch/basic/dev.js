@@ -0,0 +1 @@
+export function dev() { return 'dev' }
@@ -0,0 +1,39 @@
+import assert from 'node:assert'
+import { dev } from './dev'
+import { prod } from './prod'
+
+if (false) {
+  dev()
+} else {
+  prod()
+}
+
+if (!true) {
+  import('./lazy')
+  require('./cjs')
+}
+
+const logger = true ? prod : dev
+const level = false && dev()
+const name = null ?? 'prod'
+assert.equal(logger, prod)
+assert.equal(level, false)
+assert.equal(name, 'prod')
+
+function hoisting() {
+  if (false) {
+    var hoisted = dev()
+  }
+  return hoisted
+}
+assert.equal(hoisting(), undefined)
+
+const obj = {
+  method() {
+    return this
+  },
+}
+assert.equal((true && obj.method)(), undefined)
+assert.equal((false || obj.method)``, undefined)
+assert.equal(delete (true && obj.method), true)
+assert.equal(typeof obj.method, 'function')
@@ -0,0 +1 @@
+export function prod() { return 'prod' }
@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+
+assert.equal(process.env.NODE_ENV, 'production')
+assert.equal(process.env.NODE_ENV.length, 10)
+assert.deepEqual({ __DEV__ }, { __DEV__: false })
+assert.equal(__VERSION__, -1.5)
+assert.equal(__GLOBAL__, globalThis)
+assert.equal(import.meta.env.MODE, 'production')
+
+function shadowed(process) {
+  // `process` is a parameter, not the global
+  return process.env.NODE_ENV
+}
+assert.equal(shadowed({ env: { NODE_ENV: 'test' } }), 'test')
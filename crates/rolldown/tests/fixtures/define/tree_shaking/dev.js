@@ -0,0 +1,5 @@
+if (process.env.NODE_ENV !== 'production') {
+  console.log('development only')
+}
+
+export const mode = process.env.NODE_ENV === 'production' ? 'production' : 'development'
@@ -0,0 +1,5 @@
+import './dev'
+
+if (process.env.NODE_ENV === 'production') {
+  globalThis.mode = 'production'
+}
@@ -0,0 +1,3 @@
+import { bar } from './shared/bar'
+
+export const baz = bar
@@ -0,0 +1,3 @@
+import { bar } from './bar'
+
+export const foo = bar
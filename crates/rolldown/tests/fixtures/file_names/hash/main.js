@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+
+const [{ foo }, { baz }] = await Promise.all([import('./foo'), import('./baz')])
+assert.strictEqual(foo, 'bar')
+assert.strictEqual(baz, 'bar')
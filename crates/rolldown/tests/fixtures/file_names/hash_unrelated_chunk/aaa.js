@@ -0,0 +1 @@
+export const aaa = 'aaa'
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+
+const { aaa } = await import('./aaa')
+const [{ foo }, { baz }] = await Promise.all([import('./foo'), import('./baz')])
+assert.strictEqual(aaa, 'aaa')
+assert.strictEqual(foo, 'bar')
+assert.strictEqual(baz, 'bar')
@@ -0,0 +1,5 @@
+import { value } from './shared'
+
+console.log(value)
+
+export { value }
@@ -0,0 +1,9 @@
+import { value, setValue } from './shared'
+
+setValue('main')
+console.log(value)
+if (value !== 'main') {
+  throw new Error('`value` should be a live binding')
+}
+
+export const lazy = import('./lazy')
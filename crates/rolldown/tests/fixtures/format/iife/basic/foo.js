@@ -0,0 +1,5 @@
+export function foo() {
+  return 'foo'
+}
+
+export const bar = 'bar'
@@ -0,0 +1,7 @@
+import { b } from './b'
+
+export function a() {
+  return 'a'
+}
+
+export const fromB = b()
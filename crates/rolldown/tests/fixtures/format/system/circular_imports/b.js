@@ -0,0 +1,7 @@
+import { a } from './a'
+
+export function b() {
+  return 'b'
+}
+
+export const fromA = a()
@@ -0,0 +1,6 @@
+import { value, setValue } from './shared'
+
+setValue('main')
+console.log(value)
+
+export const lazy = import('./lazy')
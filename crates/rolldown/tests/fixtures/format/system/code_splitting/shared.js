@@ -0,0 +1,5 @@
+export let value = 'shared'
+
+export function setValue(v) {
+  value = v
+}
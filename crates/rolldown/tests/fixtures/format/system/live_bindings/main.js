@@ -0,0 +1,13 @@
+export let count = 0
+export let total = 0
+
+export function increment() {
+  count++
+  ++total
+}
+
+export function reset() {
+  count = total = 0
+}
+
+export { count as counter }
@@ -0,0 +1,5 @@
+const { foo, renamed } = globalThis.myLib
+
+if (foo() !== 'foo' || renamed !== 'bar' || globalThis.myLib.default !== 'default') {
+  throw new Error('unexpected value')
+}
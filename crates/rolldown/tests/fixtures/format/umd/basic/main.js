@@ -0,0 +1,8 @@
+import { foo, bar as renamed } from './foo'
+
+if (foo() !== 'foo' || renamed !== 'bar') {
+  throw new Error('unexpected value')
+}
+
+export { foo, renamed }
+export default 'default'
@@ -0,0 +1,3 @@
+if (globalThis.myLib.foo !== 'foo') {
+  throw new Error('unexpected value')
+}
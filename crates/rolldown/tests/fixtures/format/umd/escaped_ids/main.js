@@ -0,0 +1,3 @@
+import value from 'lib"quoted\\path'
+
+export const result = value
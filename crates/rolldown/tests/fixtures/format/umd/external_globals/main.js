@@ -0,0 +1,5 @@
+import 'polyfill'
+import React, { useState } from 'react'
+import * as client from 'react-dom/client'
+
+export const root = client.createRoot(React.createElement('div', null, useState(0)))
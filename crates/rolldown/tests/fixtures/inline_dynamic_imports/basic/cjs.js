@@ -0,0 +1 @@
+exports.cjs = 'cjs'
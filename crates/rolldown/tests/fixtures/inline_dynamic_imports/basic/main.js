@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { loadWrapped } from './wrapped_importer.js'
+
+export const result = Promise.all([
+  import('./esm.js').then((ns) => assert.strictEqual(ns.esm, 'esm')),
+  import('./cjs.js').then((ns) => assert.strictEqual(ns.default.cjs, 'cjs')),
+  import('./wrapped.js').then((ns) => assert.strictEqual(ns.wrapped, loadWrapped().wrapped)),
+])
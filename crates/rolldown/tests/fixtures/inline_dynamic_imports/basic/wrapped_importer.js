@@ -0,0 +1,2 @@
+// Requiring `wrapped.js` makes it wrapped in `init_wrapped`
+export const loadWrapped = () => require('./wrapped.js')
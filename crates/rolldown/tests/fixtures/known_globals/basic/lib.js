@@ -0,0 +1,6 @@
+export const used = 'used'
+export const cache = new Map()
+export const isArray = Array.isArray
+const max = Math.max
+Symbol.iterator, Number.MAX_SAFE_INTEGER, new Uint8Array()
+globalThis.kept = true
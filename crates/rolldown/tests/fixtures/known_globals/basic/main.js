@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import { used } from './lib'
+
+assert.equal(used, 'used')
+assert.equal(globalThis.kept, true)
@@ -0,0 +1,3 @@
+export const used = 'used'
+export const unused = typeof window === 'undefined' ? undefined : window
+notDefined
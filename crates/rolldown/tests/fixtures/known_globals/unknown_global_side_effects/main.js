@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { used } from './lib'
+
+assert.equal(used, 'used')
@@ -0,0 +1,4 @@
+import { a } from './vendor/a.js'
+import { helper } from './helper.js'
+
+console.log(a, helper)
@@ -0,0 +1,3 @@
+import { a } from './vendor/a.js'
+
+console.log(a)
@@ -0,0 +1,15 @@
+import assert from 'node:assert'
+import { isDefined, sum } from './math'
+
+function average(numbers) {
+  const count = numbers.length
+  if (count === 0) {
+    return undefined
+  }
+  return sum(numbers) / count
+}
+
+assert.equal(average([1, 2, 3]), 2)
+assert.equal(isDefined(average([])), false)
+// Undeclared globals must not be referenced directly
+assert.equal(typeof undeclaredGlobal == 'undefined', true)
@@ -0,0 +1,11 @@
+export function sum(numbers) {
+  let total = 0
+  for (const number of numbers) {
+    total += number
+  }
+  return total
+}
+
+export function isDefined(value) {
+  return value !== undefined
+}
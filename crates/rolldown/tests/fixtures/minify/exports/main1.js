@@ -0,0 +1,3 @@
+import { oftenUsed, rarelyUsed } from './shared'
+
+export const result = [rarelyUsed, oftenUsed, oftenUsed, oftenUsed]
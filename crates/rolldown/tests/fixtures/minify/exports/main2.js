@@ -0,0 +1,3 @@
+import { oftenUsed } from './shared'
+
+export default oftenUsed
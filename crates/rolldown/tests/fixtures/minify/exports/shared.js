@@ -0,0 +1,2 @@
+export const rarelyUsed = 'rarely used'
+export const oftenUsed = 'often used'
@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+
+const a = 'top-level'
+
+function outer(first, second) {
+  const local = first + second
+  function inner(third) {
+    // `a` refers to the top-level binding and `console` to a global, so neither can be used as a mangled name
+    return [local, third, a, typeof console]
+  }
+  return inner
+}
+
+assert.deepEqual(outer(1, 2)(3), [3, 3, 'top-level', 'object'])
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { foo } from './foo'
+
+function getFoo() {
+  return foo
+}
+
+assert.equal(getFoo(), 'foo')
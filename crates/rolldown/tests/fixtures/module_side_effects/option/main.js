@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { value } from './used'
+import './kept'
+import './dropped'
+
+assert.equal(value, 'value')
+assert.equal(globalThis.usedLoaded, true)
+assert.equal(globalThis.keptLoaded, true)
+assert.equal(globalThis.droppedLoaded, undefined)
@@ -0,0 +1,2 @@
+globalThis.usedLoaded = true
+export const value = 'value'
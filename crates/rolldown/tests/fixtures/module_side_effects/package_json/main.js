@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import { used } from 'pure-lib'
+import { unused } from 'pure-lib/other'
+import 'pure-lib/polyfill'
+import 'styled-lib/button.css.js'
+import 'styled-lib/helper'
+import 'broken-lib'
+
+assert.equal(used, 'used')
+assert.equal(globalThis.pureLibLoaded, undefined)
+assert.equal(globalThis.styleLoaded, true)
+assert.equal(globalThis.helperLoaded, undefined)
+assert.equal(globalThis.brokenLibLoaded, true)
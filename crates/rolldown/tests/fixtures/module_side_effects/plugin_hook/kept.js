@@ -0,0 +1 @@
+globalThis.keptLoaded = true
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import './kept'
+import './dropped'
+
+assert.equal(globalThis.keptLoaded, true)
+assert.equal(globalThis.droppedLoaded, undefined)
@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import * as utils from './utils'
+
+assert.equal(utils.a(), 'a')
+assert.equal(utils.b, 'b')
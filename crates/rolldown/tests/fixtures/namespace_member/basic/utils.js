@@ -0,0 +1,7 @@
+export function a() {
+  return 'a'
+}
+
+export const b = 'b'
+
+export const c = 'c'
@@ -0,0 +1 @@
+exports.c = 'c'
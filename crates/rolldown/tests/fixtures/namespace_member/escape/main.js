@@ -0,0 +1,12 @@
+import assert from 'node:assert'
+import * as utils from './utils'
+import * as cjs from './cjs'
+
+function keys(ns) {
+  return Object.keys(ns)
+}
+
+const name = 'b'
+assert.equal(utils[name], 'b')
+assert.deepEqual(keys(utils), ['a', 'b'])
+assert.equal(cjs.c, 'c')
@@ -0,0 +1,3 @@
+import { c } from '../c.js'
+
+export const a = c === 'c' ? 'a' : 'unexpected'
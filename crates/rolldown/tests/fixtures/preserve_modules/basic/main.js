@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import './side_effect.js'
+import { a } from './lib/a.js'
+
+assert.strictEqual(a, 'a')
+assert.strictEqual(globalThis.sideEffect, true)
+export const b = import('./lib/b.js').then(({ b }) => assert.strictEqual(b, 'b'))
@@ -0,0 +1,2 @@
+// Outside of `preserveModulesRoot`
+export const shared = 'shared'
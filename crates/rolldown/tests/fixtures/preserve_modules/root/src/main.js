@@ -0,0 +1,4 @@
+import { Button } from './components/button.js'
+import { shared } from '../shared.js'
+
+console.log(Button, shared)
@@ -0,0 +1,4 @@
+const value = 'foo'
+export function foo() {
+  return value
+}
@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+import { foo as fooFromFoo } from './foo'
+
+const value = 'main'
+function foo() {
+  return value
+}
+
+assert.equal(fooFromFoo(), 'foo')
+assert.equal(foo(), 'main')
@@ -0,0 +1,44 @@
+use derivative::Derivative;
+use napi::Either;
+use napi_derive::napi;
+use rolldown::{InnerTreeshakeOptions, ModuleSideEffects, TreeshakeOptions};
+use rolldown_error::BuildError;
+use serde::Deserialize;
+
+use super::ModuleSideEffectsFunction;
+use crate::types::js_callback::MaybeAsyncJsCallbackExt;
+
+#[napi(object, object_to_js = false)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingTreeshakeOptions {
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "boolean | string[]")]
+  pub module_side_effects: Option<Either<bool, Vec<String>>>,
+  /// Takes precedence over `moduleSideEffects`.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string) => MaybePromise<VoidNullable<boolean>>")]
+  pub module_side_effects_fn: Option<ModuleSideEffectsFunction>,
+  pub unknown_global_side_effects: Option<bool>,
+}
+
+impl From<BindingTreeshakeOptions> for TreeshakeOptions {
+  fn from(value: BindingTreeshakeOptions) -> Self {
+    let module_side_effects = match (value.module_side_effects_fn, value.module_side_effects) {
+      (Some(fn_js), _) => Some(ModuleSideEffects::Fn(Box::new(move |id| {
+        let fn_js = fn_js.clone();
+        Box::pin(async move { fn_js.await_call(id).await.map_err(BuildError::from) })
+      }))),
+      (None, Some(Either::A(value))) => Some(ModuleSideEffects::Boolean(value)),
+      (None, Some(Either::B(ids))) => Some(ModuleSideEffects::IdList(ids)),
+      (None, None) => None,
+    };
+    Self::Options(InnerTreeshakeOptions {
+      module_side_effects,
+      unknown_global_side_effects: value.unknown_global_side_effects,
+    })
+  }
+}
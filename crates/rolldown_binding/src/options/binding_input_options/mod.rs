@@ -1,17 +1,26 @@
 // cSpell:disable
 
+use std::collections::HashMap;
+
 use derivative::Derivative;
 use napi::threadsafe_function::ThreadsafeFunction;
 use napi_derive::napi;
 
 use serde::Deserialize;
 
-use self::{binding_input_item::BindingInputItem, binding_resolve_options::BindingResolveOptions};
+use self::{
+  binding_input_item::BindingInputItem, binding_resolve_options::BindingResolveOptions,
+  binding_treeshake_options::BindingTreeshakeOptions,
+};
 
 use super::plugin::BindingPluginOptions;
+use crate::types::js_callback::MaybeAsyncJsCallback;
 
 mod binding_input_item;
 mod binding_resolve_options;
+mod binding_treeshake_options;
+
+pub type ModuleSideEffectsFunction = MaybeAsyncJsCallback<String, Option<bool>>;
 
 #[napi(object, object_to_js = false)]
 #[derive(Deserialize, Default, Derivative)]
@@ -26,6 +35,7 @@ pub struct BindingInputOptions {
   // acornInjectPlugins?: (() => unknown)[] | (() => unknown);
   // cache?: false | RollupCache;
   // context?: string;sssssssssss
+  pub define: Option<HashMap<String, String>>,
   // experimentalCacheExpiry?: number;
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
@@ -51,7 +61,8 @@ pub struct BindingInputOptions {
   // pub preserve_symlinks: bool,
   // pub shim_missing_exports: bool,
   // strictDeprecations?: boolean;
-  // pub treeshake: Option<bool>,
+  /// Tree shaking is disabled if it is omitted.
+  pub treeshake: Option<BindingTreeshakeOptions>,
   // watch?: WatcherOptions | false;
 
   // extra
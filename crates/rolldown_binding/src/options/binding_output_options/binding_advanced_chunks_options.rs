@@ -0,0 +1,46 @@
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingAdvancedChunksOptions {
+  pub groups: Vec<BindingChunkGroupOptions>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingChunkGroupOptions {
+  pub name: String,
+  /// The source of a regex matched against module ids.
+  pub test: Option<String>,
+  pub priority: Option<i32>,
+  pub min_size: Option<u32>,
+  pub max_size: Option<u32>,
+  pub min_share_count: Option<u32>,
+}
+
+impl TryFrom<BindingAdvancedChunksOptions> for rolldown::AdvancedChunksOptions {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingAdvancedChunksOptions) -> Result<Self, Self::Error> {
+    let groups = value
+      .groups
+      .into_iter()
+      .map(|group| {
+        let test = group.test.map(|test| regex::Regex::new(&test)).transpose().map_err(|err| {
+          napi::Error::from_reason(format!("Invalid `test` of chunk group: {err}"))
+        })?;
+        Ok(rolldown::ChunkGroupOptions {
+          name: group.name,
+          test,
+          priority: group.priority,
+          min_size: group.min_size.map(|size| size as usize),
+          max_size: group.max_size.map(|size| size as usize),
+          min_share_count: group.min_share_count,
+        })
+      })
+      .collect::<Result<Vec<_>, Self::Error>>()?;
+    Ok(Self { groups })
+  }
+}
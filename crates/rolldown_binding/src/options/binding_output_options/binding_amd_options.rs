@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingAmdOptions {
+  pub id: Option<String>,
+  pub auto_id: Option<bool>,
+  pub define: Option<String>,
+}
+
+impl From<BindingAmdOptions> for rolldown::AmdOptions {
+  fn from(value: BindingAmdOptions) -> Self {
+    Self { id: value.id, auto_id: value.auto_id, define: value.define }
+  }
+}
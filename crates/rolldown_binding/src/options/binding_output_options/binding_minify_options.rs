@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingMinifyOptions {
+  pub whitespace: Option<bool>,
+  pub syntax: Option<bool>,
+  pub identifiers: Option<bool>,
+}
+
+impl From<BindingMinifyOptions> for rolldown::MinifyOptions {
+  fn from(value: BindingMinifyOptions) -> Self {
+    Self {
+      whitespace: value.whitespace.unwrap_or(false),
+      syntax: value.syntax.unwrap_or(false),
+      identifiers: value.identifiers.unwrap_or(false),
+    }
+  }
+}
@@ -1,11 +1,27 @@
-use super::super::types::binding_rendered_chunk::RenderedChunk;
+use std::collections::HashMap;
+
+use self::{
+  binding_advanced_chunks_options::BindingAdvancedChunksOptions,
+  binding_amd_options::BindingAmdOptions, binding_minify_options::BindingMinifyOptions,
+};
+use super::super::types::{
+  binding_pre_rendered_chunk::PreRenderedChunk, binding_rendered_chunk::RenderedChunk,
+};
 use super::plugin::BindingPluginOptions;
 use crate::types::js_callback::MaybeAsyncJsCallback;
 use derivative::Derivative;
 use napi_derive::napi;
 use serde::Deserialize;
 
+mod binding_advanced_chunks_options;
+mod binding_amd_options;
+mod binding_minify_options;
+
 pub type AddonOutputOption = MaybeAsyncJsCallback<RenderedChunk, Option<String>>;
+pub type FileNamesOutputOption = MaybeAsyncJsCallback<PreRenderedChunk, String>;
+pub type ManualChunksFunction = MaybeAsyncJsCallback<String, Option<String>>;
+pub type SourcemapIgnoreListOption = MaybeAsyncJsCallback<(String, String), bool>;
+pub type SourcemapPathTransformOption = MaybeAsyncJsCallback<(String, String), String>;
 
 #[napi(object, object_to_js = false)]
 #[derive(Deserialize, Derivative)]
@@ -15,24 +31,29 @@ pub struct BindingOutputOptions {
   // --- Options Rolldown doesn't need to be supported
   // /** @deprecated Use the "renderDynamicImport" plugin hook instead. */
   // dynamicImportFunction: string | undefined;
-  pub entry_file_names: Option<String>,
-  pub chunk_file_names: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: PreRenderedChunk) => MaybePromise<string>")]
+  pub entry_file_names: Option<FileNamesOutputOption>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: PreRenderedChunk) => MaybePromise<string>")]
+  pub chunk_file_names: Option<FileNamesOutputOption>,
 
-  // amd: NormalizedAmdOptions;
-  // assetFileNames: string | ((chunkInfo: PreRenderedAsset) => string);
+  pub amd: Option<BindingAmdOptions>,
+  pub asset_file_names: Option<String>,
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
     ts_type = "Nullable<string> | ((chunk: RenderedChunk) => MaybePromise<VoidNullable<string>>)"
   )]
   pub banner: Option<AddonOutputOption>,
-  // chunkFileNames: string | ((chunkInfo: PreRenderedChunk) => string);
   // compact: boolean;
   pub dir: Option<String>,
-  // pub entry_file_names: String, // | ((chunkInfo: PreRenderedChunk) => string)
   // esModule: boolean;
   #[napi(ts_type = "'default' | 'named' | 'none' | 'auto'")]
   pub exports: Option<String>,
+  pub experimental_min_chunk_size: Option<u32>,
   // extend: boolean;
   // externalLiveBindings: boolean;
   // footer: () => string | Promise<string>;
@@ -42,36 +63,48 @@ pub struct BindingOutputOptions {
     ts_type = "Nullable<string> | ((chunk: RenderedChunk) => MaybePromise<VoidNullable<string>>)"
   )]
   pub footer: Option<AddonOutputOption>,
-  #[napi(ts_type = "'es' | 'cjs'")]
+  #[napi(ts_type = "'es' | 'cjs' | 'iife' | 'umd' | 'system' | 'amd'")]
   pub format: Option<String>,
   // freeze: boolean;
   // generatedCode: NormalizedGeneratedCodeOptions;
-  // globals: GlobalsOption;
+  pub globals: Option<HashMap<String, String>>,
   // hoistTransitiveImports: boolean;
   // indent: true | string;
-  // inlineDynamicImports: boolean;
+  pub inline_dynamic_imports: Option<bool>,
   // interop: GetInterop;
   // intro: () => string | Promise<string>;
-  // manualChunks: ManualChunksOption;
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string) => MaybePromise<VoidNullable<string>>")]
+  pub manual_chunks: Option<ManualChunksFunction>,
+  pub advanced_chunks: Option<BindingAdvancedChunksOptions>,
   // minifyInternalExports: boolean;
-  // name: string | undefined;
+  pub name: Option<String>,
   // namespaceToStringTag: boolean;
   // noConflict: boolean;
   // outro: () => string | Promise<string>;
   // paths: OptionsPaths;
   pub plugins: Vec<BindingPluginOptions>,
   // preferConst: boolean;
-  // preserveModules: boolean;
-  // preserveModulesRoot: string | undefined;
+  pub preserve_modules: Option<bool>,
+  pub preserve_modules_root: Option<String>,
   // sanitizeFileName: (fileName: string) => string;
   #[napi(ts_type = "'file' | 'inline' | 'hidden'")]
   pub sourcemap: Option<String>,
-  // sourcemapExcludeSources: boolean;
-  // sourcemapFile: string | undefined;
-  // sourcemapPathTransform: SourcemapPathTransformOption | undefined;
+  pub sourcemap_debug_ids: Option<bool>,
+  pub sourcemap_exclude_sources: Option<bool>,
+  pub sourcemap_file: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(source: string, sourcemapPath: string) => MaybePromise<boolean>")]
+  pub sourcemap_ignore_list: Option<SourcemapIgnoreListOption>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(source: string, sourcemapPath: string) => MaybePromise<string>")]
+  pub sourcemap_path_transform: Option<SourcemapPathTransformOption>,
   // strict: boolean;
   // systemNullSetters: boolean;
   // validate: boolean;
   // --- Enhanced options
-  // pub minify: bool,
+  pub minify: Option<BindingMinifyOptions>,
 }
@@ -9,6 +9,7 @@ use serde::Deserialize;
 pub struct BindingHookLoadOutput {
   pub code: String,
   pub map: Option<String>,
+  pub module_side_effects: Option<bool>,
 }
 
 impl TryFrom<BindingHookLoadOutput> for rolldown_plugin::HookLoadOutput {
@@ -24,6 +25,7 @@ impl TryFrom<BindingHookLoadOutput> for rolldown_plugin::HookLoadOutput {
             .map_err(BuildError::sourcemap_error)
         })
         .transpose()?,
+      module_side_effects: value.module_side_effects,
     })
   }
 }
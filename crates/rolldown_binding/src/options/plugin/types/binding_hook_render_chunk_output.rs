@@ -1,4 +1,5 @@
 use derivative::Derivative;
+use rolldown_error::BuildError;
 use serde::Deserialize;
 
 #[napi_derive::napi(object)]
@@ -7,10 +8,22 @@ use serde::Deserialize;
 #[derivative(Debug)]
 pub struct BindingHookRenderChunkOutput {
   pub code: String,
+  pub map: Option<String>,
 }
 
-impl From<BindingHookRenderChunkOutput> for rolldown_plugin::HookRenderChunkOutput {
-  fn from(value: BindingHookRenderChunkOutput) -> Self {
-    Self { code: value.code }
+impl TryFrom<BindingHookRenderChunkOutput> for rolldown_plugin::HookRenderChunkOutput {
+  type Error = BuildError;
+
+  fn try_from(value: BindingHookRenderChunkOutput) -> Result<Self, Self::Error> {
+    Ok(rolldown_plugin::HookRenderChunkOutput {
+      code: value.code,
+      map: value
+        .map
+        .map(|content| {
+          rolldown_sourcemap::SourceMap::from_json_string(&content)
+            .map_err(BuildError::sourcemap_error)
+        })
+        .transpose()?,
+    })
   }
 }
@@ -8,10 +8,11 @@ use serde::Deserialize;
 pub struct BindingHookResolveIdOutput {
   pub id: String,
   pub external: Option<bool>,
+  pub module_side_effects: Option<bool>,
 }
 
 impl From<BindingHookResolveIdOutput> for rolldown_plugin::HookResolveIdOutput {
   fn from(value: BindingHookResolveIdOutput) -> Self {
-    Self { id: value.id, external: value.external }
+    Self { id: value.id, external: value.external, module_side_effects: value.module_side_effects }
   }
 }
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreRenderedChunk {
+  pub is_entry: bool,
+  pub is_dynamic_entry: bool,
+  pub facade_module_id: Option<String>,
+  pub module_ids: Vec<String>,
+  pub exports: Vec<String>,
+}
+
+impl From<rolldown::PreRenderedChunk> for PreRenderedChunk {
+  fn from(value: rolldown::PreRenderedChunk) -> Self {
+    Self {
+      is_entry: value.is_entry,
+      is_dynamic_entry: value.is_dynamic_entry,
+      facade_module_id: value.facade_module_id,
+      module_ids: value.module_ids,
+      exports: value.exports,
+    }
+  }
+}
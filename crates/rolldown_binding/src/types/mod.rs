@@ -1,6 +1,7 @@
 pub mod binding_output_asset;
 pub mod binding_output_chunk;
 pub mod binding_outputs;
+pub mod binding_pre_rendered_chunk;
 pub mod binding_rendered_chunk;
 pub mod binding_rendered_module;
 pub mod js_callback;
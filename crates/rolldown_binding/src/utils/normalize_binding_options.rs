@@ -2,9 +2,15 @@ use std::path::PathBuf;
 
 use crate::{
   options::plugin::JsPlugin,
-  types::{binding_rendered_chunk::RenderedChunk, js_callback::MaybeAsyncJsCallbackExt},
+  types::{
+    binding_pre_rendered_chunk::PreRenderedChunk, binding_rendered_chunk::RenderedChunk,
+    js_callback::MaybeAsyncJsCallbackExt,
+  },
+};
+use rolldown::{
+  AddonOutputOption, FileNamesOutputOption, InputOptions, ManualChunksOption, OutputOptions,
+  SourcemapIgnoreList, SourcemapPathTransform,
 };
-use rolldown::{AddonOutputOption, InputOptions, OutputOptions};
 use rolldown_error::BuildError;
 use rolldown_plugin::BoxPlugin;
 
@@ -27,6 +33,56 @@ fn normalize_addon_option(
   })
 }
 
+fn normalize_file_names_option(
+  option: Option<crate::options::FileNamesOutputOption>,
+) -> Option<FileNamesOutputOption> {
+  option.map(move |value| {
+    FileNamesOutputOption::Fn(Box::new(move |chunk| {
+      let fn_js = value.clone();
+      Box::pin(async move {
+        fn_js.await_call(PreRenderedChunk::from(chunk)).await.map_err(BuildError::from)
+      })
+    }))
+  })
+}
+
+fn normalize_manual_chunks_option(
+  option: Option<crate::options::ManualChunksFunction>,
+) -> Option<ManualChunksOption> {
+  option.map(move |fn_js| {
+    ManualChunksOption::Fn(Box::new(move |id| {
+      let fn_js = fn_js.clone();
+      Box::pin(async move { fn_js.await_call(id).await.map_err(BuildError::from) })
+    }))
+  })
+}
+
+fn normalize_sourcemap_ignore_list_option(
+  option: Option<crate::options::SourcemapIgnoreListOption>,
+) -> Option<SourcemapIgnoreList> {
+  option.map(move |fn_js| {
+    SourcemapIgnoreList::new(Box::new(move |source, sourcemap_path| {
+      let fn_js = fn_js.clone();
+      Box::pin(
+        async move { fn_js.await_call((source, sourcemap_path)).await.map_err(BuildError::from) },
+      )
+    }))
+  })
+}
+
+fn normalize_sourcemap_path_transform_option(
+  option: Option<crate::options::SourcemapPathTransformOption>,
+) -> Option<SourcemapPathTransform> {
+  option.map(move |fn_js| {
+    SourcemapPathTransform::new(Box::new(move |source, sourcemap_path| {
+      let fn_js = fn_js.clone();
+      Box::pin(
+        async move { fn_js.await_call((source, sourcemap_path)).await.map_err(BuildError::from) },
+      )
+    }))
+  })
+}
+
 pub fn normalize_binding_options(
   input_options: crate::options::BindingInputOptions,
   output_options: crate::options::BindingOutputOptions,
@@ -52,20 +108,47 @@ pub fn normalize_binding_options(
     input: input_options.input.into_iter().map(Into::into).collect(),
     cwd: cwd.into(),
     external: external.into(),
-    treeshake: true.into(),
+    treeshake: Some(input_options.treeshake.map_or(false.into(), Into::into)),
     resolve: input_options.resolve.map(Into::into),
+    define: input_options.define,
   };
 
   // Deal with output options
 
   let normalized_output_options = OutputOptions {
-    entry_file_names: output_options.entry_file_names,
-    chunk_file_names: output_options.chunk_file_names,
+    entry_file_names: normalize_file_names_option(output_options.entry_file_names),
+    chunk_file_names: normalize_file_names_option(output_options.chunk_file_names),
+    asset_file_names: output_options.asset_file_names,
     dir: output_options.dir,
+    format: output_options
+      .format
+      .map(TryInto::try_into)
+      .transpose()
+      .map_err(|err: BuildError| napi::Error::from_reason(err.to_string()))?,
+    name: output_options.name,
+    globals: output_options.globals,
+    amd: output_options.amd.map(Into::into),
+    manual_chunks: normalize_manual_chunks_option(output_options.manual_chunks),
+    advanced_chunks: output_options.advanced_chunks.map(TryInto::try_into).transpose()?,
+    experimental_min_chunk_size: output_options
+      .experimental_min_chunk_size
+      .map(|size| size as usize),
+    preserve_modules: output_options.preserve_modules,
+    preserve_modules_root: output_options.preserve_modules_root,
+    inline_dynamic_imports: output_options.inline_dynamic_imports,
+    minify: output_options.minify.map(Into::into),
     sourcemap: output_options.sourcemap.map(Into::into),
+    sourcemap_file: output_options.sourcemap_file,
+    sourcemap_exclude_sources: output_options.sourcemap_exclude_sources,
+    sourcemap_debug_ids: output_options.sourcemap_debug_ids,
+    sourcemap_ignore_list: normalize_sourcemap_ignore_list_option(
+      output_options.sourcemap_ignore_list,
+    ),
+    sourcemap_path_transform: normalize_sourcemap_path_transform_option(
+      output_options.sourcemap_path_transform,
+    ),
     banner: normalize_addon_option(output_options.banner),
     footer: normalize_addon_option(output_options.footer),
-    ..Default::default()
   };
 
   // Deal with plugins
@@ -65,8 +65,9 @@ pub fn bundle(file_list: Vec<FileItem>) -> Vec<AssetItem> {
           input,
           cwd: Some("/".into()),
           external: Some(External::ArrayString(vec![])),
-          treeshake: Some(false),
+          treeshake: Some(false.into()),
           resolve: None,
+          define: None,
         })
         .build();
 
@@ -29,7 +29,9 @@ pub use crate::{
   types::rendered_module::RenderedModule,
   types::resolved_export::ResolvedExport,
   types::resolved_path::ResolvedPath,
-  types::stmt_info::{DebugStmtInfoForTreeShaking, StmtInfo, StmtInfoId, StmtInfos},
+  types::stmt_info::{
+    DebugStmtInfoForTreeShaking, NamespaceMemberRef, StmtInfo, StmtInfoId, StmtInfos,
+  },
   types::symbol_ref::SymbolRef,
   types::wrap_kind::WrapKind,
 };
@@ -3,7 +3,7 @@ use std::{fmt::Debug, sync::Arc};
 use crate::{
   types::ast_scope::AstScope, DebugStmtInfoForTreeShaking, ExportsKind, ImportRecord,
   ImportRecordId, LocalExport, ModuleId, ModuleType, NamedImport, NormalModuleId, ResourceId,
-  StmtInfo, StmtInfos, SymbolRef,
+  StmtInfo, StmtInfoId, StmtInfos, SymbolRef,
 };
 use index_vec::IndexVec;
 use oxc::{semantic::SymbolId, span::Span};
@@ -33,9 +33,14 @@ pub struct NormalModule {
   // [[StarExportEntries]] in https://tc39.es/ecma262/#sec-source-text-module-records
   pub star_exports: Vec<ImportRecordId>,
   pub exports_kind: ExportsKind,
+  /// The statements assigning each export of a commonjs module, like `exports.foo = value`. Empty if the
+  /// exports can't be analyzed statically, in which case every assignment is kept.
+  pub commonjs_exports: FxHashMap<Rstr, Vec<StmtInfoId>>,
   pub scope: AstScope,
   pub default_export_ref: SymbolRef,
   pub sourcemap_chain: Vec<rolldown_sourcemap::SourceMap>,
+  /// `false` if the module is declared side effect free, so it's only included when one of its exports is used.
+  pub side_effects: bool,
   pub is_included: bool,
 }
 
@@ -1,4 +1,6 @@
 use index_vec::IndexVec;
+use oxc::span::Span;
+use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
 
 use crate::{ImportRecordId, SymbolRef};
@@ -77,11 +79,26 @@ pub struct StmtInfo {
   /// Top level symbols referenced by this statement.
   pub referenced_symbols: Vec<SymbolRef>,
   pub side_effect: bool,
+  /// Top level functions called by this statement, if these calls are the only reason for `side_effect`.
+  /// Whether they are annotated with `@__NO_SIDE_EFFECTS__` is only known after imports are bound.
+  pub side_effect_calls: Vec<SymbolRef>,
+  /// Static member accesses on namespace imports. They reference the export they read instead of the whole
+  /// namespace object, once it's known which module the namespace belongs to.
+  pub referenced_namespace_members: Vec<NamespaceMemberRef>,
   pub is_included: bool,
   pub import_records: Vec<ImportRecordId>,
   pub debug_label: Option<String>,
 }
 
+/// A static member access on a namespace import, like `ns.foo` of `import * as ns from './foo'`.
+#[derive(Debug)]
+pub struct NamespaceMemberRef {
+  pub namespace_ref: SymbolRef,
+  pub property_name: Rstr,
+  /// The span of the whole member expression, which is used to find the expression in the finalizer.
+  pub span: Span,
+}
+
 impl StmtInfo {
   pub fn to_debug_stmt_info_for_tree_shaking(&self) -> DebugStmtInfoForTreeShaking {
     DebugStmtInfoForTreeShaking {
@@ -10,10 +10,16 @@ use oxc::span::Span;
 use crate::{
   diagnostic::Diagnostic,
   error_kind::{
-    external_entry::ExternalEntry, forbid_const_assign::ForbidConstAssign,
-    sourcemap_error::SourceMapError, unresolved_entry::UnresolvedEntry,
-    unresolved_import::UnresolvedImport, unsupported_eval::UnsupportedEval, BuildErrorLike,
-    NapiError,
+    external_entry::ExternalEntry,
+    forbid_const_assign::ForbidConstAssign,
+    invalid_option::{InvalidOption, InvalidOptionTypes},
+    missing_name_option_for_iife_export::MissingNameOptionForIifeExport,
+    missing_name_option_for_umd_export::MissingNameOptionForUmdExport,
+    sourcemap_error::SourceMapError,
+    unresolved_entry::UnresolvedEntry,
+    unresolved_import::UnresolvedImport,
+    unsupported_eval::UnsupportedEval,
+    BuildErrorLike, NapiError,
   },
 };
 
@@ -101,6 +107,152 @@ impl BuildError {
     Self::new_inner(SourceMapError { error })
   }
 
+  pub fn missing_name_option_for_iife_export() -> Self {
+    Self::new_inner(MissingNameOptionForIifeExport {})
+  }
+
+  pub fn missing_name_option_for_umd_export() -> Self {
+    Self::new_inner(MissingNameOptionForUmdExport {})
+  }
+
+  pub fn unknown_output_format(format: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::UnknownOutputFormat(format.into()),
+    })
+  }
+
+  pub fn unsupported_code_splitting_format(format: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::UnsupportedCodeSplittingFormat(format.into()),
+    })
+  }
+
+  pub fn incompatible_options(option: impl Into<String>, other: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::IncompatibleOptions {
+        option: option.into(),
+        other: other.into(),
+      },
+    })
+  }
+
+  pub fn inline_dynamic_imports_with_multiple_inputs() -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InlineDynamicImportsWithMultipleInputs,
+    })
+  }
+
+  pub fn sourcemap_file_with_code_splitting() -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::SourcemapFileWithCodeSplitting,
+    })
+  }
+
+  pub fn invalid_define_key(key: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidDefineKey(key.into()),
+    })
+  }
+
+  pub fn invalid_define_value(key: impl Into<String>, value: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidDefineValue {
+        key: key.into(),
+        value: value.into(),
+      },
+    })
+  }
+
+  pub fn invalid_output_name(name: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidOutputName(name.into()),
+    })
+  }
+
+  pub fn amd_id_with_auto_id() -> Self {
+    Self::new_inner(InvalidOption { invalid_option_types: InvalidOptionTypes::AmdIdWithAutoId })
+  }
+
+  pub fn amd_id_with_code_splitting() -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::AmdIdWithCodeSplitting,
+    })
+  }
+
+  pub fn invalid_hash_length(
+    option: impl Into<String>,
+    pattern: impl Into<String>,
+    length: usize,
+  ) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidHashLength {
+        option: option.into(),
+        pattern: pattern.into(),
+        length,
+      },
+    })
+  }
+
+  pub fn mixed_hash_lengths(option: impl Into<String>, pattern: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::MixedHashLengths {
+        option: option.into(),
+        pattern: pattern.into(),
+      },
+    })
+  }
+
+  pub fn hash_too_short(
+    option: impl Into<String>,
+    chunk_count: usize,
+    min_length: usize,
+    length: usize,
+  ) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::HashTooShort {
+        option: option.into(),
+        chunk_count,
+        min_length,
+        length,
+      },
+    })
+  }
+
+  pub fn unknown_file_name_placeholder(
+    option: impl Into<String>,
+    placeholder: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::UnknownFileNamePlaceholder {
+        option: option.into(),
+        placeholder: placeholder.into(),
+      },
+    })
+  }
+
+  pub fn invalid_file_name_pattern(option: impl Into<String>, pattern: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidFileNamePattern {
+        option: option.into(),
+        pattern: pattern.into(),
+      },
+    })
+  }
+
+  pub fn invalid_file_name_substitution(
+    option: impl Into<String>,
+    placeholder: impl Into<String>,
+    value: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_types: InvalidOptionTypes::InvalidFileNameSubstitution {
+        option: option.into(),
+        placeholder: placeholder.into(),
+        value: value.into(),
+      },
+    })
+  }
+
   // --- rolldown specific
   pub fn napi_error(status: String, reason: String) -> Self {
     Self::new_inner(NapiError { status, reason })
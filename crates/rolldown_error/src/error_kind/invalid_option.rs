@@ -0,0 +1,89 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub enum InvalidOptionTypes {
+  UnknownOutputFormat(String),
+  UnsupportedCodeSplittingFormat(String),
+  AmdIdWithAutoId,
+  AmdIdWithCodeSplitting,
+  InvalidHashLength { option: String, pattern: String, length: usize },
+  MixedHashLengths { option: String, pattern: String },
+  HashTooShort { option: String, chunk_count: usize, min_length: usize, length: usize },
+  UnknownFileNamePlaceholder { option: String, placeholder: String },
+  InvalidFileNamePattern { option: String, pattern: String },
+  InvalidFileNameSubstitution { option: String, placeholder: String, value: String },
+  IncompatibleOptions { option: String, other: String },
+  InlineDynamicImportsWithMultipleInputs,
+  SourcemapFileWithCodeSplitting,
+  InvalidDefineKey(String),
+  InvalidDefineValue { key: String, value: String },
+  InvalidOutputName(String),
+}
+
+#[derive(Debug)]
+pub struct InvalidOption {
+  pub(crate) invalid_option_types: InvalidOptionTypes,
+}
+
+impl BuildErrorLike for InvalidOption {
+  fn code(&self) -> &'static str {
+    "INVALID_OPTION"
+  }
+
+  fn message(&self) -> String {
+    match &self.invalid_option_types {
+      InvalidOptionTypes::UnknownOutputFormat(format) => {
+        format!("Invalid value \"{format}\" for option \"output.format\" - Valid values are \"amd\", \"cjs\", \"system\", \"es\", \"iife\" or \"umd\".")
+      }
+      InvalidOptionTypes::UnsupportedCodeSplittingFormat(format) => {
+        format!("Invalid value \"{format}\" for option \"output.format\" - UMD and IIFE output formats are not supported for code-splitting builds.")
+      }
+      InvalidOptionTypes::AmdIdWithAutoId => {
+        "Invalid value for option \"output.amd.id\" - this option cannot be used together with \"output.amd.autoId\".".to_string()
+      }
+      InvalidOptionTypes::AmdIdWithCodeSplitting => {
+        "Invalid value for option \"output.amd.id\" - this option is only properly supported for single-file builds. Use \"output.amd.autoId\" instead.".to_string()
+      }
+      InvalidOptionTypes::InvalidHashLength { option, pattern, length } => {
+        if *length < 6 {
+          format!("Hashes cannot be shorter than 6 characters, received {length}. Check the \"{pattern}\" pattern of \"{option}\".")
+        } else {
+          format!("Hashes cannot be longer than 22 characters, received {length}. Check the \"{pattern}\" pattern of \"{option}\".")
+        }
+      }
+      InvalidOptionTypes::MixedHashLengths { option, pattern } => {
+        format!("All hashes of a file name must have the same length. Check the \"{pattern}\" pattern of \"{option}\".")
+      }
+      InvalidOptionTypes::HashTooShort { option, chunk_count, min_length, length } => {
+        format!("To generate hashes for this number of chunks (currently {chunk_count}), you need a minimum hash size of {min_length}, received {length}. Check the \"{option}\" option.")
+      }
+      InvalidOptionTypes::UnknownFileNamePlaceholder { option, placeholder } => {
+        format!("\"{placeholder}\" is not a valid placeholder in the \"{option}\" pattern.")
+      }
+      InvalidOptionTypes::InvalidFileNamePattern { option, pattern } => {
+        format!("Invalid pattern \"{pattern}\" for \"{option}\", patterns can be neither absolute nor relative paths. If you want your files to be stored in a subdirectory, write its name without a leading slash like this: subdirectory/pattern.")
+      }
+      InvalidOptionTypes::InvalidFileNameSubstitution { option, placeholder, value } => {
+        format!("Invalid substitution \"{value}\" for placeholder \"{placeholder}\" in \"{option}\" pattern, can be neither absolute nor relative path.")
+      }
+      InvalidOptionTypes::IncompatibleOptions { option, other } => {
+        format!("Invalid value for option \"{option}\" - this option is not supported for \"{other}\".")
+      }
+      InvalidOptionTypes::InlineDynamicImportsWithMultipleInputs => {
+        "Invalid value \"true\" for option \"output.inlineDynamicImports\" - multiple inputs are not supported when \"output.inlineDynamicImports\" is true.".to_string()
+      }
+      InvalidOptionTypes::SourcemapFileWithCodeSplitting => {
+        "Invalid value for option \"output.sourcemapFile\" - this option is only supported for single-file builds.".to_string()
+      }
+      InvalidOptionTypes::InvalidDefineKey(key) => {
+        format!("Invalid key \"{key}\" for option \"define\" - keys must be identifiers or member expressions like \"process.env.NODE_ENV\".")
+      }
+      InvalidOptionTypes::InvalidDefineValue { key, value } => {
+        format!("Invalid value \"{value}\" of \"{key}\" for option \"define\" - values must be JSON literals, identifiers or member expressions.")
+      }
+      InvalidOptionTypes::InvalidOutputName(name) => {
+        format!("Invalid value \"{name}\" for option \"output.name\" - the name must be a valid JavaScript identifier.")
+      }
+    }
+  }
+}
@@ -0,0 +1,14 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct MissingNameOptionForIifeExport {}
+
+impl BuildErrorLike for MissingNameOptionForIifeExport {
+  fn code(&self) -> &'static str {
+    "MISSING_NAME_OPTION_FOR_IIFE_EXPORT"
+  }
+
+  fn message(&self) -> String {
+    "If you do not supply \"output.name\", you may not be able to access the exports of an IIFE bundle.".to_string()
+  }
+}
@@ -3,6 +3,9 @@ use std::fmt::Debug;
 use crate::diagnostic::DiagnosticBuilder;
 pub mod external_entry;
 pub mod forbid_const_assign;
+pub mod invalid_option;
+pub mod missing_name_option_for_iife_export;
+pub mod missing_name_option_for_umd_export;
 pub mod sourcemap_error;
 pub mod unresolved_entry;
 pub mod unresolved_import;
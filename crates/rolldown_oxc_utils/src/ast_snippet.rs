@@ -2,6 +2,7 @@ use oxc::{
   allocator::{self, Allocator},
   ast::ast::{self, Statement},
   span::{Atom, Span, SPAN},
+  syntax::operator::BinaryOperator,
 };
 
 use crate::{Dummy, IntoIn};
@@ -44,9 +45,18 @@ impl<'ast> AstSnippet<'ast> {
     &self,
     object: PassedStr,
     property: PassedStr,
+  ) -> ast::MemberExpression<'ast> {
+    self.literal_prop_access_member_expr_with_object(self.id_ref_expr(object, SPAN), property)
+  }
+
+  /// `[object].[property]`, where `object` is an arbitrary expression
+  pub fn literal_prop_access_member_expr_with_object(
+    &self,
+    object: ast::Expression<'ast>,
+    property: PassedStr,
   ) -> ast::MemberExpression<'ast> {
     ast::MemberExpression::StaticMemberExpression(ast::StaticMemberExpression {
-      object: ast::Expression::Identifier(self.id_ref(object, SPAN).into_in(self.alloc)),
+      object,
       property: ast::IdentifierName { name: self.atom(property), ..Dummy::dummy(self.alloc) },
       ..Dummy::dummy(self.alloc)
     })
@@ -277,6 +287,129 @@ impl<'ast> AstSnippet<'ast> {
     )
   }
 
+  /// ```js
+  /// "value"
+  /// ```
+  pub fn string_literal_expr(&self, value: PassedStr, span: Span) -> ast::Expression<'ast> {
+    ast::Expression::StringLiteral(
+      ast::StringLiteral { span, value: self.atom(value) }.into_in(self.alloc),
+    )
+  }
+
+  /// ```js
+  /// left + right
+  /// ```
+  pub fn binary_expr(
+    &self,
+    left: ast::Expression<'ast>,
+    operator: BinaryOperator,
+    right: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    ast::Expression::BinaryExpression(
+      ast::BinaryExpression { span: SPAN, left, operator, right }.into_in(self.alloc),
+    )
+  }
+
+  /// `callee(...arguments)`
+  pub fn call_expr_with_callee_expr(
+    &self,
+    callee: ast::Expression<'ast>,
+    arguments: allocator::Vec<'ast, ast::Argument<'ast>>,
+  ) -> ast::Expression<'ast> {
+    ast::Expression::CallExpression(
+      ast::CallExpression { callee, arguments, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+    )
+  }
+
+  /// ```js
+  /// new Promise((resolve) => require([source], resolve))
+  /// ```
+  pub fn require_in_promise_expr(&self, source: ast::Expression<'ast>) -> ast::Expression<'ast> {
+    // require([source], resolve)
+    let mut elements = allocator::Vec::new_in(self.alloc);
+    elements.push(ast::ArrayExpressionElement::Expression(source));
+    let mut require_call_expr = self.call_expr("require");
+    require_call_expr.arguments.push(ast::Argument::Expression(ast::Expression::ArrayExpression(
+      ast::ArrayExpression { span: SPAN, elements, trailing_comma: None }.into_in(self.alloc),
+    )));
+    require_call_expr.arguments.push(ast::Argument::Expression(self.id_ref_expr("resolve", SPAN)));
+
+    // (resolve) => ...
+    let mut arrow_expr = self.only_return_arrow_expr(ast::Expression::CallExpression(
+      require_call_expr.into_in(self.alloc),
+    ));
+    if let ast::Expression::ArrowFunctionExpression(arrow_expr) = &mut arrow_expr {
+      arrow_expr.params.items.push(ast::FormalParameter {
+        pattern: ast::BindingPattern {
+          kind: ast::BindingPatternKind::BindingIdentifier(
+            self.id("resolve", SPAN).into_in(self.alloc),
+          ),
+          ..Dummy::dummy(self.alloc)
+        },
+        ..Dummy::dummy(self.alloc)
+      });
+    }
+
+    // new Promise(...)
+    let mut arguments = allocator::Vec::new_in(self.alloc);
+    arguments.push(ast::Argument::Expression(arrow_expr));
+    ast::Expression::NewExpression(
+      ast::NewExpression {
+        span: SPAN,
+        callee: self.id_ref_expr("Promise", SPAN),
+        arguments,
+        type_parameters: None,
+      }
+      .into_in(self.alloc),
+    )
+  }
+
+  /// ```js
+  /// Promise.resolve().then(() => value)
+  /// ```
+  pub fn promise_resolve_then_expr(&self, value: ast::Expression<'ast>) -> ast::Expression<'ast> {
+    let resolve_call_expr = self.call_expr_with_callee_expr(
+      ast::Expression::MemberExpression(
+        self.literal_prop_access_member_expr("Promise", "resolve").into_in(self.alloc),
+      ),
+      allocator::Vec::new_in(self.alloc),
+    );
+    let then_member_expr = ast::Expression::MemberExpression(
+      ast::MemberExpression::StaticMemberExpression(ast::StaticMemberExpression {
+        object: resolve_call_expr,
+        property: ast::IdentifierName { name: self.atom("then"), ..Dummy::dummy(self.alloc) },
+        ..Dummy::dummy(self.alloc)
+      })
+      .into_in(self.alloc),
+    );
+    let mut arguments = allocator::Vec::new_in(self.alloc);
+    arguments.push(ast::Argument::Expression(self.only_return_arrow_expr(value)));
+    self.call_expr_with_callee_expr(then_member_expr, arguments)
+  }
+
+  /// ```js
+  /// object.property = value
+  /// ```
+  pub fn assign_to_literal_prop_expr(
+    &self,
+    object: PassedStr,
+    property: PassedStr,
+    value: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    ast::Expression::AssignmentExpression(
+      ast::AssignmentExpression {
+        left: ast::AssignmentTarget::SimpleAssignmentTarget(
+          ast::SimpleAssignmentTarget::MemberAssignmentTarget(
+            self.literal_prop_access_member_expr(object, property).into_in(self.alloc),
+          ),
+        ),
+        right: value,
+        ..Dummy::dummy(self.alloc)
+      }
+      .into_in(self.alloc),
+    )
+  }
+
   /// ```js
   /// 42
   /// ```
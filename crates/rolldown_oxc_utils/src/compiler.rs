@@ -2,7 +2,7 @@ use std::{fmt::Debug, pin::Pin, sync::Arc};
 
 use oxc::{
   allocator::Allocator,
-  ast::ast,
+  ast::{ast, Trivias},
   codegen::{Codegen, CodegenOptions, CodegenReturn},
   parser::Parser,
   semantic::{Semantic, SemanticBuilder},
@@ -15,7 +15,10 @@ pub struct OxcCompiler;
 #[allow(clippy::box_collection, clippy::non_send_fields_in_send_ty, unused)]
 pub struct OxcProgram {
   program: ast::Program<'static>,
+  /// The leading statements split off from `program`, see `OxcProgram::split_off_hoisted_statements`.
+  hoisted_program: Option<ast::Program<'static>>,
   source: Pin<Arc<str>>,
+  trivias: Trivias,
   // Order matters here, we need drop the program first, then drop the allocator. Otherwise, there will be a segmentation fault.
   // The `program` is allocated on the `allocator`. Clippy think it's not used, but it's used.
   allocator: Pin<Box<Allocator>>,
@@ -35,7 +38,7 @@ impl Default for OxcProgram {
       let alloc = std::mem::transmute::<_, &'static Allocator>(allocator.as_ref());
       ast::Program::dummy(alloc)
     };
-    Self { program, source, allocator }
+    Self { program, hoisted_program: None, source, trivias: Trivias::default(), allocator }
   }
 }
 
@@ -47,6 +50,11 @@ impl OxcProgram {
     &self.source
   }
 
+  /// The comments of the source
+  pub fn trivias(&self) -> &Trivias {
+    &self.trivias
+  }
+
   pub fn program(&self) -> &ast::Program<'_> {
     // SAFETY: `&'a ast::Program<'a>` can't outlive the `&'a ast::Program<'static>`.
     unsafe { std::mem::transmute(&self.program) }
@@ -63,6 +71,24 @@ impl OxcProgram {
     (program, &self.allocator)
   }
 
+  /// Move the leading `len` statements of the body into a separate program, so they can be printed in
+  /// another place of the output than the rest of the body.
+  pub fn split_off_hoisted_statements(&mut self, len: usize) {
+    let (program, allocator) = self.program_mut_and_allocator();
+    let mut stmts = program.body.take_in(allocator).into_iter();
+    let mut hoisted_program = ast::Program::dummy(allocator);
+    hoisted_program.body.extend(stmts.by_ref().take(len));
+    program.body.extend(stmts);
+    // SAFETY: `hoisted_program` is allocated on `self.allocator` like `self.program`.
+    self.hoisted_program = Some(unsafe { std::mem::transmute(hoisted_program) });
+  }
+
+  /// The statements split off by `OxcProgram::split_off_hoisted_statements`.
+  pub fn hoisted_program(&self) -> Option<&ast::Program<'_>> {
+    // SAFETY: `&'a ast::Program<'a>` can't outlive the `&'a ast::Program<'static>`.
+    unsafe { std::mem::transmute(self.hoisted_program.as_ref()) }
+  }
+
   pub fn make_semantic(&self, ty: SourceType) -> Semantic<'_> {
     let semantic = SemanticBuilder::new(&self.source, ty).build(self.program()).semantic;
     semantic
@@ -90,13 +116,19 @@ impl OxcCompiler {
   pub fn parse(source: impl Into<Arc<str>>, ty: SourceType) -> OxcProgram {
     let source = Pin::new(source.into());
     let allocator = Box::pin(oxc::allocator::Allocator::default());
-    let program = unsafe {
+    let ret = unsafe {
       let source = std::mem::transmute::<_, &'static str>(&*source);
       let alloc = std::mem::transmute::<_, &'static Allocator>(allocator.as_ref());
-      Parser::new(alloc, source, ty).parse().program
+      Parser::new(alloc, source, ty).parse()
     };
 
-    OxcProgram { program, source, allocator }
+    OxcProgram {
+      program: ret.program,
+      hoisted_program: None,
+      source,
+      trivias: ret.trivias,
+      allocator,
+    }
   }
 
   pub fn print(ast: &OxcProgram, source_name: &str, enable_source_map: bool) -> CodegenReturn {
@@ -107,6 +139,50 @@ impl OxcCompiler {
     );
     codegen.build(&ast.program)
   }
+
+  /// Like `print`, but without unnecessary whitespace and comments.
+  pub fn print_minified(
+    ast: &OxcProgram,
+    source_name: &str,
+    enable_source_map: bool,
+  ) -> CodegenReturn {
+    let codegen = Codegen::<true>::new(
+      source_name,
+      ast.source(),
+      CodegenOptions { enable_typescript: false, enable_source_map },
+    );
+    codegen.build(&ast.program)
+  }
+
+  /// Like `print`, but prints the statements split off by `OxcProgram::split_off_hoisted_statements`.
+  pub fn print_hoisted(
+    ast: &OxcProgram,
+    source_name: &str,
+    enable_source_map: bool,
+  ) -> Option<CodegenReturn> {
+    let hoisted_program = ast.hoisted_program.as_ref()?;
+    let codegen = Codegen::<false>::new(
+      source_name,
+      ast.source(),
+      CodegenOptions { enable_typescript: false, enable_source_map },
+    );
+    Some(codegen.build(hoisted_program))
+  }
+
+  /// Like `print_hoisted`, but without unnecessary whitespace and comments.
+  pub fn print_hoisted_minified(
+    ast: &OxcProgram,
+    source_name: &str,
+    enable_source_map: bool,
+  ) -> Option<CodegenReturn> {
+    let hoisted_program = ast.hoisted_program.as_ref()?;
+    let codegen = Codegen::<true>::new(
+      source_name,
+      ast.source(),
+      CodegenOptions { enable_typescript: false, enable_source_map },
+    );
+    Some(codegen.build(hoisted_program))
+  }
 }
 
 #[test]
@@ -10,6 +10,7 @@ pub use crate::{
   },
   plugin_context::{PluginContext, SharedPluginContext},
   plugin_driver::{PluginDriver, SharedPluginDriver},
+  types::emitted_asset::EmittedAsset,
   types::hook_build_end_args::HookBuildEndArgs,
   types::hook_load_args::HookLoadArgs,
   types::hook_load_output::HookLoadOutput,
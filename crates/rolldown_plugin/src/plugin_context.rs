@@ -1,12 +1,17 @@
 use std::{path::Path, sync::Weak};
 
-use crate::{types::plugin_context_resolve_options::PluginContextResolveOptions, PluginDriver};
+use crate::{
+  types::{
+    emitted_asset::EmittedAsset, plugin_context_resolve_options::PluginContextResolveOptions,
+  },
+  PluginDriver,
+};
 
 pub type SharedPluginContext = std::sync::Arc<PluginContext>;
 
 #[derive(Debug, Default)]
 pub struct PluginContext {
-  pub(crate) _plugin_driver: Weak<PluginDriver>,
+  pub(crate) plugin_driver: Weak<PluginDriver>,
 }
 
 impl PluginContext {
@@ -18,4 +23,11 @@ impl PluginContext {
   ) {
     unimplemented!()
   }
+
+  /// Emit an asset, which is written next to the chunks. Its file name comes from `asset_file_names`,
+  /// unless `EmittedAsset::file_name` is given.
+  pub fn emit_file(&self, asset: EmittedAsset) {
+    let plugin_driver = self.plugin_driver.upgrade().expect("Plugin driver should be alive");
+    plugin_driver.emitted_assets.lock().expect("ignore poison error").push(asset);
+  }
 }
@@ -7,6 +7,8 @@ use rolldown_sourcemap::SourceMap;
 
 impl PluginDriver {
   pub async fn build_start(&self) -> HookNoopReturn {
+    // Assets emitted by a previous build are stale
+    self.emitted_assets.lock().expect("ignore poison error").clear();
     // TODO should call `build_start` of all plugins in parallel
     for (plugin, ctx) in &self.plugins {
       plugin.build_start(ctx).await?;
@@ -32,9 +34,11 @@ impl PluginDriver {
     Ok(None)
   }
 
+  /// The `module_side_effects` returned by the last plugin that set one is written to `module_side_effects`.
   pub async fn transform(
     &self,
     args: &HookTransformArgs<'_>,
+    module_side_effects: &mut Option<bool>,
   ) -> Result<(String, Vec<SourceMap>), BuildError> {
     let mut sourcemap_chain = vec![];
     let mut code = args.code.to_string();
@@ -46,6 +50,9 @@ impl PluginDriver {
         if let Some(map) = r.map {
           sourcemap_chain.push(map);
         }
+        if r.module_side_effects.is_some() {
+          *module_side_effects = r.module_side_effects;
+        }
       }
     }
     Ok((code, sourcemap_chain))
@@ -62,12 +69,16 @@ impl PluginDriver {
   pub async fn render_chunk(
     &self,
     mut args: HookRenderChunkArgs<'_>,
-  ) -> Result<String, BuildError> {
+  ) -> Result<(String, Vec<SourceMap>), BuildError> {
+    let mut sourcemap_chain = vec![];
     for (plugin, ctx) in &self.plugins {
       if let Some(r) = plugin.render_chunk(ctx, &args).await? {
         args.code = r.code;
+        if let Some(map) = r.map {
+          sourcemap_chain.push(map);
+        }
       }
     }
-    Ok(args.code)
+    Ok((args.code, sourcemap_chain))
   }
 }
@@ -1,6 +1,6 @@
-use std::sync::{Arc, Weak};
+use std::sync::{Arc, Mutex, Weak};
 
-use crate::{plugin_context::SharedPluginContext, BoxPlugin, PluginContext};
+use crate::{plugin_context::SharedPluginContext, BoxPlugin, EmittedAsset, PluginContext};
 
 mod build_hooks;
 mod output_hooks;
@@ -9,6 +9,7 @@ pub type SharedPluginDriver = Arc<PluginDriver>;
 
 pub struct PluginDriver {
   plugins: Vec<(BoxPlugin, SharedPluginContext)>,
+  pub(crate) emitted_assets: Mutex<Vec<EmittedAsset>>,
 }
 
 impl PluginDriver {
@@ -16,10 +17,15 @@ impl PluginDriver {
     Arc::new_cyclic(|plugin_driver| {
       let with_context = plugins
         .into_iter()
-        .map(|plugin| (plugin, PluginContext { _plugin_driver: Weak::clone(plugin_driver) }.into()))
+        .map(|plugin| (plugin, PluginContext { plugin_driver: Weak::clone(plugin_driver) }.into()))
         .collect::<Vec<_>>();
 
-      Self { plugins: with_context }
+      Self { plugins: with_context, emitted_assets: Mutex::default() }
     })
   }
+
+  /// Takes the assets emitted by plugins during the current build.
+  pub fn take_emitted_assets(&self) -> Vec<EmittedAsset> {
+    std::mem::take(&mut *self.emitted_assets.lock().expect("ignore poison error"))
+  }
 }
@@ -0,0 +1,9 @@
+/// An asset emitted by a plugin with `PluginContext::emit_file`.
+#[derive(Debug, Clone)]
+pub struct EmittedAsset {
+  /// Fills the `[name]`, `[ext]` and `[extname]` placeholders of `asset_file_names`.
+  pub name: Option<String>,
+  /// The file name of the asset, which takes precedence over `asset_file_names`.
+  pub file_name: Option<String>,
+  pub source: String,
+}
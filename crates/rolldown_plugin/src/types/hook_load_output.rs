@@ -4,4 +4,6 @@ use rolldown_sourcemap::SourceMap;
 pub struct HookLoadOutput {
   pub code: String,
   pub map: Option<SourceMap>,
+  /// Overrides the `module_side_effects` returned by earlier hooks for the module.
+  pub module_side_effects: Option<bool>,
 }
@@ -1,4 +1,7 @@
+use rolldown_sourcemap::SourceMap;
+
 #[derive(Debug)]
 pub struct HookRenderChunkOutput {
   pub code: String,
+  pub map: Option<SourceMap>,
 }
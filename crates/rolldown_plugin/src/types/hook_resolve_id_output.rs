@@ -2,4 +2,6 @@
 pub struct HookResolveIdOutput {
   pub id: String,
   pub external: Option<bool>,
+  /// Overrides the `sideEffects` of the package.json that the module belongs to.
+  pub module_side_effects: Option<bool>,
 }
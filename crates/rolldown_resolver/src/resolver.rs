@@ -1,9 +1,11 @@
+use dashmap::DashMap;
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use std::path::{Path, PathBuf};
 use sugar_path::SugarPathBuf;
 
-use oxc_resolver::{Resolution, ResolveError, ResolverGeneric};
+use oxc_resolver::{PackageJson, Resolution, ResolveError, ResolverGeneric};
+use regex::Regex;
 
 use crate::{types::resolved_path::ResolvedPath, ModuleType, ResolveOptions};
 
@@ -11,12 +13,15 @@ use crate::{types::resolved_path::ResolvedPath, ModuleType, ResolveOptions};
 pub struct Resolver<T: FileSystem + Default> {
   cwd: PathBuf,
   inner: ResolverGeneric<T>,
+  /// The `sideEffects` of every package.json seen so far, keyed by the directory of the package.json.
+  // Using `DashMap` because resolve is called in parallel
+  package_side_effects: DashMap<PathBuf, PackageSideEffects>,
 }
 
 impl<F: FileSystem + Default> Resolver<F> {
   pub fn new(options: ResolveOptions, cwd: PathBuf, fs: F) -> Self {
     let inner_resolver = ResolverGeneric::new_with_file_system(fs, options);
-    Self { cwd, inner: inner_resolver }
+    Self { cwd, inner: inner_resolver, package_side_effects: DashMap::default() }
   }
 
   pub fn cwd(&self) -> &PathBuf {
@@ -28,6 +33,8 @@ impl<F: FileSystem + Default> Resolver<F> {
 pub struct ResolveRet {
   pub resolved: ResolvedPath,
   pub module_type: ModuleType,
+  /// The `sideEffects` field of the package.json that the module belongs to, if there is one.
+  pub side_effects: Option<bool>,
 }
 
 impl<F: FileSystem + Default> Resolver<F> {
@@ -68,6 +75,7 @@ impl<F: FileSystem + Default> Resolver<F> {
           info.full_path().to_str().expect("should be valid utf8").to_string(),
           false,
           calc_module_type(&info),
+          self.calc_side_effects(&info),
         )
       })
       .or_else(|err| match err {
@@ -76,6 +84,7 @@ impl<F: FileSystem + Default> Resolver<F> {
           path.to_str().expect("should be valid utf8").to_string(),
           true,
           ModuleType::CJS,
+          None,
         )),
         // To determine whether there is an importer.
         _ => {
@@ -108,6 +117,123 @@ fn calc_module_type(info: &Resolution) -> ModuleType {
   ModuleType::Unknown
 }
 
-fn build_resolve_ret(path: String, ignored: bool, module_type: ModuleType) -> ResolveRet {
-  ResolveRet { resolved: ResolvedPath { path: path.into(), ignored }, module_type }
+impl<F: FileSystem + Default> Resolver<F> {
+  /// The globs of `sideEffects` are compiled once per package.json, since every module of a package is
+  /// matched against them.
+  fn calc_side_effects(&self, info: &Resolution) -> Option<bool> {
+    let package_json = info.package_json()?;
+    let relative_path = info.path().strip_prefix(package_json.directory()).ok()?;
+    let relative_path = relative_path.to_str()?.replace('\\', "/");
+    if let Some(side_effects) = self.package_side_effects.get(package_json.directory()) {
+      return side_effects.matches(&relative_path);
+    }
+    let side_effects = PackageSideEffects::new(package_json);
+    let matches = side_effects.matches(&relative_path);
+    self.package_side_effects.insert(package_json.directory().to_path_buf(), side_effects);
+    matches
+  }
+}
+
+/// `sideEffects` is either a boolean or globs of the files that have side effects. Like webpack, globs
+/// without a `/` match files in any directory of the package.
+#[derive(Debug)]
+enum PackageSideEffects {
+  Unspecified,
+  Boolean(bool),
+  Globs(Vec<Regex>),
+}
+
+impl PackageSideEffects {
+  fn new(package_json: &PackageJson) -> Self {
+    let Some(side_effects) = package_json.raw_json().get("sideEffects") else {
+      return Self::Unspecified;
+    };
+    if let Some(value) = side_effects.as_bool() {
+      return Self::Boolean(value);
+    }
+    let patterns = match (side_effects.as_array(), side_effects.as_str()) {
+      (Some(patterns), _) => patterns.iter().filter_map(|pattern| pattern.as_str()).collect(),
+      (None, Some(pattern)) => vec![pattern],
+      (None, None) => return Self::Unspecified,
+    };
+    let regexes = patterns
+      .into_iter()
+      .map(|pattern| {
+        let pattern = pattern.trim_start_matches("./");
+        if pattern.contains('/') {
+          glob_to_regex(pattern)
+        } else {
+          glob_to_regex(&format!("**/{pattern}"))
+        }
+      })
+      .collect::<Option<Vec<_>>>();
+    // A malformed glob might have been meant to match any module, so the package keeps its side effects.
+    regexes.map_or(Self::Boolean(true), Self::Globs)
+  }
+
+  fn matches(&self, relative_path: &str) -> Option<bool> {
+    match self {
+      Self::Unspecified => None,
+      Self::Boolean(value) => Some(*value),
+      Self::Globs(regexes) => Some(regexes.iter().any(|regex| regex.is_match(relative_path))),
+    }
+  }
+}
+
+/// Supports `*`, `**`, `?` and `{a,b}`. Returns `None` for globs that can't be converted, like unbalanced
+/// or nested braces.
+fn glob_to_regex(glob: &str) -> Option<Regex> {
+  let mut source = String::from("^");
+  let mut chars = glob.chars().peekable();
+  let mut in_braces = false;
+  while let Some(c) = chars.next() {
+    match c {
+      '*' if chars.peek() == Some(&'*') => {
+        chars.next();
+        if chars.peek() == Some(&'/') {
+          chars.next();
+          source.push_str("(?:.*/)?");
+        } else {
+          source.push_str(".*");
+        }
+      }
+      '*' => source.push_str("[^/]*"),
+      '?' => source.push_str("[^/]"),
+      '{' => {
+        in_braces = true;
+        source.push_str("(?:");
+      }
+      '}' if in_braces => {
+        in_braces = false;
+        source.push(')');
+      }
+      ',' if in_braces => source.push('|'),
+      _ => source.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+    }
+  }
+  source.push('$');
+  Regex::new(&source).ok()
+}
+
+fn build_resolve_ret(
+  path: String,
+  ignored: bool,
+  module_type: ModuleType,
+  side_effects: Option<bool>,
+) -> ResolveRet {
+  ResolveRet { resolved: ResolvedPath { path: path.into(), ignored }, module_type, side_effects }
+}
+
+#[test]
+fn test_glob_to_regex() {
+  let is_match =
+    |glob: &str, path: &str| glob_to_regex(glob).expect("Should be valid").is_match(path);
+  assert!(is_match("**/*.css", "style.css"));
+  assert!(is_match("**/*.css", "src/style.css"));
+  assert!(!is_match("src/*.js", "src/lib/index.js"));
+  assert!(is_match("src/**/*.{js,mjs}", "src/lib/index.mjs"));
+  assert!(is_match("polyfill?.js", "polyfill2.js"));
+  assert!(!is_match("index.js", "index_js"));
+  assert!(glob_to_regex("*.{css").is_none());
+  assert!(glob_to_regex("{a,{b,c}}").is_none());
 }
@@ -0,0 +1,138 @@
+use oxc::sourcemap::SourceMap;
+use serde::{Deserialize, Serialize};
+
+/// Fields that are part of the emitted JSON of a source map, but aren't stored by `SourceMap`.
+#[derive(Debug, Default)]
+pub struct SourceMapExtensions {
+  /// Indices of the sources listed in `x_google_ignoreList`, which tells devtools to skip them while
+  /// debugging.
+  pub ignore_list: Vec<u32>,
+  /// The `debugId` of the TC39 debug ID proposal, also referenced by the `//# debugId=` comment of the code.
+  pub debug_id: Option<String>,
+}
+
+impl SourceMapExtensions {
+  fn is_empty(&self) -> bool {
+    self.ignore_list.is_empty() && self.debug_id.is_none()
+  }
+}
+
+/// The JSON representation of a source map, with the fields of `SourceMapExtensions` in addition to the ones
+/// emitted by `SourceMap::to_json_string`, in the same order.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSourceMap<'a> {
+  version: u32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  file: Option<&'a str>,
+  names: Vec<&'a str>,
+  sources: Vec<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sources_content: Option<Vec<&'a str>>,
+  mappings: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  debug_id: Option<&'a str>,
+  #[serde(rename = "x_google_ignoreList", skip_serializing_if = "<[u32]>::is_empty")]
+  x_google_ignore_list: &'a [u32],
+}
+
+#[derive(Deserialize)]
+struct JsonMappings {
+  mappings: String,
+}
+
+/// The VLQ encoded `mappings` of `sourcemap`, which `SourceMap` only exposes as part of its JSON.
+fn encode_mappings(sourcemap: &SourceMap) -> String {
+  let tokens_only =
+    SourceMap::new(None, vec![], vec![], None, sourcemap.get_tokens().cloned().collect(), None);
+  serde_json::from_str::<JsonMappings>(&tokens_only.to_json_string())
+    .expect("the JSON of a source map without names and sources should be valid")
+    .mappings
+}
+
+pub fn to_json_string_with_extensions(
+  sourcemap: &SourceMap,
+  extensions: &SourceMapExtensions,
+) -> String {
+  if extensions.is_empty() {
+    return sourcemap.to_json_string();
+  }
+  let json_sourcemap = JsonSourceMap {
+    version: 3,
+    file: sourcemap.get_file(),
+    names: sourcemap.get_names().collect(),
+    sources: sourcemap.get_sources().collect(),
+    sources_content: sourcemap.get_source_contents().map(Iterator::collect),
+    mappings: encode_mappings(sourcemap),
+    debug_id: extensions.debug_id.as_deref(),
+    x_google_ignore_list: &extensions.ignore_list,
+  };
+  serde_json::to_string(&json_sourcemap).expect("source maps should be serializable")
+}
+
+pub fn to_data_url_with_extensions(
+  sourcemap: &SourceMap,
+  extensions: &SourceMapExtensions,
+) -> String {
+  if extensions.is_empty() {
+    return sourcemap.to_data_url();
+  }
+  let json = to_json_string_with_extensions(sourcemap, extensions);
+  let base64 = base64_simd::Base64::STANDARD.encode_to_boxed_str(json.as_bytes());
+  format!("data:application/json;charset=utf-8;base64,{base64}")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{SourceMap, SourceMapExtensions};
+
+  #[test]
+  fn extensions_are_appended() {
+    let sourcemap = SourceMap::from_json_string(
+      r#"{
+        "mappings": "AAAA",
+        "names": [],
+        "sources": ["node_modules/lib/index.js", "main.js"],
+        "version": 3
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      super::to_json_string_with_extensions(
+        &sourcemap,
+        &SourceMapExtensions {
+          ignore_list: vec![0],
+          debug_id: Some("85314830-023f-4cf1-a267-535f4e37bb17".to_string()),
+        }
+      ),
+      "{\"version\":3,\"names\":[],\"sources\":[\"node_modules/lib/index.js\",\"main.js\"],\"mappings\":\"AAAA\",\"debugId\":\"85314830-023f-4cf1-a267-535f4e37bb17\",\"x_google_ignoreList\":[0]}"
+    );
+    assert_eq!(
+      super::to_json_string_with_extensions(&sourcemap, &SourceMapExtensions::default()),
+      sourcemap.to_json_string()
+    );
+  }
+
+  #[test]
+  fn extensions_keep_the_json_valid() {
+    let sourcemap = SourceMap::from_json_string(
+      r#"{
+        "mappings": "AAAA",
+        "names": [],
+        "sources": ["main.js"],
+        "sourcesContent": ["console.log(\"\u001b[1m\")\n"],
+        "version": 3
+      }"#,
+    )
+    .unwrap();
+
+    let json = super::to_json_string_with_extensions(
+      &sourcemap,
+      &SourceMapExtensions { ignore_list: vec![0], debug_id: None },
+    );
+    let parsed = SourceMap::from_json_string(&json).unwrap();
+    assert_eq!(parsed.get_source_content(0), Some("console.log(\"\u{1b}[1m\")\n"));
+    assert!(json.ends_with(",\"x_google_ignoreList\":[0]}"));
+  }
+}
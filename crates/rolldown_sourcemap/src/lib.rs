@@ -1,11 +1,80 @@
 // cSpell:disable
-pub use concat_sourcemap::{ConcatSource, RawSource, SourceMapSource};
+pub use concat_sourcemap::{ConcatSource, RawSource, Source, SourceMapSource};
+pub use json::{to_data_url_with_extensions, to_json_string_with_extensions, SourceMapExtensions};
 pub use oxc::sourcemap::SourceMap;
 
-use oxc::sourcemap::SourceMapBuilder;
+use std::sync::Arc;
+
+use oxc::sourcemap::{SourceMapBuilder, Token};
 use rolldown_error::BuildError;
+use rustc_hash::FxHashMap;
 
 mod concat_sourcemap;
+mod json;
+
+/// Returns `sourcemap` with its `sources` replaced by `sources`, which are expected to be in the same order.
+/// `sourcesContent` is dropped if `exclude_sources_content` is true.
+pub fn replace_sources(
+  sourcemap: &SourceMap,
+  sources: Vec<String>,
+  exclude_sources_content: bool,
+) -> SourceMap {
+  let sources_content = if exclude_sources_content {
+    None
+  } else {
+    sourcemap.get_source_contents().map(|contents| contents.map(Into::into).collect())
+  };
+  SourceMap::new(
+    sourcemap.get_file().map(Into::into),
+    sourcemap.get_names().map(Into::into).collect(),
+    sources.into_iter().map(Into::into).collect(),
+    sources_content,
+    sourcemap.get_tokens().cloned().collect(),
+    None,
+  )
+}
+
+/// Names the tokens of `sourcemap` that don't have a name yet with `name_for(src_line, src_col)`, which
+/// returns the original name of the identifier at that position, if it was renamed.
+pub fn fill_token_names(
+  sourcemap: &SourceMap,
+  mut name_for: impl FnMut(u32, u32) -> Option<String>,
+) -> SourceMap {
+  let mut names = sourcemap.get_names().map(Into::into).collect::<Vec<Arc<str>>>();
+  let mut name_to_id =
+    (0u32..).zip(&names).map(|(id, name)| (Arc::clone(name), id)).collect::<FxHashMap<_, _>>();
+  let tokens = sourcemap
+    .get_tokens()
+    .map(|token| {
+      if token.get_name_id().is_some() {
+        return token.clone();
+      }
+      let name_id = name_for(token.get_src_line(), token.get_src_col()).map(|name| {
+        let name: Arc<str> = name.into();
+        *name_to_id.entry(Arc::clone(&name)).or_insert_with(|| {
+          names.push(name);
+          u32::try_from(names.len() - 1).expect("too many names")
+        })
+      });
+      Token::new(
+        token.get_dst_line(),
+        token.get_dst_col(),
+        token.get_src_line(),
+        token.get_src_col(),
+        token.get_source_id(),
+        name_id,
+      )
+    })
+    .collect();
+  SourceMap::new(
+    sourcemap.get_file().map(Into::into),
+    names,
+    sourcemap.get_sources().map(Into::into).collect(),
+    sourcemap.get_source_contents().map(|contents| contents.map(Into::into).collect()),
+    tokens,
+    None,
+  )
+}
 
 pub fn collapse_sourcemaps(
   mut sourcemap_chain: Vec<&SourceMap>,
@@ -32,13 +101,13 @@ pub fn collapse_sourcemaps(
     );
 
     if let Some(original_token) = original_token {
-      token
-        .get_name_id()
-        .and_then(|id| last_map.get_name(id))
+      // Prefer the name in the original source, but keep the one of the last map if there is none, e.g. for
+      // identifiers renamed while printing a transformed module.
+      let name_id = original_token
+        .get_name()
+        .or_else(|| token.get_name_id().and_then(|id| last_map.get_name(id)))
         .map(|name| sourcemap_builder.add_name(name));
 
-      let name_id = original_token.get_name().map(|name| sourcemap_builder.add_name(name));
-
       let source_id = original_token.get_source_and_content().map(|(source, source_content)| {
         sourcemap_builder.add_source_and_content(source, source_content)
       });
@@ -94,8 +163,29 @@ mod tests {
       map.to_json_string()
     };
 
-    let expected = "{\"version\":3,\"names\":[\"add\"],\"sources\":[\"helloworld.js\"],\"sourcesContent\":[\"\\n\\n  1 + 1;\"],\"mappings\":\"AAEE\"}";
+    let expected = "{\"version\":3,\"names\":[\"add\"],\"sources\":[\"helloworld.js\"],\"sourcesContent\":[\"\\n\\n  1 + 1;\"],\"mappings\":\"AAEEA\"}";
 
     assert_eq!(&result, expected);
   }
+
+  #[test]
+  fn fill_token_names() {
+    // `foo$1(foo)`, where only the callee `foo$1` was renamed from `foo`.
+    let sourcemap = SourceMap::from_json_string(
+      r#"{
+        "mappings": "AAAA,MAAMA",
+        "names": ["foo"],
+        "sources": ["main.js"],
+        "version": 3
+      }"#,
+    )
+    .unwrap();
+
+    let map = super::fill_token_names(&sourcemap, |src_line, src_col| {
+      (src_line == 0 && src_col == 0).then(|| "foo".to_string())
+    });
+
+    assert_eq!(map.get_names().collect::<Vec<_>>(), ["foo"]);
+    assert_eq!(map.to_json_string(), sourcemap.to_json_string().replace("AAAA,", "AAAAA,"));
+  }
 }
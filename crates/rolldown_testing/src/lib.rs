@@ -1,3 +1,6 @@
 mod test_config;
 
-pub use test_config::{input_options::InputItem, TestConfig};
+pub use test_config::{
+  input_options::{InputItem, ModuleSideEffects, TreeshakeOptions},
+  EmittedAsset, TestConfig,
+};
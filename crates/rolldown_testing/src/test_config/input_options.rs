@@ -7,8 +7,9 @@ use std::collections::HashMap;
 pub struct InputOptions {
   pub input: Option<Vec<InputItem>>,
   pub external: Option<Vec<String>>,
-  pub treeshake: Option<bool>,
+  pub treeshake: Option<TreeshakeOptions>,
   pub resolve: Option<ResolveOptions>,
+  pub define: Option<HashMap<String, String>>,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -18,6 +19,27 @@ pub struct InputItem {
   pub import: String,
 }
 
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum TreeshakeOptions {
+  Boolean(bool),
+  Options(InnerTreeshakeOptions),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InnerTreeshakeOptions {
+  pub module_side_effects: Option<ModuleSideEffects>,
+  pub unknown_global_side_effects: Option<bool>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ModuleSideEffects {
+  Boolean(bool),
+  IdList(Vec<String>),
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct TsConfig {
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -38,6 +38,20 @@ pub struct TestConfig {
   #[serde(default)]
   /// If `true`, the fixture output stats will be snapshot.
   pub snapshot_output_stats: bool,
+  #[serde(default)]
+  /// Assets emitted by a plugin at the start of the build.
+  pub emitted_assets: Vec<EmittedAsset>,
+  #[serde(default)]
+  /// The `module_side_effects` returned by a plugin `load` hook, keyed by module paths relative to the fixture.
+  pub load_side_effects: HashMap<String, bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EmittedAsset {
+  pub name: Option<String>,
+  pub file_name: Option<String>,
+  pub source: String,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 use crate::impl_serde_default;
 
@@ -18,6 +19,61 @@ pub struct OutputOptions {
   pub format: String,
   #[serde(default = "auto_by_default")]
   pub export_mode: String,
+  pub name: Option<String>,
+  pub globals: Option<HashMap<String, String>>,
+  pub amd: Option<AmdOptions>,
+  /// Defaults to `[name].mjs`.
+  pub chunk_file_names: Option<String>,
+  pub asset_file_names: Option<String>,
+  /// Maps chunk names to module ids relative to the fixture.
+  pub manual_chunks: Option<HashMap<String, Vec<String>>>,
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  pub experimental_min_chunk_size: Option<usize>,
+  pub preserve_modules: Option<bool>,
+  pub preserve_modules_root: Option<String>,
+  pub inline_dynamic_imports: Option<bool>,
+  /// `file`, `inline` or `hidden`.
+  pub sourcemap: Option<String>,
+  pub sourcemap_file: Option<String>,
+  pub sourcemap_exclude_sources: Option<bool>,
+  pub sourcemap_debug_ids: Option<bool>,
+  pub banner: Option<String>,
+  pub footer: Option<String>,
+  pub minify: Option<MinifyOptions>,
 }
 
 impl_serde_default!(OutputOptions);
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AmdOptions {
+  pub id: Option<String>,
+  pub auto_id: Option<bool>,
+  pub define: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MinifyOptions {
+  pub whitespace: Option<bool>,
+  pub syntax: Option<bool>,
+  pub identifiers: Option<bool>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdvancedChunksOptions {
+  pub groups: Vec<ChunkGroupOptions>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChunkGroupOptions {
+  pub name: String,
+  /// A regex matched against module ids.
+  pub test: Option<String>,
+  pub priority: Option<i32>,
+  pub min_size: Option<usize>,
+  pub max_size: Option<usize>,
+  pub min_share_count: Option<u32>,
+}
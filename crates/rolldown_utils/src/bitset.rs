@@ -21,6 +21,20 @@ impl BitSet {
   pub fn is_empty(&self) -> bool {
     self.entries.iter().all(|&e| e == 0)
   }
+
+  pub fn count_ones(&self) -> u32 {
+    self.entries.iter().map(|e| e.count_ones()).sum()
+  }
+
+  /// Sets all bits that are set in `other`. Both sets should be created with the same `max_bit_count`.
+  pub fn union(&mut self, other: &Self) {
+    self.entries.iter_mut().zip(&other.entries).for_each(|(a, b)| *a |= b);
+  }
+
+  /// Whether every bit set in `other` is also set in `self`.
+  pub fn is_superset_of(&self, other: &Self) -> bool {
+    self.entries.iter().zip(&other.entries).all(|(a, b)| a & b == *b)
+  }
 }
 
 impl Display for BitSet {
@@ -63,4 +77,18 @@ mod tests {
     bs.set_bit(15);
     assert_eq!(bs.to_string(), "10000011_10000001");
   }
+
+  #[test]
+  fn union() {
+    let mut a = BitSet::new(9);
+    a.set_bit(0);
+    let mut b = BitSet::new(9);
+    b.set_bit(1);
+    b.set_bit(8);
+    a.union(&b);
+    assert_eq!(a.to_string(), "00000011_00000001");
+    assert_eq!(a.count_ones(), 3);
+    assert!(a.is_superset_of(&b));
+    assert!(!b.is_superset_of(&a));
+  }
 }
@@ -1,6 +1,7 @@
 // We keep some standalone utilities here
 
 mod bitset;
+pub mod xxhash;
 
 pub use bitset::BitSet;
 use std::future::Future;
@@ -0,0 +1,43 @@
+use base64_simd::Base64;
+use xxhash_rust::xxh3::xxh3_128;
+
+/// Hash `input` with xxh3-128 and encode the digest with the URL-safe base64 alphabet, so the result can be
+/// used in file names.
+pub fn xxhash_base64_url(input: &[u8]) -> String {
+  let hash = xxh3_128(input).to_le_bytes();
+  Base64::URL_SAFE_NO_PAD.encode_to_boxed_str(&hash).into_string()
+}
+
+/// Hash `input` with xxh3-128 and format the digest as a version 4 UUID, so the same input always results
+/// in the same UUID.
+pub fn xxhash_uuid(input: &[u8]) -> String {
+  let mut hash = xxh3_128(input).to_be_bytes();
+  hash[6] = (hash[6] & 0x0f) | 0x40;
+  hash[8] = (hash[8] & 0x3f) | 0x80;
+  let hex = format!("{:032x}", u128::from_be_bytes(hash));
+  format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{xxhash_base64_url, xxhash_uuid};
+
+  #[test]
+  fn test_xxhash_base64_url() {
+    let hash = xxhash_base64_url(b"hello");
+    assert_eq!(hash.len(), 22);
+    assert_eq!(hash, xxhash_base64_url(b"hello"));
+    assert_ne!(hash, xxhash_base64_url(b"world"));
+    assert!(hash.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+  }
+
+  #[test]
+  fn test_xxhash_uuid() {
+    let uuid = xxhash_uuid(b"hello");
+    assert_eq!(uuid, xxhash_uuid(b"hello"));
+    assert_ne!(uuid, xxhash_uuid(b"world"));
+    let groups = uuid.split('-').map(str::len).collect::<Vec<_>>();
+    assert_eq!(groups, [8, 4, 4, 4, 12]);
+    assert_eq!(&uuid[14..15], "4");
+  }
+}
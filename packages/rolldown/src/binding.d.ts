@@ -42,13 +42,41 @@ export class Bundler {
   scan(): Promise<void>
 }
 
+export interface BindingAdvancedChunksOptions {
+  groups: Array<BindingChunkGroupOptions>
+}
+
+export interface BindingAmdOptions {
+  id?: string
+  autoId?: boolean
+  define?: string
+}
+
+export interface BindingMinifyOptions {
+  whitespace?: boolean
+  syntax?: boolean
+  identifiers?: boolean
+}
+
+export interface BindingChunkGroupOptions {
+  name: string
+  /** The source of a regex matched against module ids. */
+  test?: string
+  priority?: number
+  minSize?: number
+  maxSize?: number
+  minShareCount?: number
+}
+
 export interface BindingHookLoadOutput {
   code: string
   map?: string
+  moduleSideEffects?: boolean
 }
 
 export interface BindingHookRenderChunkOutput {
   code: string
+  map?: string
 }
 
 export interface BindingHookResolveIdExtraOptions {
@@ -59,6 +87,7 @@ export interface BindingHookResolveIdExtraOptions {
 export interface BindingHookResolveIdOutput {
   id: string
   external?: boolean
+  moduleSideEffects?: boolean
 }
 
 export interface BindingInputItem {
@@ -67,6 +96,7 @@ export interface BindingInputItem {
 }
 
 export interface BindingInputOptions {
+  define?: Record<string, string>
   external?:
     | undefined
     | ((
@@ -77,23 +107,41 @@ export interface BindingInputOptions {
   input: Array<BindingInputItem>
   plugins: Array<BindingPluginOptions>
   resolve?: BindingResolveOptions
+  /** Tree shaking is disabled if it is omitted. */
+  treeshake?: BindingTreeshakeOptions
   cwd: string
 }
 
 export interface BindingOutputOptions {
-  entryFileNames?: string
-  chunkFileNames?: string
+  entryFileNames?: (chunk: PreRenderedChunk) => MaybePromise<string>
+  chunkFileNames?: (chunk: PreRenderedChunk) => MaybePromise<string>
+  amd?: BindingAmdOptions
+  assetFileNames?: string
   banner?:
     | Nullable<string>
     | ((chunk: RenderedChunk) => MaybePromise<VoidNullable<string>>)
   dir?: string
   exports?: 'default' | 'named' | 'none' | 'auto'
+  experimentalMinChunkSize?: number
   footer?:
     | Nullable<string>
     | ((chunk: RenderedChunk) => MaybePromise<VoidNullable<string>>)
-  format?: 'es' | 'cjs'
+  format?: 'es' | 'cjs' | 'iife' | 'umd' | 'system' | 'amd'
+  globals?: Record<string, string>
+  inlineDynamicImports?: boolean
+  manualChunks?: (id: string) => MaybePromise<VoidNullable<string>>
+  advancedChunks?: BindingAdvancedChunksOptions
+  name?: string
+  minify?: BindingMinifyOptions
   plugins: Array<BindingPluginOptions>
+  preserveModules?: boolean
+  preserveModulesRoot?: string
   sourcemap?: 'file' | 'inline' | 'hidden'
+  sourcemapDebugIds?: boolean
+  sourcemapExcludeSources?: boolean
+  sourcemapFile?: string
+  sourcemapIgnoreList?: (source: string, sourcemapPath: string) => MaybePromise<boolean>
+  sourcemapPathTransform?: (source: string, sourcemapPath: string) => MaybePromise<string>
 }
 
 export interface BindingPluginContextResolveOptions {
@@ -141,6 +189,21 @@ export interface BindingResolveOptions {
   symlinks?: boolean
 }
 
+export interface BindingTreeshakeOptions {
+  moduleSideEffects?: boolean | string[]
+  /** Takes precedence over `moduleSideEffects`. */
+  moduleSideEffectsFn?: (id: string) => MaybePromise<VoidNullable<boolean>>
+  unknownGlobalSideEffects?: boolean
+}
+
+export interface PreRenderedChunk {
+  isEntry: boolean
+  isDynamicEntry: boolean
+  facadeModuleId?: string
+  moduleIds: Array<string>
+  exports: Array<string>
+}
+
 export interface RenderedChunk {
   isEntry: boolean
   isDynamicEntry: boolean
@@ -1,4 +1,8 @@
-import { RolldownOutput, RolldownOutputChunk } from './types/rolldown-output'
+import {
+  RolldownOutput,
+  RolldownOutputAsset,
+  RolldownOutputChunk,
+} from './types/rolldown-output'
 import type { InputOptions } from './options/input-options'
 import type { OutputOptions } from './options/output-options'
 import type { RolldownOptions } from './types/rolldown-options'
@@ -9,6 +13,7 @@ import { rolldown, experimental_scan } from './rolldown'
 export { defineConfig, rolldown, experimental_scan }
 
 export type {
+  RolldownOutputAsset,
   RolldownOutputChunk,
   RolldownOptions,
   RolldownOutput,
@@ -17,6 +17,8 @@ export function createInputOptionsAdapter(
     cwd: inputOptions.cwd ?? process.cwd(),
     external: inputOptions.external ? options.external : undefined,
     resolve: options.resolve,
+    define: options.define,
+    treeshake: options.treeshake,
   }
 }
 
@@ -3,8 +3,9 @@ import {
   InputOptions as RollupInputOptions,
 } from '../rollup-types'
 import { ensureArray, normalizePluginOption } from '../utils'
-import { BindingResolveOptions } from '../binding'
+import { BindingResolveOptions, BindingTreeshakeOptions } from '../binding'
 import { Plugin } from '../plugin'
+import { MaybePromise } from '../types/utils'
 
 // TODO export compat plugin type
 export interface InputOptions {
@@ -13,14 +14,44 @@ export interface InputOptions {
   external?: RollupInputOptions['external']
   resolve?: RolldownResolveOptions
   cwd?: string
+  /**
+   * Replaces global expressions like `process.env.NODE_ENV` with the given JSON literals or references.
+   */
+  define?: Record<string, string>
+  /**
+   * `false` disables tree shaking. `true` is the same as the default options.
+   */
+  treeshake?: boolean | TreeshakingOptions
+}
+
+export interface TreeshakingOptions {
+  /**
+   * Whether modules have side effects, for the modules the `resolveId`, `load` and `transform` hooks don't
+   * decide. It overrides the `sideEffects` field of package.json. Module ids in the list could be absolute
+   * paths or paths relative to `cwd`. A module without side effects is dropped if none of its exports are used.
+   */
+  moduleSideEffects?:
+    | boolean
+    | string[]
+    | ((id: string) => MaybePromise<boolean | null | undefined | void>)
+  /**
+   * Whether reading a global variable that isn't a known global of the language, like `window`, is a side
+   * effect. Defaults to `true`.
+   */
+  unknownGlobalSideEffects?: boolean
 }
 
 export type RolldownResolveOptions = Omit<BindingResolveOptions, 'alias'> & {
   alias?: Record<string, string>
 }
 
-export type RolldownNormalizedInputOptions = NormalizedInputOptions & {
+export type RolldownNormalizedInputOptions = Omit<
+  NormalizedInputOptions,
+  'treeshake'
+> & {
   resolve?: BindingResolveOptions
+  define?: Record<string, string>
+  treeshake?: BindingTreeshakeOptions
 }
 
 export async function normalizeInputOptions(
@@ -32,6 +63,8 @@ export async function normalizeInputOptions(
     plugins: await normalizePluginOption(config.plugins),
     external: getIdMatcher(config.external),
     resolve: getResolve(config.resolve),
+    define: config.define,
+    treeshake: getTreeshake(config.treeshake),
   }
 }
 
@@ -91,3 +124,18 @@ function getResolve(
     }
   }
 }
+
+function getTreeshake(
+  treeshake: InputOptions['treeshake'],
+): RolldownNormalizedInputOptions['treeshake'] {
+  if (treeshake === false) {
+    return undefined
+  }
+  if (treeshake === true || treeshake == null) {
+    return {}
+  }
+  const { moduleSideEffects, unknownGlobalSideEffects } = treeshake
+  return typeof moduleSideEffects === 'function'
+    ? { moduleSideEffectsFn: moduleSideEffects, unknownGlobalSideEffects }
+    : { moduleSideEffects, unknownGlobalSideEffects }
+}
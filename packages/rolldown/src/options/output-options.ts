@@ -1,20 +1,69 @@
 import { OutputOptions as RollupOutputOptions } from '../rollup-types'
-import { BindingOutputOptions } from '../binding'
+import { BindingOutputOptions, PreRenderedChunk } from '../binding'
+import nodePath from 'node:path'
 import { unimplemented } from '../utils'
+import { MaybePromise } from '../types/utils'
 
 export interface OutputOptions {
   dir?: RollupOutputOptions['dir']
-  format?: 'es'
+  format?: 'es' | 'iife' | 'umd' | 'system' | 'amd'
   exports?: RollupOutputOptions['exports']
   sourcemap?: RollupOutputOptions['sourcemap']
+  sourcemapFile?: RollupOutputOptions['sourcemapFile']
+  sourcemapIgnoreList?: RollupOutputOptions['sourcemapIgnoreList']
+  sourcemapPathTransform?: RollupOutputOptions['sourcemapPathTransform']
+  sourcemapExcludeSources?: RollupOutputOptions['sourcemapExcludeSources']
+  sourcemapDebugIds?: boolean
   banner?: RollupOutputOptions['banner']
   footer?: RollupOutputOptions['footer']
+  entryFileNames?: string | ((chunk: PreRenderedChunk) => MaybePromise<string>)
+  chunkFileNames?: string | ((chunk: PreRenderedChunk) => MaybePromise<string>)
+  assetFileNames?: string
+  name?: RollupOutputOptions['name']
+  globals?: Record<string, string>
+  amd?: {
+    id?: string
+    autoId?: boolean
+    define?: string
+  }
+  manualChunks?:
+    | Record<string, string[]>
+    | ((id: string) => MaybePromise<string | null | undefined | void>)
+  advancedChunks?: {
+    groups: {
+      name: string
+      test?: string | RegExp
+      priority?: number
+      minSize?: number
+      maxSize?: number
+      minShareCount?: number
+    }[]
+  }
+  experimentalMinChunkSize?: RollupOutputOptions['experimentalMinChunkSize']
+  preserveModules?: RollupOutputOptions['preserveModules']
+  preserveModulesRoot?: RollupOutputOptions['preserveModulesRoot']
+  inlineDynamicImports?: RollupOutputOptions['inlineDynamicImports']
+  minify?:
+    | boolean
+    | {
+        whitespace?: boolean
+        syntax?: boolean
+        identifiers?: boolean
+      }
 }
 
 function normalizeFormat(
   format: OutputOptions['format'],
 ): BindingOutputOptions['format'] {
-  if (format == null || format === 'es' || format === 'cjs') {
+  if (
+    format == null ||
+    format === 'es' ||
+    format === 'cjs' ||
+    format === 'iife' ||
+    format === 'umd' ||
+    format === 'system' ||
+    format === 'amd'
+  ) {
     return format
   } else {
     return unimplemented(`output.format: ${format}`)
@@ -31,16 +80,43 @@ function normalizeSourcemap(
     case 'inline':
       return 'inline'
 
-    case false:
-    case undefined:
     case 'hidden':
       return 'hidden'
 
+    case false:
+    case undefined:
+      return undefined
+
     default:
       throw new Error(`unknown sourcemap: ${sourcemap}`)
   }
 }
 
+function normalizeSourcemapIgnoreList(
+  sourcemapIgnoreList: OutputOptions['sourcemapIgnoreList'],
+): BindingOutputOptions['sourcemapIgnoreList'] {
+  // `true` and `undefined` keep the default, which ignores sources from `node_modules`.
+  if (sourcemapIgnoreList === true || sourcemapIgnoreList === undefined) {
+    return undefined
+  }
+  if (sourcemapIgnoreList === false) {
+    return () => false
+  }
+  return sourcemapIgnoreList
+}
+
+function normalizeMinify(
+  minify: OutputOptions['minify'],
+): BindingOutputOptions['minify'] {
+  if (minify === true) {
+    return { whitespace: true, syntax: true, identifiers: true }
+  }
+  if (minify === false || minify === undefined) {
+    return undefined
+  }
+  return minify
+}
+
 const getAddon = <T extends 'banner' | 'footer'>(
   config: OutputOptions,
   name: T,
@@ -53,17 +129,80 @@ const getAddon = <T extends 'banner' | 'footer'>(
   return () => configAddon || ''
 }
 
+const getFileNames = <T extends 'entryFileNames' | 'chunkFileNames'>(
+  config: OutputOptions,
+  name: T,
+): BindingOutputOptions[T] => {
+  const configFileNames = config[name]
+  if (configFileNames === undefined) return undefined
+  if (typeof configFileNames === 'function') {
+    return configFileNames
+  }
+  return () => configFileNames
+}
+
+function normalizeManualChunks(
+  manualChunks: OutputOptions['manualChunks'],
+  cwd: string,
+): BindingOutputOptions['manualChunks'] {
+  if (manualChunks === undefined || typeof manualChunks === 'function') {
+    return manualChunks
+  }
+  // Module ids in the object form could be absolute paths or paths relative to `cwd`.
+  const idToName = new Map<string, string>()
+  for (const [name, ids] of Object.entries(manualChunks)) {
+    for (const id of ids) {
+      const resolved = nodePath.resolve(cwd, id)
+      if (!idToName.has(resolved)) {
+        idToName.set(resolved, name)
+      }
+    }
+  }
+  return (id) => idToName.get(id)
+}
+
+function normalizeAdvancedChunks(
+  advancedChunks: OutputOptions['advancedChunks'],
+): BindingOutputOptions['advancedChunks'] {
+  if (advancedChunks === undefined) return undefined
+  return {
+    groups: advancedChunks.groups.map(({ test, ...group }) => ({
+      ...group,
+      test: test instanceof RegExp ? test.source : test,
+    })),
+  }
+}
+
 export function normalizeOutputOptions(
   opts: OutputOptions,
+  cwd: string,
 ): BindingOutputOptions {
-  const { dir, format, exports, sourcemap } = opts
+  const { dir, format, exports, sourcemap, name, globals, amd } = opts
   return {
     dir: dir,
     format: normalizeFormat(format),
     exports,
+    name,
+    globals,
+    amd,
+    manualChunks: normalizeManualChunks(opts.manualChunks, cwd),
+    advancedChunks: normalizeAdvancedChunks(opts.advancedChunks),
+    experimentalMinChunkSize: opts.experimentalMinChunkSize,
+    preserveModules: opts.preserveModules,
+    preserveModulesRoot: opts.preserveModulesRoot,
+    inlineDynamicImports: opts.inlineDynamicImports,
+    minify: normalizeMinify(opts.minify),
     sourcemap: normalizeSourcemap(sourcemap),
+    sourcemapFile: opts.sourcemapFile,
+    sourcemapIgnoreList: normalizeSourcemapIgnoreList(opts.sourcemapIgnoreList),
+    sourcemapPathTransform: opts.sourcemapPathTransform,
+    sourcemapExcludeSources: opts.sourcemapExcludeSources,
+    sourcemapDebugIds: opts.sourcemapDebugIds,
     plugins: [],
     banner: getAddon(opts, 'banner'),
     footer: getAddon(opts, 'footer'),
+    entryFileNames: getFileNames(opts, 'entryFileNames'),
+    chunkFileNames: getFileNames(opts, 'chunkFileNames'),
+    assetFileNames: opts.assetFileNames,
   }
 }
@@ -58,7 +58,11 @@ export function bindingifyResolveId(
         id: ret,
       }
     }
-    return ret
+    return {
+      id: ret.id,
+      external: ret.external,
+      moduleSideEffects: ret.moduleSideEffects ?? undefined,
+    }
   }
 }
 
@@ -83,6 +87,10 @@ export function bindingifyTransform(
     return {
       code: retCode,
       map: retMap ?? undefined,
+      moduleSideEffects:
+        typeof ret === 'string'
+          ? undefined
+          : ret.moduleSideEffects ?? undefined,
     }
   }
 }
@@ -108,6 +116,10 @@ export function bindingifyLoad(
     return {
       code: retCode,
       map: retMap ?? undefined,
+      moduleSideEffects:
+        typeof ret === 'string'
+          ? undefined
+          : ret.moduleSideEffects ?? undefined,
     }
   }
 }
@@ -127,8 +139,12 @@ export function bindingifyRenderChunk(
       return
     }
 
+    const retCode = typeof ret === 'string' ? ret : ret.code
+    const retMap = typeof ret === 'string' ? undefined : ret.map
+
     return {
-      code: ret,
+      code: retCode,
+      map: retMap ?? undefined,
     }
   }
 }
@@ -45,6 +45,7 @@ export interface Plugin {
       | {
           id: string
           external?: boolean
+          moduleSideEffects?: boolean | null
         }
     >
   >
@@ -54,7 +55,13 @@ export interface Plugin {
       this: null,
       id: string,
     ) => MaybePromise<
-      NullValue | string | { code: string; map?: string | null }
+      | NullValue
+      | string
+      | {
+          code: string
+          map?: string | null
+          moduleSideEffects?: boolean | null
+        }
     >
   >
 
@@ -69,6 +76,7 @@ export interface Plugin {
       | {
           code: string
           map?: string | null
+          moduleSideEffects?: boolean | null
         }
     >
   >
@@ -78,7 +86,14 @@ export interface Plugin {
       this: null,
       code: string,
       chunk: RenderedChunk,
-    ) => MaybePromise<NullValue | string>
+    ) => MaybePromise<
+      | NullValue
+      | string
+      | {
+          code: string
+          map?: string | null
+        }
+    >
   >
 
   buildEnd?: Hook<(this: null, err?: string) => MaybePromise<NullValue>>
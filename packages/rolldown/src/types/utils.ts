@@ -5,3 +5,5 @@ export interface AnyFn {
 export interface AnyObj {}
 
 export type NullValue<T = void> = T | undefined | null | void
+
+export type MaybePromise<T> = T | Promise<T>
@@ -20,5 +20,8 @@ export async function createBundler(
     normalizedInputOptions,
     inputOptions,
   )
-  return new Bundler(bindingInputOptions, normalizeOutputOptions(outputOptions))
+  return new Bundler(
+    bindingInputOptions,
+    normalizeOutputOptions(outputOptions, bindingInputOptions.cwd),
+  )
 }
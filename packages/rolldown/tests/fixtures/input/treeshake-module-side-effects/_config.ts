@@ -0,0 +1,16 @@
+import type { RolldownOutputChunk } from 'rolldown'
+import { defineTest } from '@tests'
+import { expect } from 'vitest'
+
+export default defineTest({
+  config: {
+    treeshake: {
+      moduleSideEffects: (id) => id.endsWith('kept.js'),
+    },
+  },
+  afterTest: (output) => {
+    const main = output.output[0] as RolldownOutputChunk
+    expect(main.code).toContain('keptLoaded')
+    expect(main.code).not.toContain('droppedLoaded')
+  },
+})
@@ -0,0 +1 @@
+globalThis.droppedLoaded = true
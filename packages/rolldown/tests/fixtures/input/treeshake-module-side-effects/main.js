@@ -0,0 +1,2 @@
+import './kept'
+import './dropped'
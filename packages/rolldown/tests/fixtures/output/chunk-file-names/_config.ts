@@ -0,0 +1,16 @@
+import { defineTest } from '@tests'
+import { expect } from 'vitest'
+
+export default defineTest({
+  config: {
+    output: {
+      chunkFileNames: (chunk) =>
+        chunk.exports.includes('foo') ? 'foo-[hash].js' : '[name]-[hash].js',
+    },
+  },
+  afterTest: (output) => {
+    expect(
+      output.output.some(({ fileName }) => /^foo-[\w-]{8}\.js$/.test(fileName)),
+    ).toBe(true)
+  },
+})
@@ -0,0 +1,18 @@
+import { defineTest } from '@tests'
+import { expect } from 'vitest'
+
+export default defineTest({
+  config: {
+    output: {
+      entryFileNames: async (chunk) =>
+        chunk.facadeModuleId?.endsWith('main.js')
+          ? 'entries/[name].js'
+          : '[name].js',
+    },
+  },
+  afterTest: (output) => {
+    expect(output.output.map(({ fileName }) => fileName)).toContain(
+      'entries/main.js',
+    )
+  },
+})
@@ -0,0 +1,17 @@
+import type { RolldownOutputChunk } from 'rolldown'
+import { defineTest } from '@tests'
+import { expect } from 'vitest'
+
+export default defineTest({
+  config: {
+    output: {
+      manualChunks: (id) => (id.includes('node_modules') ? 'vendor' : null),
+    },
+  },
+  afterTest: (output) => {
+    const vendor = output.output.find(
+      (chunk) => chunk.type === 'chunk' && chunk.fileName.startsWith('vendor-'),
+    ) as RolldownOutputChunk | undefined
+    expect(vendor?.code).toContain('lib')
+  },
+})
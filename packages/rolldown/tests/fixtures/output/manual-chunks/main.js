@@ -0,0 +1,3 @@
+import { lib } from 'lib'
+
+console.log(lib)
@@ -0,0 +1,21 @@
+import type { RolldownOutputAsset } from 'rolldown'
+import { defineTest } from '@tests'
+import { expect } from 'vitest'
+
+export default defineTest({
+  config: {
+    output: {
+      sourcemap: true,
+      sourcemapIgnoreList: (source) => source.endsWith('foo.js'),
+      sourcemapPathTransform: (source) => source.replace('../', 'src/'),
+    },
+  },
+  afterTest: (output) => {
+    const asset = output.output.find(
+      (asset) => asset.type === 'asset' && asset.fileName.endsWith('.map'),
+    ) as RolldownOutputAsset
+    const map = JSON.parse(asset.source as string)
+    expect(map.sources).toStrictEqual(['src/foo.js', 'src/main.js'])
+    expect(map.x_google_ignoreList).toStrictEqual([0])
+  },
+})
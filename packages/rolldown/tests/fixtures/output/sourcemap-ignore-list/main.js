@@ -0,0 +1,3 @@
+import { value } from './foo'
+
+console.log(value)